@@ -23,6 +23,49 @@ pub enum ContractError {
     ApplicationAlreadyExists(String),
     /// Invalid input parameters
     InvalidParams(String),
+    /// Program is not active, so takes no applications
+    ProgramNotAccepting { program_id: String, status: String },
+    /// Program's application deadline has passed
+    DeadlinePassed(String),
+    /// Requested amount is outside the program's min/max bounds
+    AmountOutOfBounds {
+        requested: u128,
+        min: Option<u128>,
+        max: Option<u128>,
+    },
+    /// Reviewer is a member of the project under review
+    ConflictOfInterest {
+        reviewer: String,
+        project_id: String,
+    },
+    /// Program escrow can't cover a payout
+    InsufficientEscrow {
+        program_id: String,
+        available: u128,
+        requested: u128,
+    },
+    /// Token is not whitelisted for program funding
+    TokenNotWhitelisted(String),
+    /// QF round not found by ID
+    RoundNotFound(String),
+    /// QF round already exists with this ID
+    RoundAlreadyExists(String),
+    /// QF round is outside its donation window, or already settled
+    RoundNotOpen(String),
+    /// Donor has not passed the round's sybil check
+    DonorNotVerified(String),
+    /// Account is already on the project's team or invited to it
+    AlreadyTeamMember(String),
+    /// Account is neither on the project's team nor invited to it
+    NotTeamMember(String),
+    /// Program has no review rubric
+    RubricNotSet(String),
+    /// Program or application status change not allowed
+    InvalidTransition { from: String, to: String },
+    /// Stored state has a layout version this code cannot migrate
+    UnsupportedStateVersion(u32),
+    /// Migration found no contract state
+    StateNotInitialized,
 }
 
 impl fmt::Display for ContractError {
@@ -39,6 +82,58 @@ impl fmt::Display for ContractError {
                 write!(f, "Application already exists: {id}")
             }
             Self::InvalidParams(msg) => write!(f, "Invalid parameters: {msg}"),
+            Self::ProgramNotAccepting { program_id, status } => {
+                write!(
+                    f,
+                    "Program not accepting applications: {program_id} is {status}"
+                )
+            }
+            Self::DeadlinePassed(id) => write!(f, "Program deadline has passed: {id}"),
+            Self::AmountOutOfBounds {
+                requested,
+                min,
+                max,
+            } => {
+                let bound = |b: &Option<u128>| b.map_or("-".to_string(), |b| b.to_string());
+                write!(
+                    f,
+                    "Requested amount {requested} outside program bounds [{}, {}]",
+                    bound(min),
+                    bound(max)
+                )
+            }
+            Self::ConflictOfInterest {
+                reviewer,
+                project_id,
+            } => {
+                write!(
+                    f,
+                    "Conflict of interest: {reviewer} is on the team of {project_id}"
+                )
+            }
+            Self::InsufficientEscrow {
+                program_id,
+                available,
+                requested,
+            } => write!(
+                f,
+                "Insufficient escrow for {program_id}: {available} available, {requested} requested"
+            ),
+            Self::TokenNotWhitelisted(id) => write!(f, "Token not whitelisted: {id}"),
+            Self::RoundNotFound(id) => write!(f, "QF round not found: {id}"),
+            Self::RoundAlreadyExists(id) => write!(f, "QF round already exists: {id}"),
+            Self::RoundNotOpen(msg) => write!(f, "QF round not open: {msg}"),
+            Self::DonorNotVerified(id) => write!(f, "Donor has no sybil-check credential: {id}"),
+            Self::AlreadyTeamMember(id) => write!(f, "Already a team member or invited: {id}"),
+            Self::NotTeamMember(id) => write!(f, "Not a team member: {id}"),
+            Self::RubricNotSet(id) => write!(f, "Program has no review rubric: {id}"),
+            Self::InvalidTransition { from, to } => {
+                write!(f, "Invalid status transition: {from} -> {to}")
+            }
+            Self::UnsupportedStateVersion(version) => {
+                write!(f, "Unsupported state version: {version}")
+            }
+            Self::StateNotInitialized => write!(f, "Contract state is not initialized"),
         }
     }
 }
@@ -61,4 +156,17 @@ mod tests {
         let err = ContractError::ProjectAlreadyExists("proj-1".into());
         assert!(err.to_string().contains("proj-1"));
     }
+
+    #[test]
+    fn amount_bounds_display() {
+        let err = ContractError::AmountOutOfBounds {
+            requested: 5,
+            min: Some(10),
+            max: None,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Requested amount 5 outside program bounds [10, -]"
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use near_sdk::serde::Serialize;
 use near_sdk::AccountId;
 
+use crate::types::{Payout, PayoutStatus, QfRound, Review, Rubric};
+
 const EVENT_STANDARD: &str = "grant-registry";
 const EVENT_VERSION: &str = "1.0.0";
 
@@ -51,21 +53,190 @@ struct ApplicationUpdatedData {
     updated_by: String,
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProgramUpdatedData {
+    program_id: String,
+    fields: Vec<String>,
+    updated_by: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProgramStatusChangedData {
+    program_id: String,
+    old_status: String,
+    new_status: String,
+    updated_by: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProgramTransferredData {
+    program_id: String,
+    old_steward: String,
+    new_steward: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProgramReviewerData {
+    program_id: String,
+    reviewer: String,
+    updated_by: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProgramRubricSetData {
+    program_id: String,
+    criteria: usize,
+    min_reviews: u32,
+    approve_threshold_bps: u32,
+    reject_threshold_bps: u32,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ReviewSubmittedData {
+    application_id: String,
+    reviewer: String,
+    score_bps: u32,
+    comment_hash: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct MilestonesSetData {
+    application_id: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct MilestoneEventData {
+    application_id: String,
+    index: u32,
+    status: String,
+    account: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProgramFundedData {
+    program_id: String,
+    token_id: Option<String>,
+    funder: String,
+    amount: String,
+    deposited: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct PayoutData {
+    payout_id: String,
+    token_id: Option<String>,
+    recipient: String,
+    amount: String,
+    status: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct FundsReclaimedData {
+    program_id: String,
+    token_id: Option<String>,
+    recipient: String,
+    amount: String,
+    success: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct PayoutTokenSetData {
+    program_id: String,
+    token_id: Option<String>,
+    updated_by: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct FtTokenWhitelistData {
+    token_id: String,
+    whitelisted: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct QfRoundCreatedData {
+    round_id: String,
+    program_id: String,
+    starts_at: u64,
+    ends_at: u64,
+    matching_pool: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct QfDonationData {
+    round_id: String,
+    application_id: String,
+    donor: String,
+    amount: String,
+    refunded: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct QfDonorVerifiedData {
+    round_id: String,
+    donor: String,
+    credential_id: String,
+    verified: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct QfRoundSettledData {
+    round_id: String,
+    applications: usize,
+    total_matched: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct TeamMemberData {
+    project_id: String,
+    account_id: String,
+    updated_by: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProjectOwnerData {
+    project_id: String,
+    old_owner: String,
+    new_owner: String,
+}
+
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 struct ContractPausedData {
     paused: bool,
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct StateMigratedData {
+    from_version: u32,
+    to_version: u32,
+}
+
 // ---------------------------------------------------------------------------
 // Public emit functions
 // ---------------------------------------------------------------------------
 
-pub fn emit_program_registered(
-    program_id: &str,
-    organization: &str,
-    registered_by: &AccountId,
-) {
+pub fn emit_program_registered(program_id: &str, organization: &str, registered_by: &AccountId) {
     emit(
         "program_registered",
         &ProgramRegisteredData {
@@ -76,6 +247,49 @@ pub fn emit_program_registered(
     );
 }
 
+pub fn emit_program_updated(program_id: &str, fields: Vec<String>, updated_by: &AccountId) {
+    emit(
+        "program_updated",
+        &ProgramUpdatedData {
+            program_id: program_id.to_string(),
+            fields,
+            updated_by: updated_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_program_status_changed(
+    program_id: &str,
+    old_status: &str,
+    new_status: &str,
+    updated_by: &AccountId,
+) {
+    emit(
+        "program_status_changed",
+        &ProgramStatusChangedData {
+            program_id: program_id.to_string(),
+            old_status: old_status.to_string(),
+            new_status: new_status.to_string(),
+            updated_by: updated_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_program_transferred(
+    program_id: &str,
+    old_steward: &AccountId,
+    new_steward: &AccountId,
+) {
+    emit(
+        "program_transferred",
+        &ProgramTransferredData {
+            program_id: program_id.to_string(),
+            old_steward: old_steward.to_string(),
+            new_steward: new_steward.to_string(),
+        },
+    );
+}
+
 pub fn emit_project_registered(project_id: &str, registered_by: &AccountId) {
     emit(
         "project_registered",
@@ -103,11 +317,7 @@ pub fn emit_application_recorded(
     );
 }
 
-pub fn emit_application_updated(
-    application_id: &str,
-    new_status: &str,
-    updated_by: &AccountId,
-) {
+pub fn emit_application_updated(application_id: &str, new_status: &str, updated_by: &AccountId) {
     emit(
         "application_updated",
         &ApplicationUpdatedData {
@@ -118,6 +328,294 @@ pub fn emit_application_updated(
     );
 }
 
+pub fn emit_program_reviewer_added(program_id: &str, reviewer: &AccountId, updated_by: &AccountId) {
+    emit(
+        "program_reviewer_added",
+        &ProgramReviewerData {
+            program_id: program_id.to_string(),
+            reviewer: reviewer.to_string(),
+            updated_by: updated_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_program_reviewer_removed(
+    program_id: &str,
+    reviewer: &AccountId,
+    updated_by: &AccountId,
+) {
+    emit(
+        "program_reviewer_removed",
+        &ProgramReviewerData {
+            program_id: program_id.to_string(),
+            reviewer: reviewer.to_string(),
+            updated_by: updated_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_program_rubric_set(program_id: &str, rubric: &Rubric) {
+    emit(
+        "program_rubric_set",
+        &ProgramRubricSetData {
+            program_id: program_id.to_string(),
+            criteria: rubric.criteria.len(),
+            min_reviews: rubric.min_reviews,
+            approve_threshold_bps: rubric.approve_threshold_bps,
+            reject_threshold_bps: rubric.reject_threshold_bps,
+        },
+    );
+}
+
+pub fn emit_review_submitted(application_id: &str, review: &Review) {
+    emit(
+        "review_submitted",
+        &ReviewSubmittedData {
+            application_id: application_id.to_string(),
+            reviewer: review.reviewer.to_string(),
+            score_bps: review.score_bps,
+            comment_hash: review.comment_hash.clone(),
+        },
+    );
+}
+
+pub fn emit_milestones_set(application_id: &str, count: usize) {
+    emit(
+        "milestones_set",
+        &MilestonesSetData {
+            application_id: application_id.to_string(),
+            count,
+        },
+    );
+}
+
+pub fn emit_milestone_submitted(application_id: &str, index: u32, applicant: &AccountId) {
+    emit(
+        "milestone_submitted",
+        &MilestoneEventData {
+            application_id: application_id.to_string(),
+            index,
+            status: "submitted".to_string(),
+            account: applicant.to_string(),
+        },
+    );
+}
+
+pub fn emit_milestone_reviewed(
+    application_id: &str,
+    index: u32,
+    approved: bool,
+    reviewer: &AccountId,
+) {
+    emit(
+        "milestone_reviewed",
+        &MilestoneEventData {
+            application_id: application_id.to_string(),
+            index,
+            status: if approved { "approved" } else { "rejected" }.to_string(),
+            account: reviewer.to_string(),
+        },
+    );
+}
+
+pub fn emit_program_funded(
+    program_id: &str,
+    token_id: Option<&AccountId>,
+    funder: &AccountId,
+    amount: u128,
+    deposited: u128,
+) {
+    emit(
+        "program_funded",
+        &ProgramFundedData {
+            program_id: program_id.to_string(),
+            token_id: token_id.map(|t| t.to_string()),
+            funder: funder.to_string(),
+            amount: amount.to_string(),
+            deposited: deposited.to_string(),
+        },
+    );
+}
+
+pub fn emit_payout(payout_id: &str, payout: &Payout) {
+    let status = match payout.status {
+        PayoutStatus::InFlight => "in-flight",
+        PayoutStatus::Paid => "paid",
+        PayoutStatus::Failed => "failed",
+    };
+    emit(
+        "payout",
+        &PayoutData {
+            payout_id: payout_id.to_string(),
+            token_id: payout.token_id.as_ref().map(|t| t.to_string()),
+            recipient: payout.recipient.to_string(),
+            amount: payout.amount.0.to_string(),
+            status: status.to_string(),
+        },
+    );
+}
+
+pub fn emit_funds_reclaimed(
+    program_id: &str,
+    token_id: Option<&AccountId>,
+    recipient: &AccountId,
+    amount: u128,
+    success: bool,
+) {
+    emit(
+        "funds_reclaimed",
+        &FundsReclaimedData {
+            program_id: program_id.to_string(),
+            token_id: token_id.map(|t| t.to_string()),
+            recipient: recipient.to_string(),
+            amount: amount.to_string(),
+            success,
+        },
+    );
+}
+
+pub fn emit_payout_token_set(
+    program_id: &str,
+    token_id: Option<&AccountId>,
+    updated_by: &AccountId,
+) {
+    emit(
+        "payout_token_set",
+        &PayoutTokenSetData {
+            program_id: program_id.to_string(),
+            token_id: token_id.map(|t| t.to_string()),
+            updated_by: updated_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_ft_token_whitelisted(token_id: &AccountId, whitelisted: bool) {
+    emit(
+        "ft_token_whitelisted",
+        &FtTokenWhitelistData {
+            token_id: token_id.to_string(),
+            whitelisted,
+        },
+    );
+}
+
+pub fn emit_qf_round_created(round: &QfRound) {
+    emit(
+        "qf_round_created",
+        &QfRoundCreatedData {
+            round_id: round.id.clone(),
+            program_id: round.program_id.clone(),
+            starts_at: round.starts_at,
+            ends_at: round.ends_at,
+            matching_pool: round.matching_pool.0.to_string(),
+        },
+    );
+}
+
+pub fn emit_qf_donation(
+    round_id: &str,
+    application_id: &str,
+    donor: &AccountId,
+    amount: u128,
+    refunded: bool,
+) {
+    emit(
+        if refunded {
+            "qf_donation_refunded"
+        } else {
+            "qf_donation"
+        },
+        &QfDonationData {
+            round_id: round_id.to_string(),
+            application_id: application_id.to_string(),
+            donor: donor.to_string(),
+            amount: amount.to_string(),
+            refunded,
+        },
+    );
+}
+
+pub fn emit_qf_donor_verified(
+    round_id: &str,
+    donor: &AccountId,
+    credential_id: &str,
+    verified: bool,
+) {
+    emit(
+        "qf_donor_verified",
+        &QfDonorVerifiedData {
+            round_id: round_id.to_string(),
+            donor: donor.to_string(),
+            credential_id: credential_id.to_string(),
+            verified,
+        },
+    );
+}
+
+pub fn emit_qf_round_settled(round_id: &str, applications: usize, total_matched: u128) {
+    emit(
+        "qf_round_settled",
+        &QfRoundSettledData {
+            round_id: round_id.to_string(),
+            applications,
+            total_matched: total_matched.to_string(),
+        },
+    );
+}
+
+/// `action` is one of `invited`, `joined`, `updated` or `removed`.
+pub fn emit_team_member(
+    action: &str,
+    project_id: &str,
+    account_id: &AccountId,
+    updated_by: &AccountId,
+) {
+    emit(
+        &format!("team_member_{action}"),
+        &TeamMemberData {
+            project_id: project_id.to_string(),
+            account_id: account_id.to_string(),
+            updated_by: updated_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_project_owner_proposed(project_id: &str, old_owner: &AccountId, new_owner: &AccountId) {
+    emit(
+        "project_owner_proposed",
+        &ProjectOwnerData {
+            project_id: project_id.to_string(),
+            old_owner: old_owner.to_string(),
+            new_owner: new_owner.to_string(),
+        },
+    );
+}
+
+pub fn emit_project_ownership_transferred(
+    project_id: &str,
+    old_owner: &AccountId,
+    new_owner: &AccountId,
+) {
+    emit(
+        "project_ownership_transferred",
+        &ProjectOwnerData {
+            project_id: project_id.to_string(),
+            old_owner: old_owner.to_string(),
+            new_owner: new_owner.to_string(),
+        },
+    );
+}
+
 pub fn emit_contract_paused(paused: bool) {
     emit("contract_paused", &ContractPausedData { paused });
 }
+
+pub fn emit_state_migrated(from_version: u32, to_version: u32) {
+    emit(
+        "state_migrated",
+        &StateMigratedData {
+            from_version,
+            to_version,
+        },
+    );
+}
@@ -0,0 +1,20 @@
+use near_sdk::json_types::U128;
+use near_sdk::serde::Deserialize;
+use near_sdk::{ext_contract, AccountId, Gas};
+
+/// Gas for an outgoing `ft_transfer`.
+pub const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+
+/// The part of NEP-141 used for payouts.
+#[ext_contract(ext_ft)]
+#[allow(dead_code)]
+pub trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+/// `msg` of an `ft_transfer_call` funding a program.
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FundingMessage {
+    pub program_id: String,
+}
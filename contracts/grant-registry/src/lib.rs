@@ -1,18 +1,31 @@
 mod errors;
 mod events;
+mod ft;
+mod migration;
+mod qf;
 mod storage;
 mod types;
 
-use near_sdk::store::{LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::{env, near, AccountId, FunctionError, PanicOnDefault};
+use near_sdk::json_types::U128;
+use near_sdk::store::{IterableMap, IterableSet, LookupMap, LookupSet};
+use near_sdk::{
+    env, is_promise_success, near, AccountId, FunctionError, Gas, NearToken, PanicOnDefault,
+    Promise, PromiseError, PromiseOrValue,
+};
 
 use errors::ContractError;
 use storage::StorageKey;
 
 pub use types::{
-    Application, ApplicationStatus, Category, Chain, EcosystemStats, Program, ProgramStatus,
-    Project, TeamMember,
+    Application, ApplicationScore, ApplicationStatus, Category, Chain, EcosystemStats, EscrowView,
+    Milestone, MilestoneInput, MilestoneStatus, Payout, PayoutStatus, PendingMilestone, Program,
+    ProgramStatus, ProgramUpdate, Project, ProjectAggregates, QfMatch, QfRound, Review, Rubric,
+    RubricCriterion, SybilFilter, TeamMember, TeamMemberUpdate,
 };
+use types::{ApplicationOutcome, Escrow, QfTally};
+
+/// Gas reserved for the payout and reclaim transfer callbacks.
+const GAS_FOR_TRANSFER_CALLBACK: Gas = Gas::from_tgas(5);
 
 /// Global Grant Registry contract for NEAR.
 ///
@@ -26,17 +39,55 @@ pub struct GrantRegistry {
     /// Whether the contract is paused
     is_paused: bool,
     /// Grant programs by ID
-    programs: UnorderedMap<String, Program>,
+    programs: IterableMap<String, Program>,
     /// Projects by ID
     projects: LookupMap<String, Project>,
     /// Applications by ID
     applications: LookupMap<String, Application>,
     /// Project → application IDs index
-    applications_by_project: LookupMap<String, UnorderedSet<String>>,
+    applications_by_project: LookupMap<String, IterableSet<String>>,
     /// Program → application IDs index
-    applications_by_program: LookupMap<String, UnorderedSet<String>>,
+    applications_by_program: LookupMap<String, IterableSet<String>>,
     /// Owner → project IDs index
-    projects_by_owner: LookupMap<AccountId, UnorderedSet<String>>,
+    projects_by_owner: LookupMap<AccountId, IterableSet<String>>,
+    /// Program → reviewers delegated by the program's registrar
+    program_reviewers: LookupMap<String, IterableSet<AccountId>>,
+    /// Program → review rubric and auto-transition thresholds
+    program_rubrics: LookupMap<String, Rubric>,
+    /// Application → reviewer → review
+    application_reviews: LookupMap<String, IterableMap<AccountId, Review>>,
+    /// Program → token (`None` for NEAR) → balance held in escrow
+    escrows: LookupMap<String, IterableMap<Option<AccountId>, Escrow>>,
+    /// Program → token its payouts are made in. Programs without an entry
+    /// have no escrow and track funding off-chain.
+    payout_tokens: LookupMap<String, Option<AccountId>>,
+    /// NEP-141 tokens accepted for program funding
+    ft_tokens: IterableSet<AccountId>,
+    /// QF rounds by ID
+    qf_rounds: LookupMap<String, QfRound>,
+    /// Round → application → donation tally
+    qf_tallies: LookupMap<String, IterableMap<String, QfTally>>,
+    /// (round, application, donor) → donor's total contribution
+    qf_contributions: LookupMap<(String, String, AccountId), u128>,
+    /// (round, donor) pairs that passed the round's sybil check
+    qf_verified_donors: LookupSet<(String, AccountId)>,
+    /// Project → invited account → member details awaiting acceptance
+    team_invites: LookupMap<String, IterableMap<AccountId, TeamMember>>,
+    /// Project → proposed new owner (two-step transfer)
+    pending_project_owners: LookupMap<String, AccountId>,
+    /// Payout key (application ID, or `application#milestone`) → payout
+    payouts: LookupMap<String, Payout>,
+    /// Program → escrow owed to its applications but not yet sent, in its
+    /// payout token; see `Application::committed`
+    commitments: LookupMap<String, u128>,
+    /// Records and indexes from before state version 2, converted on read
+    /// and moved to the maps above when modified
+    legacy_programs: migration::LegacyPrograms,
+    legacy_projects: LookupMap<String, migration::ProjectV1>,
+    legacy_applications: LookupMap<String, migration::ApplicationV1>,
+    legacy_applications_by_project: LookupMap<String, migration::LegacyIdSet>,
+    legacy_applications_by_program: LookupMap<String, migration::LegacyIdSet>,
+    legacy_projects_by_owner: LookupMap<AccountId, migration::LegacyIdSet>,
     /// Running counters
     total_programs: u64,
     total_projects: u64,
@@ -53,21 +104,54 @@ impl GrantRegistry {
 
     #[init]
     pub fn new(owner: AccountId) -> Self {
+        migration::write_state_version(migration::STATE_VERSION);
         Self {
             owner,
             is_paused: false,
-            programs: UnorderedMap::new(borsh::to_vec(&StorageKey::Programs).unwrap()),
-            projects: LookupMap::new(borsh::to_vec(&StorageKey::Projects).unwrap()),
-            applications: LookupMap::new(borsh::to_vec(&StorageKey::Applications).unwrap()),
+            programs: IterableMap::new(borsh::to_vec(&StorageKey::ProgramsV2).unwrap()),
+            projects: LookupMap::new(borsh::to_vec(&StorageKey::ProjectsV2).unwrap()),
+            applications: LookupMap::new(borsh::to_vec(&StorageKey::ApplicationsV2).unwrap()),
             applications_by_project: LookupMap::new(
-                borsh::to_vec(&StorageKey::ApplicationsByProject).unwrap(),
+                borsh::to_vec(&StorageKey::ApplicationsByProjectV2).unwrap(),
             ),
             applications_by_program: LookupMap::new(
-                borsh::to_vec(&StorageKey::ApplicationsByProgram).unwrap(),
+                borsh::to_vec(&StorageKey::ApplicationsByProgramV2).unwrap(),
             ),
             projects_by_owner: LookupMap::new(
-                borsh::to_vec(&StorageKey::ProjectsByOwner).unwrap(),
+                borsh::to_vec(&StorageKey::ProjectsByOwnerV2).unwrap(),
+            ),
+            program_reviewers: LookupMap::new(
+                borsh::to_vec(&StorageKey::ProgramReviewers).unwrap(),
+            ),
+            program_rubrics: LookupMap::new(borsh::to_vec(&StorageKey::ProgramRubrics).unwrap()),
+            application_reviews: LookupMap::new(
+                borsh::to_vec(&StorageKey::ApplicationReviews).unwrap(),
+            ),
+            escrows: LookupMap::new(borsh::to_vec(&StorageKey::Escrows).unwrap()),
+            payout_tokens: LookupMap::new(borsh::to_vec(&StorageKey::PayoutTokens).unwrap()),
+            ft_tokens: IterableSet::new(borsh::to_vec(&StorageKey::FtTokens).unwrap()),
+            qf_rounds: LookupMap::new(borsh::to_vec(&StorageKey::QfRounds).unwrap()),
+            qf_tallies: LookupMap::new(borsh::to_vec(&StorageKey::QfTallies).unwrap()),
+            qf_contributions: LookupMap::new(borsh::to_vec(&StorageKey::QfContributions).unwrap()),
+            qf_verified_donors: LookupSet::new(
+                borsh::to_vec(&StorageKey::QfVerifiedDonors).unwrap(),
+            ),
+            team_invites: LookupMap::new(borsh::to_vec(&StorageKey::TeamInvites).unwrap()),
+            pending_project_owners: LookupMap::new(
+                borsh::to_vec(&StorageKey::PendingProjectOwners).unwrap(),
+            ),
+            payouts: LookupMap::new(borsh::to_vec(&StorageKey::Payouts).unwrap()),
+            commitments: LookupMap::new(borsh::to_vec(&StorageKey::Commitments).unwrap()),
+            legacy_programs: migration::legacy_programs(),
+            legacy_projects: LookupMap::new(borsh::to_vec(&StorageKey::Projects).unwrap()),
+            legacy_applications: LookupMap::new(borsh::to_vec(&StorageKey::Applications).unwrap()),
+            legacy_applications_by_project: migration::legacy_id_index(
+                StorageKey::ApplicationsByProject,
+            ),
+            legacy_applications_by_program: migration::legacy_id_index(
+                StorageKey::ApplicationsByProgram,
             ),
+            legacy_projects_by_owner: migration::legacy_id_index(StorageKey::ProjectsByOwner),
             total_programs: 0,
             total_projects: 0,
             total_applications: 0,
@@ -76,11 +160,23 @@ impl GrantRegistry {
         }
     }
 
+    /// Convert the stored state to the current layout after a code upgrade.
+    /// Reads the state version recorded beside the contract state; a missing
+    /// version means the original (v1) layout. No-op on current state.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        migration::migrate_state()
+    }
+
     // =========================================================================
     // Program management
     // =========================================================================
 
-    /// Register a new grant program.
+    /// Register a new grant program. `deadline` is an ISO-8601 date or
+    /// date-time; a bare date accepts applications through the end of that
+    /// day, UTC.
+    #[allow(clippy::too_many_arguments)]
     pub fn register_program(
         &mut self,
         id: String,
@@ -89,9 +185,9 @@ impl GrantRegistry {
         organization: String,
         chains: Vec<Chain>,
         categories: Vec<Category>,
-        funding_pool: String,
-        min_amount: Option<String>,
-        max_amount: Option<String>,
+        funding_pool: U128,
+        min_amount: Option<U128>,
+        max_amount: Option<U128>,
         deadline: Option<String>,
         website: String,
         application_url: Option<String>,
@@ -100,10 +196,11 @@ impl GrantRegistry {
         self.assert_not_paused();
         let caller = env::predecessor_account_id();
 
-        if self.programs.get(&id).is_some() {
+        if self.program(&id).is_some() {
             ContractError::ProgramAlreadyExists(id).panic();
         }
 
+        let deadline = deadline.as_deref().map(Self::parse_deadline);
         let is_active = status == ProgramStatus::Active;
 
         let program = Program {
@@ -147,8 +244,15 @@ impl GrantRegistry {
         let from = from_index.unwrap_or(0) as usize;
         let lim = limit.unwrap_or(50) as usize;
 
+        let legacy = self
+            .legacy_programs
+            .values()
+            .cloned()
+            .map(migration::ProgramV1::upgrade);
         self.programs
             .values()
+            .cloned()
+            .chain(legacy)
             .filter(|p| {
                 if let Some(ref cat) = category {
                     if !p.categories.contains(cat) {
@@ -169,20 +273,151 @@ impl GrantRegistry {
             })
             .skip(from)
             .take(lim)
-            .cloned()
             .collect()
     }
 
     /// Get a program by ID.
     pub fn get_program(&self, program_id: String) -> Option<Program> {
-        self.programs.get(&program_id).cloned()
+        self.program(&program_id)
+    }
+
+    /// Update a program's descriptive and funding fields. Steward only.
+    pub fn update_program(&mut self, program_id: String, update: ProgramUpdate) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+        if let Some(Some(deadline)) = &update.deadline {
+            Self::parse_deadline(deadline);
+        }
+
+        let program = self.program_mut(&program_id).unwrap();
+        let fields = update.apply(program);
+        if fields.is_empty() {
+            ContractError::InvalidParams("update sets no fields".to_string()).panic();
+        }
+
+        events::emit_program_updated(&program_id, fields, &caller);
+    }
+
+    /// Change a program's status. Steward only; a closed program stays
+    /// closed.
+    pub fn set_program_status(&mut self, program_id: String, status: ProgramStatus) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        let program = self.program_mut(&program_id).unwrap();
+        let old_status = program.status.clone();
+        if !old_status.can_transition_to(&status) {
+            ContractError::InvalidTransition {
+                from: old_status.as_str().to_string(),
+                to: status.as_str().to_string(),
+            }
+            .panic();
+        }
+        program.status = status.clone();
+
+        match (
+            old_status == ProgramStatus::Active,
+            status == ProgramStatus::Active,
+        ) {
+            (false, true) => self.active_programs += 1,
+            (true, false) => self.active_programs -= 1,
+            _ => {}
+        }
+
+        events::emit_program_status_changed(
+            &program_id,
+            old_status.as_str(),
+            status.as_str(),
+            &caller,
+        );
+    }
+
+    /// Hand stewardship of a program to another account. The new steward
+    /// becomes `registered_by` and takes over updates, status changes and
+    /// reviewer delegation.
+    pub fn transfer_program(&mut self, program_id: String, new_steward: AccountId) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        if new_steward == caller {
+            ContractError::InvalidParams("new steward is the current steward".to_string()).panic();
+        }
+        self.program_mut(&program_id).unwrap().registered_by = new_steward.clone();
+
+        events::emit_program_transferred(&program_id, &caller, &new_steward);
+    }
+
+    /// Delegate application review for a program. Registrar only.
+    pub fn add_program_reviewer(&mut self, program_id: String, reviewer: AccountId) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        storage::add_program_reviewer(&mut self.program_reviewers, &program_id, reviewer.clone());
+
+        events::emit_program_reviewer_added(&program_id, &reviewer, &caller);
+    }
+
+    /// Revoke a reviewer's delegation for a program. Registrar only.
+    pub fn remove_program_reviewer(&mut self, program_id: String, reviewer: AccountId) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        if !storage::remove_program_reviewer(&mut self.program_reviewers, &program_id, &reviewer) {
+            ContractError::InvalidParams(format!("{reviewer} is not a reviewer of {program_id}"))
+                .panic();
+        }
+
+        events::emit_program_reviewer_removed(&program_id, &reviewer, &caller);
+    }
+
+    /// Get the reviewers delegated for a program.
+    pub fn get_program_reviewers(&self, program_id: String) -> Vec<AccountId> {
+        self.program_reviewers
+            .get(&program_id)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Set the rubric reviewers score applications against. Steward only.
+    /// Reviews already submitted keep the score they were given.
+    pub fn set_program_rubric(&mut self, program_id: String, rubric: Rubric) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        if let Err(msg) = rubric.validate() {
+            ContractError::InvalidParams(msg).panic();
+        }
+
+        events::emit_program_rubric_set(&program_id, &rubric);
+        self.program_rubrics.insert(program_id, rubric);
+    }
+
+    /// Get a program's review rubric.
+    pub fn get_program_rubric(&self, program_id: String) -> Option<Rubric> {
+        self.program_rubrics.get(&program_id).cloned()
+    }
+
+    /// Get all applications submitted to a program.
+    pub fn get_program_applications(&self, program_id: String) -> Vec<Application> {
+        self.program_application_ids(&program_id)
+            .iter()
+            .filter_map(|id| self.application(id))
+            .collect()
     }
 
     // =========================================================================
     // Project management
     // =========================================================================
 
-    /// Register a new project.
+    /// Register a new project. The caller is listed directly if included in
+    /// `team_members`; every other member is invited and joins with
+    /// `accept_team_invite`.
     pub fn register_project(
         &mut self,
         id: String,
@@ -194,20 +429,30 @@ impl GrantRegistry {
         self.assert_not_paused();
         let caller = env::predecessor_account_id();
 
-        if self.projects.get(&id).is_some() {
+        if self.project_exists(&id) {
             ContractError::ProjectAlreadyExists(id).panic();
         }
 
+        let (confirmed, invited): (Vec<_>, Vec<_>) = team_members
+            .into_iter()
+            .partition(|m| m.account_id == caller);
+        for member in invited {
+            events::emit_team_member("invited", &id, &member.account_id, &caller);
+            storage::store_team_invite(&mut self.team_invites, &id, member);
+        }
+
         let project = Project {
             id: id.clone(),
             name,
             description,
             website,
-            team_members,
+            team_members: confirmed,
             registered_by: caller.clone(),
             registered_at: env::block_timestamp(),
-            total_funded: "0".to_string(),
+            total_funded: U128(0),
             application_count: 0,
+            successful_count: 0,
+            decided_count: 0,
             success_rate: 0,
         };
 
@@ -219,54 +464,254 @@ impl GrantRegistry {
 
     /// Get a project by ID.
     pub fn get_project(&self, project_id: String) -> Option<Project> {
-        self.projects.get(&project_id).cloned()
+        self.project(&project_id)
     }
 
     /// Get a project's application history.
     pub fn get_project_history(&self, project_id: String) -> Vec<Application> {
-        storage::get_applications_by_project(
-            &self.applications,
-            &self.applications_by_project,
+        self.project_application_ids(&project_id)
+            .iter()
+            .filter_map(|id| self.application(id))
+            .collect()
+    }
+
+    /// Rebuild a project's aggregates from its application history, for
+    /// auditing the stored `total_funded` and `success_rate`.
+    pub fn recompute_project_aggregates(&self, project_id: String) -> ProjectAggregates {
+        let applications = self.get_project_history(project_id.clone());
+        let outcome = applications.iter().map(Application::outcome).fold(
+            ApplicationOutcome::default(),
+            |sum, o| ApplicationOutcome {
+                funded: sum.funded + o.funded,
+                successful: sum.successful + o.successful,
+                decided: sum.decided + o.decided,
+            },
+        );
+        ProjectAggregates {
+            project_id,
+            total_funded: U128(outcome.funded),
+            application_count: applications.len() as u64,
+            successful_count: outcome.successful,
+            decided_count: outcome.decided,
+            success_rate: types::success_rate(outcome.successful, outcome.decided),
+        }
+    }
+
+    /// Invite an account to the project's team. Project owner only.
+    pub fn add_team_member(&mut self, project_id: String, member: TeamMember) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_project_owner(&project_id, &caller);
+
+        let account_id = member.account_id.clone();
+        if self.is_team_member_or_invited(&project_id, &account_id) {
+            ContractError::AlreadyTeamMember(account_id.to_string()).panic();
+        }
+        storage::store_team_invite(&mut self.team_invites, &project_id, member);
+
+        events::emit_team_member("invited", &project_id, &account_id, &caller);
+    }
+
+    /// Join a project's team by accepting a pending invite.
+    pub fn accept_team_invite(&mut self, project_id: String) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+
+        let member = self
+            .team_invites
+            .get_mut(&project_id)
+            .and_then(|invites| invites.remove(&caller))
+            .unwrap_or_else(|| ContractError::NotTeamMember(caller.to_string()).panic());
+        self.project_mut(&project_id)
+            .unwrap_or_else(|| ContractError::ProjectNotFound(project_id.clone()).panic())
+            .team_members
+            .push(member);
+
+        events::emit_team_member("joined", &project_id, &caller, &caller);
+    }
+
+    /// Remove a team member or withdraw an invite. Callable by the project
+    /// owner, or by the member to leave or decline.
+    pub fn remove_team_member(&mut self, project_id: String, account_id: AccountId) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        if caller != account_id {
+            self.assert_project_owner(&project_id, &caller);
+        }
+
+        let was_invited = self
+            .team_invites
+            .get_mut(&project_id)
+            .and_then(|invites| invites.remove(&account_id))
+            .is_some();
+        let project = self
+            .project_mut(&project_id)
+            .unwrap_or_else(|| ContractError::ProjectNotFound(project_id.clone()).panic());
+        let before = project.team_members.len();
+        project.team_members.retain(|m| m.account_id != account_id);
+        if !was_invited && project.team_members.len() == before {
+            ContractError::NotTeamMember(account_id.to_string()).panic();
+        }
+
+        events::emit_team_member("removed", &project_id, &account_id, &caller);
+    }
+
+    /// Update a team member's (or invite's) name, role or profile URL.
+    /// Callable by the project owner or the member.
+    pub fn update_team_member(
+        &mut self,
+        project_id: String,
+        account_id: AccountId,
+        update: TeamMemberUpdate,
+    ) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        if caller != account_id {
+            self.assert_project_owner(&project_id, &caller);
+        }
+
+        let project = self
+            .project_mut(&project_id)
+            .unwrap_or_else(|| ContractError::ProjectNotFound(project_id.clone()).panic());
+        let member = match project
+            .team_members
+            .iter_mut()
+            .find(|m| m.account_id == account_id)
+        {
+            Some(member) => member,
+            None => self
+                .team_invites
+                .get_mut(&project_id)
+                .and_then(|invites| invites.get_mut(&account_id))
+                .unwrap_or_else(|| ContractError::NotTeamMember(account_id.to_string()).panic()),
+        };
+        update.apply(member);
+
+        events::emit_team_member("updated", &project_id, &account_id, &caller);
+    }
+
+    /// Get a project's pending team invites.
+    pub fn get_team_invites(&self, project_id: String) -> Vec<TeamMember> {
+        self.team_invites
+            .get(&project_id)
+            .map(|invites| invites.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Propose a new owner for a project (step 1 of 2). Owner only;
+    /// replaces any earlier proposal.
+    pub fn propose_project_owner(&mut self, project_id: String, new_owner: AccountId) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_project_owner(&project_id, &caller);
+
+        if new_owner == caller {
+            ContractError::InvalidParams("new owner is the current owner".to_string()).panic();
+        }
+        self.pending_project_owners
+            .insert(project_id.clone(), new_owner.clone());
+
+        events::emit_project_owner_proposed(&project_id, &caller, &new_owner);
+    }
+
+    /// Accept ownership of a project (step 2 of 2). Only the proposed owner.
+    pub fn accept_project_ownership(&mut self, project_id: String) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+
+        if self.pending_project_owners.get(&project_id) != Some(&caller) {
+            ContractError::Unauthorized.panic();
+        }
+        self.pending_project_owners.remove(&project_id);
+
+        let project = self.project_mut(&project_id).unwrap();
+        let old_owner = std::mem::replace(&mut project.registered_by, caller.clone());
+        storage::move_project_owner(
+            &mut self.projects_by_owner,
+            &mut self.legacy_projects_by_owner,
             &project_id,
-        )
+            &old_owner,
+            &caller,
+        );
+
+        events::emit_project_ownership_transferred(&project_id, &old_owner, &caller);
+    }
+
+    /// Get the proposed new owner of a project, if any.
+    pub fn get_pending_project_owner(&self, project_id: String) -> Option<AccountId> {
+        self.pending_project_owners.get(&project_id).cloned()
     }
 
     /// Get all projects registered by a specific owner.
     pub fn get_projects_by_owner(&self, owner: AccountId) -> Vec<Project> {
-        storage::get_projects_by_owner(&self.projects, &self.projects_by_owner, &owner)
+        storage::index_ids(
+            &self.projects_by_owner,
+            &self.legacy_projects_by_owner,
+            &owner,
+        )
+        .iter()
+        .filter_map(|id| self.project(id))
+        .collect()
     }
 
     // =========================================================================
     // Application management
     // =========================================================================
 
-    /// Record a new grant application.
+    /// Record a new grant application. The program must be active, before
+    /// its deadline and allow the requested amount; the caller must be on
+    /// the project's team.
     pub fn record_application(
         &mut self,
         id: String,
         program_id: String,
         project_id: String,
         title: String,
-        requested_amount: String,
+        requested_amount: U128,
     ) {
         self.assert_not_paused();
         let caller = env::predecessor_account_id();
 
-        if self.applications.get(&id).is_some() {
+        if self.application_exists(&id) {
             ContractError::ApplicationAlreadyExists(id).panic();
         }
 
-        // Verify program exists
-        let program = self.programs.get(&program_id).unwrap_or_else(|| {
-            ContractError::ProgramNotFound(program_id.clone()).panic()
-        });
+        let now = env::block_timestamp();
 
-        // Verify project exists
-        if self.projects.get(&project_id).is_none() {
-            ContractError::ProjectNotFound(project_id.clone()).panic();
+        let program = self
+            .program(&program_id)
+            .unwrap_or_else(|| ContractError::ProgramNotFound(program_id.clone()).panic());
+        if program.status != ProgramStatus::Active {
+            ContractError::ProgramNotAccepting {
+                program_id,
+                status: program.status.as_str().to_string(),
+            }
+            .panic();
+        }
+        if program.deadline.is_some_and(|deadline| now > deadline) {
+            ContractError::DeadlinePassed(program_id).panic();
+        }
+        let (min, max) = (
+            program.min_amount.map(|a| a.0),
+            program.max_amount.map(|a| a.0),
+        );
+        if min.is_some_and(|min| requested_amount.0 < min)
+            || max.is_some_and(|max| requested_amount.0 > max)
+        {
+            ContractError::AmountOutOfBounds {
+                requested: requested_amount.0,
+                min,
+                max,
+            }
+            .panic();
         }
 
-        let now = env::block_timestamp();
+        if !self.project_exists(&project_id) {
+            ContractError::ProjectNotFound(project_id.clone()).panic();
+        }
+        if !self.is_project_member(&project_id, &caller) {
+            ContractError::NotTeamMember(caller.to_string()).panic();
+        }
 
         let application = Application {
             id: id.clone(),
@@ -279,8 +724,12 @@ impl GrantRegistry {
             submitted_at: Some(now),
             funded_amount: None,
             completed_at: None,
+            milestones: Vec::new(),
         };
 
+        // Convert a pre-v2 project before its aggregates are rebuilt with
+        // this application in them
+        self.upgrade_legacy_project(&project_id);
         storage::store_application(
             &mut self.applications,
             &mut self.applications_by_project,
@@ -289,12 +738,12 @@ impl GrantRegistry {
         );
 
         // Update program application count
-        if let Some(p) = self.programs.get_mut(&program_id) {
+        if let Some(p) = self.program_mut(&program_id) {
             p.application_count += 1;
         }
 
         // Update project application count
-        if let Some(proj) = self.projects.get_mut(&project_id) {
+        if let Some(proj) = self.project_mut(&project_id) {
             proj.application_count += 1;
         }
 
@@ -304,112 +753,1530 @@ impl GrantRegistry {
     }
 
     /// Update an application's status.
+    ///
+    /// The change must follow `ApplicationStatus::allowed_transitions`.
+    /// Submitting and withdrawing are done by the applicant; every other
+    /// transition by the program's registrar or one of its reviewers, who
+    /// must not be on the project's team. `funded_amount` is only accepted
+    /// when moving to `Funded`; on an escrowed program it is paid out, up to
+    /// the requested amount and the escrow not committed to other
+    /// applications.
     pub fn update_application(
         &mut self,
         application_id: String,
         new_status: ApplicationStatus,
-        funded_amount: Option<String>,
+        funded_amount: Option<U128>,
     ) {
         self.assert_not_paused();
         let caller = env::predecessor_account_id();
 
-        let app = self
-            .applications
-            .get_mut(&application_id)
-            .unwrap_or_else(|| {
-                ContractError::ApplicationNotFound(application_id.clone()).panic()
-            });
+        let (current, applicant, program_id, project_id) = match self.application(&application_id) {
+            Some(app) => (
+                app.status.clone(),
+                app.applicant_account_id.clone(),
+                app.program_id.clone(),
+                app.project_id.clone(),
+            ),
+            None => ContractError::ApplicationNotFound(application_id).panic(),
+        };
 
-        let was_funded = app.status == ApplicationStatus::Funded;
+        if !current.can_transition_to(&new_status) {
+            ContractError::InvalidTransition {
+                from: current.as_str().to_string(),
+                to: new_status.as_str().to_string(),
+            }
+            .panic();
+        }
 
-        app.status = new_status.clone();
+        let reviewer_side = !ApplicationStatus::is_applicant_transition(&new_status);
+        let authorized = if reviewer_side {
+            self.is_program_reviewer(&program_id, &caller)
+        } else {
+            caller == applicant
+        };
+        if !authorized {
+            ContractError::Unauthorized.panic();
+        }
+        if reviewer_side && self.is_project_member(&project_id, &caller) {
+            ContractError::ConflictOfInterest {
+                reviewer: caller.to_string(),
+                project_id,
+            }
+            .panic();
+        }
 
-        if let Some(ref amount) = funded_amount {
-            app.funded_amount = Some(amount.clone());
+        if funded_amount.is_some() && new_status != ApplicationStatus::Funded {
+            ContractError::InvalidParams(
+                "funded_amount is only accepted when moving to funded".to_string(),
+            )
+            .panic();
         }
 
-        if new_status == ApplicationStatus::Completed {
-            app.completed_at = Some(env::block_timestamp());
+        let has_milestones = !self
+            .application(&application_id)
+            .unwrap()
+            .milestones
+            .is_empty();
+        if has_milestones
+            && matches!(
+                new_status,
+                ApplicationStatus::Funded | ApplicationStatus::Completed
+            )
+        {
+            ContractError::InvalidParams(
+                "status follows milestone approvals for this application".to_string(),
+            )
+            .panic();
         }
 
-        // Track funded count on program
-        if new_status == ApplicationStatus::Funded && !was_funded {
-            if let Some(p) = self.programs.get_mut(&app.program_id) {
-                p.funded_count += 1;
+        let payout = match (&new_status, funded_amount) {
+            (ApplicationStatus::Funded, Some(amount))
+                if self.payout_tokens.contains_key(&program_id) =>
+            {
+                self.assert_payout_available(&program_id, &application_id, amount.0);
+                Some(amount.0)
             }
-            // Track total funded
-            if let Some(ref amount) = funded_amount {
-                if let Ok(num) = amount.parse::<u128>() {
-                    self.total_funded += num;
-                }
+            (ApplicationStatus::Funded, None) if self.payout_tokens.contains_key(&program_id) => {
+                ContractError::InvalidParams("escrowed programs need a funded_amount".to_string())
+                    .panic()
             }
+            _ => None,
+        };
+
+        self.apply_application_status(&application_id, new_status, funded_amount, &caller);
+
+        if let Some(amount) = payout {
+            self.pay_out(&program_id, &application_id, None, applicant, amount);
         }
+    }
 
-        let status_str = serde_json::to_string(&new_status).unwrap_or_default();
-        events::emit_application_updated(&application_id, &status_str, &caller);
+    /// Get an application by ID.
+    pub fn get_application(&self, application_id: String) -> Option<Application> {
+        self.application(&application_id)
     }
 
     // =========================================================================
-    // Stats
+    // Milestones
     // =========================================================================
 
-    /// Get ecosystem-wide statistics.
-    pub fn get_ecosystem_stats(&self) -> EcosystemStats {
-        EcosystemStats {
-            total_programs: self.total_programs,
-            total_projects: self.total_projects,
-            total_funded: self.total_funded.to_string(),
-            total_applications: self.total_applications,
-            active_programs: self.active_programs,
+    /// Define the application's payout milestones, replacing any earlier
+    /// set. Applicant only, before the application is approved. Amounts
+    /// must add up to the requested amount.
+    pub fn set_milestones(&mut self, application_id: String, milestones: Vec<MilestoneInput>) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+
+        let app = self
+            .application_mut(&application_id)
+            .unwrap_or_else(|| ContractError::ApplicationNotFound(application_id.clone()).panic());
+        if app.applicant_account_id != caller {
+            ContractError::Unauthorized.panic();
+        }
+        if !matches!(
+            app.status,
+            ApplicationStatus::Draft
+                | ApplicationStatus::Submitted
+                | ApplicationStatus::UnderReview
+        ) {
+            ContractError::InvalidParams(format!(
+                "milestones are fixed once the application is {}",
+                app.status.as_str()
+            ))
+            .panic();
+        }
+        if milestones.is_empty() {
+            ContractError::InvalidParams("at least one milestone is required".to_string()).panic();
         }
-    }
 
-    // =========================================================================
-    // Admin
-    // =========================================================================
+        let mut total: u128 = 0;
+        for m in &milestones {
+            total = total.checked_add(m.amount.0).unwrap_or_else(|| {
+                ContractError::InvalidParams("milestone amounts overflow".to_string()).panic()
+            });
+            if !types::is_sha256_hex(&m.deliverable_hash) {
+                ContractError::InvalidParams("deliverable_hash must be a hex sha256".to_string())
+                    .panic();
+            }
+        }
+        if app.requested_amount.0 != total {
+            ContractError::InvalidParams(format!(
+                "milestone amounts sum to {total}, requested {}",
+                app.requested_amount.0
+            ))
+            .panic();
+        }
 
-    /// Pause or unpause the contract.
-    pub fn set_paused(&mut self, paused: bool) {
-        self.assert_owner();
-        self.is_paused = paused;
-        events::emit_contract_paused(paused);
+        let count = milestones.len();
+        app.milestones = milestones
+            .into_iter()
+            .map(|m| Milestone {
+                title: m.title,
+                amount: m.amount,
+                due_at: m.due_at,
+                deliverable_hash: m.deliverable_hash,
+                status: MilestoneStatus::Pending,
+                evidence_hash: None,
+                submitted_at: None,
+                reviewed_at: None,
+            })
+            .collect();
+
+        events::emit_milestones_set(&application_id, count);
     }
 
-    // =========================================================================
-    // Internal helpers
-    // =========================================================================
+    /// Submit delivery evidence for a milestone of an approved or funded
+    /// application. Applicant only; a rejected milestone can be resubmitted.
+    pub fn submit_milestone(&mut self, application_id: String, index: u32, evidence_hash: String) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
 
-    fn assert_owner(&self) {
-        if env::predecessor_account_id() != self.owner {
+        if !types::is_sha256_hex(&evidence_hash) {
+            ContractError::InvalidParams("evidence_hash must be a hex sha256".to_string()).panic();
+        }
+
+        let app = self
+            .application_mut(&application_id)
+            .unwrap_or_else(|| ContractError::ApplicationNotFound(application_id.clone()).panic());
+        if app.applicant_account_id != caller {
             ContractError::Unauthorized.panic();
         }
+        if !matches!(
+            app.status,
+            ApplicationStatus::Approved | ApplicationStatus::Funded
+        ) {
+            ContractError::InvalidParams(format!(
+                "application is {}, not approved",
+                app.status.as_str()
+            ))
+            .panic();
+        }
+        let milestone = app.milestones.get_mut(index as usize).unwrap_or_else(|| {
+            ContractError::InvalidParams(format!("no milestone {index}")).panic()
+        });
+        if !matches!(
+            milestone.status,
+            MilestoneStatus::Pending | MilestoneStatus::Rejected
+        ) {
+            ContractError::InvalidParams(format!("milestone {index} is not awaiting evidence"))
+                .panic();
+        }
+
+        milestone.status = MilestoneStatus::Submitted;
+        milestone.evidence_hash = Some(evidence_hash);
+        milestone.submitted_at = Some(env::block_timestamp());
+
+        events::emit_milestone_submitted(&application_id, index, &caller);
     }
 
-    fn assert_not_paused(&self) {
-        if self.is_paused {
-            ContractError::ContractPaused.panic();
+    /// Approve or reject a submitted milestone of an approved or funded
+    /// application. Program reviewers only, excluding the applying
+    /// project's team.
+    ///
+    /// The first approval moves the application to `Funded` and approving
+    /// the last milestone completes it. `funded_amount` is the sum of
+    /// approved milestones.
+    pub fn review_milestone(&mut self, application_id: String, index: u32, approve: bool) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+
+        let app = self
+            .application(&application_id)
+            .unwrap_or_else(|| ContractError::ApplicationNotFound(application_id.clone()).panic());
+        let (program_id, project_id) = (app.program_id.clone(), app.project_id.clone());
+        if !self.is_program_reviewer(&program_id, &caller) {
+            ContractError::Unauthorized.panic();
+        }
+        if self.is_project_member(&project_id, &caller) {
+            ContractError::ConflictOfInterest {
+                reviewer: caller.to_string(),
+                project_id,
+            }
+            .panic();
         }
-    }
-}
 
-use near_sdk::borsh;
+        let app = self.application_mut(&application_id).unwrap();
+        let status = app.status.clone();
+        if !matches!(
+            status,
+            ApplicationStatus::Approved | ApplicationStatus::Funded
+        ) {
+            ContractError::InvalidParams(format!(
+                "application is {}, not approved",
+                status.as_str()
+            ))
+            .panic();
+        }
+        let applicant = app.applicant_account_id.clone();
+        let committed_before = app.committed();
+        let milestone = app.milestones.get_mut(index as usize).unwrap_or_else(|| {
+            ContractError::InvalidParams(format!("no milestone {index}")).panic()
+        });
+        if milestone.status != MilestoneStatus::Submitted {
+            ContractError::InvalidParams(format!("milestone {index} has no pending submission"))
+                .panic();
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::test_utils::VMContextBuilder;
-    use near_sdk::testing_env;
+        milestone.reviewed_at = Some(env::block_timestamp());
+        events::emit_milestone_reviewed(&application_id, index, approve, &caller);
+        if !approve {
+            milestone.status = MilestoneStatus::Rejected;
+            return;
+        }
+        milestone.status = MilestoneStatus::Approved;
+        let amount = milestone.amount.0;
 
-    fn owner() -> AccountId {
-        "owner.testnet".parse().unwrap()
+        let funded: u128 = app
+            .milestones
+            .iter()
+            .filter(|m| m.status == MilestoneStatus::Approved)
+            .map(|m| m.amount.0)
+            .sum();
+        let all_approved = app
+            .milestones
+            .iter()
+            .all(|m| m.status == MilestoneStatus::Approved);
+        let before = app.outcome();
+        app.funded_amount = Some(U128(funded));
+        let committed_after = app.committed();
+        self.total_funded += amount;
+        self.record_project_outcome(&application_id, before);
+        self.record_commitment(&program_id, committed_before, committed_after);
+
+        // Funded on the first approval, completed on the last
+        let mut current = status;
+        let steps = [
+            (ApplicationStatus::Funded, true),
+            (ApplicationStatus::Completed, all_approved),
+        ];
+        for (next, due) in steps {
+            if !due || current == next {
+                continue;
+            }
+            if !current.can_transition_to(&next) {
+                ContractError::InvalidTransition {
+                    from: current.as_str().to_string(),
+                    to: next.as_str().to_string(),
+                }
+                .panic();
+            }
+            self.apply_application_status(&application_id, next.clone(), None, &caller);
+            current = next;
+        }
+        if self.payout_tokens.contains_key(&program_id) {
+            self.pay_out(&program_id, &application_id, Some(index), applicant, amount);
+        }
     }
 
-    fn alice() -> AccountId {
-        "alice.testnet".parse().unwrap()
+    /// Milestones of a program's approved and funded applications that
+    /// are not yet approved, optionally only those in `status`.
+    pub fn get_pending_milestones(
+        &self,
+        program_id: String,
+        status: Option<MilestoneStatus>,
+    ) -> Vec<PendingMilestone> {
+        let mut result = Vec::new();
+        for app_id in self.program_application_ids(&program_id) {
+            let Some(app) = self.application(&app_id) else {
+                continue;
+            };
+            if !matches!(
+                app.status,
+                ApplicationStatus::Approved | ApplicationStatus::Funded
+            ) {
+                continue;
+            }
+            for (index, milestone) in app.milestones.iter().enumerate() {
+                let wanted = match &status {
+                    Some(s) => &milestone.status == s,
+                    None => milestone.status != MilestoneStatus::Approved,
+                };
+                if wanted {
+                    result.push(PendingMilestone {
+                        application_id: app_id.clone(),
+                        index: index as u32,
+                        milestone: milestone.clone(),
+                    });
+                }
+            }
+        }
+        result
     }
 
-    fn bob() -> AccountId {
-        "bob.testnet".parse().unwrap()
+    // =========================================================================
+    // Escrow
+    // =========================================================================
+
+    /// Deposit the attached NEAR into a program's escrow. Once a program
+    /// has escrow, funding an application or approving a milestone pays
+    /// the applicant from it, in the program's payout token.
+    #[payable]
+    pub fn fund_program(&mut self, program_id: String) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        let amount = env::attached_deposit().as_yoctonear();
+        self.internal_deposit(&program_id, None, &caller, amount);
+    }
+
+    /// NEP-141 receiver: fund a program with a whitelisted token. `msg` is
+    /// `{"program_id": "..."}`. Panicking refunds the transfer.
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused();
+        let token_id = env::predecessor_account_id();
+        if !self.ft_tokens.contains(&token_id) {
+            ContractError::TokenNotWhitelisted(token_id.to_string()).panic();
+        }
+        let message: ft::FundingMessage = serde_json::from_str(&msg).unwrap_or_else(|_| {
+            ContractError::InvalidParams("msg must be {\"program_id\": \"...\"}".to_string())
+                .panic()
+        });
+
+        self.internal_deposit(&message.program_id, Some(token_id), &sender_id, amount.0);
+        PromiseOrValue::Value(U128(0))
+    }
+
+    /// Choose the token a program pays out in (`None` for NEAR). Steward
+    /// only. Defaults to the token of the program's first deposit.
+    pub fn set_program_payout_token(&mut self, program_id: String, token_id: Option<AccountId>) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        if let Some(token) = &token_id {
+            if !self.ft_tokens.contains(token) {
+                ContractError::TokenNotWhitelisted(token.to_string()).panic();
+            }
+        }
+        events::emit_payout_token_set(&program_id, token_id.as_ref(), &caller);
+        self.payout_tokens.insert(program_id, token_id);
+    }
+
+    /// Get the token a program pays out in, if it has escrow. The inner
+    /// `None` means NEAR.
+    pub fn get_program_payout_token(&self, program_id: String) -> Option<Option<AccountId>> {
+        self.payout_tokens.get(&program_id).cloned()
+    }
+
+    /// Get a program's escrow balances in one token (`None` for NEAR).
+    pub fn get_program_escrow(
+        &self,
+        program_id: String,
+        token_id: Option<AccountId>,
+    ) -> Option<EscrowView> {
+        let escrow = self.escrows.get(&program_id)?.get(&token_id)?;
+        Some(self.escrow_view(&program_id, token_id, escrow))
+    }
+
+    /// Get a program's escrow balances in every token it holds.
+    pub fn get_program_balances(&self, program_id: String) -> Vec<EscrowView> {
+        self.escrows
+            .get(&program_id)
+            .map(|balances| {
+                balances
+                    .iter()
+                    .map(|(token_id, escrow)| {
+                        self.escrow_view(&program_id, token_id.clone(), escrow)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get the payout for an application, or for one of its milestones.
+    pub fn get_payout(
+        &self,
+        application_id: String,
+        milestone_index: Option<u32>,
+    ) -> Option<Payout> {
+        self.payouts
+            .get(&storage::payout_key(&application_id, milestone_index))
+            .cloned()
+    }
+
+    /// Resend a failed payout in the token it was first sent in. Callable
+    /// by the recipient or the program's reviewers.
+    pub fn retry_payout(&mut self, application_id: String, milestone_index: Option<u32>) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+
+        let key = storage::payout_key(&application_id, milestone_index);
+        let payout =
+            self.payouts.get(&key).cloned().unwrap_or_else(|| {
+                ContractError::InvalidParams(format!("no payout {key}")).panic()
+            });
+        if payout.status != PayoutStatus::Failed {
+            ContractError::InvalidParams(format!("payout {key} has not failed")).panic();
+        }
+        if caller != payout.recipient && !self.is_program_reviewer(&payout.program_id, &caller) {
+            ContractError::Unauthorized.panic();
+        }
+        storage::escrow_mut(&mut self.escrows, &payout.program_id, &payout.token_id).failed -=
+            payout.amount.0;
+
+        self.start_payout(
+            &payout.program_id,
+            payout.token_id,
+            &application_id,
+            milestone_index,
+            payout.recipient,
+            payout.amount.0,
+        );
+    }
+
+    /// Callback for payout transfers: settle the escrow ledger, or return
+    /// the amount to the balance and mark the payout failed.
+    #[private]
+    pub fn on_payout_complete(&mut self, payout_key: String) -> bool {
+        let success = is_promise_success();
+        let Some(payout) = self.payouts.get_mut(&payout_key) else {
+            return false;
+        };
+        payout.status = if success {
+            PayoutStatus::Paid
+        } else {
+            PayoutStatus::Failed
+        };
+        payout.updated_at = env::block_timestamp();
+
+        let amount = payout.amount.0;
+        let escrow = storage::escrow_mut(&mut self.escrows, &payout.program_id, &payout.token_id);
+        escrow.in_flight -= amount;
+        if success {
+            escrow.paid_out += amount;
+        } else {
+            escrow.failed += amount;
+        }
+
+        events::emit_payout(&payout_key, payout);
+        success
+    }
+
+    /// Return a closed program's uncommitted escrow in one token to its
+    /// steward.
+    pub fn reclaim_program_funds(
+        &mut self,
+        program_id: String,
+        token_id: Option<AccountId>,
+    ) -> Promise {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        if self.program(&program_id).unwrap().status != ProgramStatus::Closed {
+            ContractError::InvalidParams(
+                "funds can only be reclaimed after the program closes".to_string(),
+            )
+            .panic();
+        }
+        if !self
+            .escrows
+            .get(&program_id)
+            .is_some_and(|b| b.contains_key(&token_id))
+        {
+            ContractError::InvalidParams(format!("{program_id} has no escrow in that token"))
+                .panic();
+        }
+        let committed = self.committed_funds(&program_id, &token_id);
+        let escrow = storage::escrow_mut(&mut self.escrows, &program_id, &token_id);
+        let amount = escrow.balance().saturating_sub(committed);
+        if amount == 0 {
+            ContractError::InsufficientEscrow {
+                program_id,
+                available: 0,
+                requested: 0,
+            }
+            .panic();
+        }
+        escrow.reclaimed += amount;
+
+        self.transfer(&token_id, caller.clone(), amount).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_TRANSFER_CALLBACK)
+                .on_funds_reclaimed(program_id, token_id, caller, U128(amount)),
+        )
+    }
+
+    /// Callback for `reclaim_program_funds`: restore the balance if the
+    /// transfer failed.
+    #[private]
+    pub fn on_funds_reclaimed(
+        &mut self,
+        program_id: String,
+        token_id: Option<AccountId>,
+        recipient: AccountId,
+        amount: U128,
+    ) -> bool {
+        let success = is_promise_success();
+        if !success {
+            storage::escrow_mut(&mut self.escrows, &program_id, &token_id).reclaimed -= amount.0;
+        }
+        events::emit_funds_reclaimed(
+            &program_id,
+            token_id.as_ref(),
+            &recipient,
+            amount.0,
+            success,
+        );
+        success
+    }
+
+    /// Whitelist a NEP-141 token for program funding. Owner only.
+    pub fn add_ft_token(&mut self, token_id: AccountId) {
+        self.assert_owner();
+        if self.ft_tokens.insert(token_id.clone()) {
+            events::emit_ft_token_whitelisted(&token_id, true);
+        }
+    }
+
+    /// Remove a token from the whitelist. Balances already held stay
+    /// payable and reclaimable. Owner only.
+    pub fn remove_ft_token(&mut self, token_id: AccountId) {
+        self.assert_owner();
+        if self.ft_tokens.remove(&token_id) {
+            events::emit_ft_token_whitelisted(&token_id, false);
+        }
+    }
+
+    /// Get the whitelisted funding tokens.
+    pub fn get_ft_tokens(&self) -> Vec<AccountId> {
+        self.ft_tokens.iter().cloned().collect()
+    }
+
+    // =========================================================================
+    // Reviews
+    // =========================================================================
+
+    /// Submit or revise a scored review of an application.
+    ///
+    /// Open to the program's reviewers while the application is submitted
+    /// or under review, except members of the applying project's team. The
+    /// first review moves the application to `UnderReview`; once the
+    /// rubric's `min_reviews` are in, an average at or above the approve
+    /// threshold approves it and one at or below the reject threshold
+    /// rejects it.
+    pub fn submit_review(
+        &mut self,
+        application_id: String,
+        scores: Vec<u32>,
+        comment_hash: String,
+    ) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+
+        let app = self
+            .application(&application_id)
+            .unwrap_or_else(|| ContractError::ApplicationNotFound(application_id.clone()).panic());
+        let (status, program_id, project_id) = (
+            app.status.clone(),
+            app.program_id.clone(),
+            app.project_id.clone(),
+        );
+
+        if !self.is_program_reviewer(&program_id, &caller) {
+            ContractError::Unauthorized.panic();
+        }
+        if self.is_project_member(&project_id, &caller) {
+            ContractError::ConflictOfInterest {
+                reviewer: caller.to_string(),
+                project_id,
+            }
+            .panic();
+        }
+        if !matches!(
+            status,
+            ApplicationStatus::Submitted | ApplicationStatus::UnderReview
+        ) {
+            ContractError::InvalidParams(format!(
+                "application is {}, not open for review",
+                status.as_str()
+            ))
+            .panic();
+        }
+        if !types::is_sha256_hex(&comment_hash) {
+            ContractError::InvalidParams("comment_hash must be a hex sha256".to_string()).panic();
+        }
+
+        let rubric = self
+            .program_rubrics
+            .get(&program_id)
+            .cloned()
+            .unwrap_or_else(|| ContractError::RubricNotSet(program_id.clone()).panic());
+        let score_bps = rubric
+            .score_bps(&scores)
+            .unwrap_or_else(|msg| ContractError::InvalidParams(msg).panic());
+
+        let review = Review {
+            reviewer: caller.clone(),
+            scores,
+            comment_hash,
+            score_bps,
+            submitted_at: env::block_timestamp(),
+        };
+        events::emit_review_submitted(&application_id, &review);
+        storage::store_review(&mut self.application_reviews, &application_id, review);
+
+        if status == ApplicationStatus::Submitted {
+            self.apply_application_status(
+                &application_id,
+                ApplicationStatus::UnderReview,
+                None,
+                &caller,
+            );
+        }
+
+        let score = self.application_score(&application_id);
+        if score.review_count >= rubric.min_reviews {
+            if score.average_score_bps >= rubric.approve_threshold_bps {
+                self.apply_application_status(
+                    &application_id,
+                    ApplicationStatus::Approved,
+                    None,
+                    &caller,
+                );
+            } else if score.average_score_bps <= rubric.reject_threshold_bps {
+                self.apply_application_status(
+                    &application_id,
+                    ApplicationStatus::Rejected,
+                    None,
+                    &caller,
+                );
+            }
+        }
+    }
+
+    /// Get all reviews of an application.
+    pub fn get_application_reviews(&self, application_id: String) -> Vec<Review> {
+        self.application_reviews
+            .get(&application_id)
+            .map(|reviews| reviews.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the aggregate review score of an application.
+    pub fn get_application_score(&self, application_id: String) -> ApplicationScore {
+        self.application_score(&application_id)
+    }
+
+    // =========================================================================
+    // Quadratic funding
+    // =========================================================================
+
+    /// Open a QF round for a program. Steward only. With a sybil filter,
+    /// donors must first pass `verify_qf_donor`. The matching pool is a
+    /// pledge: it is not taken from or checked against the program's
+    /// escrow.
+    pub fn create_qf_round(
+        &mut self,
+        round_id: String,
+        program_id: String,
+        starts_at: u64,
+        ends_at: u64,
+        matching_pool: U128,
+        sybil_filter: Option<SybilFilter>,
+    ) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        self.assert_program_registrar(&program_id, &caller);
+
+        if self.qf_rounds.contains_key(&round_id) {
+            ContractError::RoundAlreadyExists(round_id).panic();
+        }
+        if ends_at <= starts_at || ends_at <= env::block_timestamp() {
+            ContractError::InvalidParams(
+                "round must end after it starts and in the future".to_string(),
+            )
+            .panic();
+        }
+        if matching_pool.0 == 0 {
+            ContractError::InvalidParams("matching_pool must be positive".to_string()).panic();
+        }
+
+        let round = QfRound {
+            id: round_id.clone(),
+            program_id,
+            starts_at,
+            ends_at,
+            matching_pool,
+            sybil_filter,
+            total_donations: U128(0),
+            pending_forwards: 0,
+            total_matched: U128(0),
+            settled_at: None,
+        };
+        events::emit_qf_round_created(&round);
+        self.qf_rounds.insert(round_id, round);
+    }
+
+    /// Get a QF round by ID.
+    pub fn get_qf_round(&self, round_id: String) -> Option<QfRound> {
+        self.qf_rounds.get(&round_id).cloned()
+    }
+
+    /// Pass the round's sybil check with a zk-verifier credential owned by
+    /// the caller. Resolves in `on_qf_donor_verified`.
+    pub fn verify_qf_donor(&mut self, round_id: String, credential_id: String) -> Promise {
+        self.assert_not_paused();
+        let donor = env::predecessor_account_id();
+
+        let round = self.qf_round(&round_id);
+        let Some(filter) = round.sybil_filter.clone() else {
+            ContractError::InvalidParams(format!("{round_id} has no sybil filter")).panic()
+        };
+
+        qf::ext_zk_verifier::ext(filter.verifier.clone())
+            .with_static_gas(qf::GAS_FOR_CREDENTIAL_VIEW)
+            .get_credential(credential_id.clone())
+            .and(
+                qf::ext_zk_verifier::ext(filter.verifier)
+                    .with_static_gas(qf::GAS_FOR_CREDENTIAL_VIEW)
+                    .is_credential_valid(credential_id.clone()),
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(qf::GAS_FOR_DONOR_CALLBACK)
+                    .on_qf_donor_verified(round_id, donor, credential_id),
+            )
+    }
+
+    /// Callback for `verify_qf_donor`: accept the donor if the credential
+    /// is theirs, of the round's circuit, and currently valid.
+    #[private]
+    pub fn on_qf_donor_verified(
+        &mut self,
+        round_id: String,
+        donor: AccountId,
+        credential_id: String,
+        #[callback_result] credential: Result<Option<qf::VerifierCredential>, PromiseError>,
+        #[callback_result] valid: Result<Option<bool>, PromiseError>,
+    ) -> bool {
+        let circuit_type = self
+            .qf_rounds
+            .get(&round_id)
+            .and_then(|round| round.sybil_filter.as_ref())
+            .map(|filter| filter.circuit_type.clone());
+        let verified = match (credential, valid, circuit_type) {
+            (Ok(Some(credential)), Ok(Some(true)), Some(circuit_type)) => {
+                credential.owner == donor && credential.circuit_type == circuit_type
+            }
+            _ => false,
+        };
+        if verified {
+            self.qf_verified_donors
+                .insert((round_id.clone(), donor.clone()));
+        }
+        events::emit_qf_donor_verified(&round_id, &donor, &credential_id, verified);
+        verified
+    }
+
+    /// Whether an account passed a round's sybil check.
+    pub fn is_qf_donor_verified(&self, round_id: String, account: AccountId) -> bool {
+        self.qf_verified_donors.contains(&(round_id, account))
+    }
+
+    /// Donate the attached NEAR to an approved application during the
+    /// round window. The donation is forwarded to the applicant and counts
+    /// towards the application's quadratic match.
+    #[payable]
+    pub fn donate(&mut self, round_id: String, application_id: String) {
+        self.assert_not_paused();
+        let donor = env::predecessor_account_id();
+        let amount = env::attached_deposit().as_yoctonear();
+        let now = env::block_timestamp();
+
+        let round = self.qf_round(&round_id);
+        if round.settled_at.is_some() || now < round.starts_at || now >= round.ends_at {
+            ContractError::RoundNotOpen(round_id).panic();
+        }
+        if round.sybil_filter.is_some()
+            && !self
+                .qf_verified_donors
+                .contains(&(round_id.clone(), donor.clone()))
+        {
+            ContractError::DonorNotVerified(donor.to_string()).panic();
+        }
+        if amount == 0 {
+            ContractError::InvalidParams("attach NEAR to donate".to_string()).panic();
+        }
+
+        let app = self
+            .application(&application_id)
+            .unwrap_or_else(|| ContractError::ApplicationNotFound(application_id.clone()).panic());
+        if app.program_id != round.program_id
+            || !matches!(
+                app.status,
+                ApplicationStatus::Approved | ApplicationStatus::Funded
+            )
+        {
+            ContractError::InvalidParams(format!(
+                "{application_id} is not an approved application of {}",
+                round.program_id
+            ))
+            .panic();
+        }
+        let applicant = app.applicant_account_id.clone();
+        if applicant == donor || self.is_project_member(&app.project_id, &donor) {
+            ContractError::InvalidParams("project members cannot donate to themselves".to_string())
+                .panic();
+        }
+
+        self.adjust_contribution(&round_id, &application_id, &donor, amount, true);
+        self.qf_rounds.get_mut(&round_id).unwrap().pending_forwards += 1;
+        events::emit_qf_donation(&round_id, &application_id, &donor, amount, false);
+
+        Promise::new(applicant)
+            .transfer(NearToken::from_yoctonear(amount))
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_TRANSFER_CALLBACK)
+                    .on_donation_forwarded(round_id, application_id, donor, U128(amount)),
+            )
+            .detach();
+    }
+
+    /// Callback for `donate`: if forwarding failed, remove the donation
+    /// from the tally and refund the donor.
+    #[private]
+    pub fn on_donation_forwarded(
+        &mut self,
+        round_id: String,
+        application_id: String,
+        donor: AccountId,
+        amount: U128,
+    ) -> bool {
+        let success = is_promise_success();
+        let round = self.qf_rounds.get_mut(&round_id).unwrap();
+        round.pending_forwards -= 1;
+        if !success {
+            self.adjust_contribution(&round_id, &application_id, &donor, amount.0, false);
+            events::emit_qf_donation(&round_id, &application_id, &donor, amount.0, true);
+            Promise::new(donor)
+                .transfer(NearToken::from_yoctonear(amount.0))
+                .detach();
+        }
+        success
+    }
+
+    /// Donations and matches per application. Before settlement the
+    /// matches are projected from the current tallies.
+    pub fn get_qf_matches(&self, round_id: String) -> Vec<QfMatch> {
+        let round = self.qf_round(&round_id);
+        let Some(tallies) = self.qf_tallies.get(&round_id) else {
+            return vec![];
+        };
+        let matches = if round.settled_at.is_some() {
+            tallies.values().map(|t| t.matched).collect()
+        } else {
+            Self::clr_matches(tallies, round.matching_pool.0)
+        };
+        tallies
+            .iter()
+            .zip(matches)
+            .map(|((application_id, tally), matched)| QfMatch {
+                application_id: application_id.clone(),
+                contributions: U128(tally.contributions),
+                donors: tally.donors,
+                match_amount: U128(matched),
+            })
+            .collect()
+    }
+
+    /// Record each application's CLR match once the round has ended and
+    /// every donation transfer has resolved. Steward only. Matches are
+    /// scaled down pro rata if they would exceed the matching pool; paying
+    /// them out is up to the program.
+    pub fn settle_qf_round(&mut self, round_id: String) {
+        self.assert_not_paused();
+        let caller = env::predecessor_account_id();
+        let round = self.qf_round(&round_id);
+        self.assert_program_registrar(&round.program_id, &caller);
+
+        if round.settled_at.is_some() || env::block_timestamp() < round.ends_at {
+            ContractError::RoundNotOpen(format!("{round_id} cannot be settled yet or again"))
+                .panic();
+        }
+        if round.pending_forwards > 0 {
+            ContractError::RoundNotOpen(format!(
+                "{round_id} has {} donations still being forwarded",
+                round.pending_forwards
+            ))
+            .panic();
+        }
+
+        let mut applications = 0;
+        let mut total_matched: u128 = 0;
+        if let Some(tallies) = self.qf_tallies.get_mut(&round_id) {
+            let matches = Self::clr_matches(tallies, round.matching_pool.0);
+            for ((_, tally), matched) in tallies.iter_mut().zip(matches) {
+                tally.matched = matched;
+                total_matched += matched;
+                applications += 1;
+            }
+        }
+
+        let round = self.qf_rounds.get_mut(&round_id).unwrap();
+        round.settled_at = Some(env::block_timestamp());
+        round.total_matched = U128(total_matched);
+        events::emit_qf_round_settled(&round_id, applications, total_matched);
+    }
+
+    // =========================================================================
+    // Stats
+    // =========================================================================
+
+    /// Get ecosystem-wide statistics.
+    pub fn get_ecosystem_stats(&self) -> EcosystemStats {
+        EcosystemStats {
+            total_programs: self.total_programs,
+            total_projects: self.total_projects,
+            total_funded: U128(self.total_funded),
+            total_applications: self.total_applications,
+            active_programs: self.active_programs,
+        }
+    }
+
+    // =========================================================================
+    // Admin
+    // =========================================================================
+
+    /// Pause or unpause the contract.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.assert_owner();
+        self.is_paused = paused;
+        events::emit_contract_paused(paused);
+    }
+
+    // =========================================================================
+    // Internal helpers
+    // =========================================================================
+
+    fn assert_owner(&self) {
+        if env::predecessor_account_id() != self.owner {
+            ContractError::Unauthorized.panic();
+        }
+    }
+
+    /// Program by ID, converting a record stored before state version 2.
+    fn program(&self, program_id: &str) -> Option<Program> {
+        self.programs.get(program_id).cloned().or_else(|| {
+            self.legacy_programs
+                .get(program_id)
+                .cloned()
+                .map(migration::ProgramV1::upgrade)
+        })
+    }
+
+    /// Mutable program. A record stored before state version 2 is moved to
+    /// the current map.
+    fn program_mut(&mut self, program_id: &str) -> Option<&mut Program> {
+        if let Some(old) = self.legacy_programs.remove(program_id) {
+            self.programs.insert(program_id.to_string(), old.upgrade());
+        }
+        self.programs.get_mut(program_id)
+    }
+
+    /// Project by ID. A record stored before state version 2 gets its
+    /// aggregates rebuilt from its applications.
+    fn project(&self, project_id: &str) -> Option<Project> {
+        if let Some(project) = self.projects.get(project_id) {
+            return Some(project.clone());
+        }
+        let old = self.legacy_projects.get(project_id)?.clone();
+        Some(old.upgrade(self.recompute_project_aggregates(project_id.to_string())))
+    }
+
+    fn project_mut(&mut self, project_id: &str) -> Option<&mut Project> {
+        self.upgrade_legacy_project(project_id);
+        self.projects.get_mut(project_id)
+    }
+
+    fn project_exists(&self, project_id: &str) -> bool {
+        self.projects.contains_key(project_id) || self.legacy_projects.contains_key(project_id)
+    }
+
+    /// Move a project stored before state version 2 to the current map.
+    /// Done before any of its applications changes, so the rebuilt
+    /// aggregates don't count that change twice.
+    fn upgrade_legacy_project(&mut self, project_id: &str) {
+        if self.projects.contains_key(project_id) {
+            return;
+        }
+        if let Some(project) = self.project(project_id) {
+            self.legacy_projects.remove(project_id);
+            self.projects.insert(project_id.to_string(), project);
+        }
+    }
+
+    /// Application by ID, converting a record stored before state version 2.
+    fn application(&self, application_id: &str) -> Option<Application> {
+        self.applications.get(application_id).cloned().or_else(|| {
+            self.legacy_applications
+                .get(application_id)
+                .cloned()
+                .map(migration::ApplicationV1::upgrade)
+        })
+    }
+
+    /// Mutable application. A record stored before state version 2 is moved
+    /// to the current map, after its project, and from then on counts
+    /// towards its program's commitments.
+    fn application_mut(&mut self, application_id: &str) -> Option<&mut Application> {
+        if let Some(old) = self.legacy_applications.get(application_id).cloned() {
+            self.upgrade_legacy_project(&old.project_id);
+            self.legacy_applications.remove(application_id);
+            let app = old.upgrade();
+            self.record_commitment(&app.program_id, 0, app.committed());
+            self.applications.insert(application_id.to_string(), app);
+        }
+        self.applications.get_mut(application_id)
+    }
+
+    fn application_exists(&self, application_id: &str) -> bool {
+        self.applications.contains_key(application_id)
+            || self.legacy_applications.contains_key(application_id)
+    }
+
+    fn program_application_ids(&self, program_id: &str) -> Vec<String> {
+        storage::index_ids(
+            &self.applications_by_program,
+            &self.legacy_applications_by_program,
+            program_id,
+        )
+    }
+
+    fn project_application_ids(&self, project_id: &str) -> Vec<String> {
+        storage::index_ids(
+            &self.applications_by_project,
+            &self.legacy_applications_by_project,
+            project_id,
+        )
+    }
+
+    /// Set an application's status and apply its side effects. Callers
+    /// check the transition and authorization.
+    fn apply_application_status(
+        &mut self,
+        application_id: &str,
+        new_status: ApplicationStatus,
+        funded_amount: Option<U128>,
+        caller: &AccountId,
+    ) {
+        let app = self.application_mut(application_id).unwrap();
+        let was_funded = app.status == ApplicationStatus::Funded;
+        let before = app.outcome();
+        let committed_before = app.committed();
+
+        app.status = new_status.clone();
+
+        if funded_amount.is_some() {
+            app.funded_amount = funded_amount;
+        }
+
+        if new_status == ApplicationStatus::Completed {
+            app.completed_at = Some(env::block_timestamp());
+        }
+        let program_id = app.program_id.clone();
+        let committed_after = app.committed();
+        self.record_commitment(&program_id, committed_before, committed_after);
+
+        // Track funded count on program
+        if new_status == ApplicationStatus::Funded && !was_funded {
+            if let Some(p) = self.program_mut(&program_id) {
+                p.funded_count += 1;
+            }
+            // Track total funded
+            if let Some(amount) = funded_amount {
+                self.total_funded += amount.0;
+            }
+        }
+        self.record_project_outcome(application_id, before);
+
+        events::emit_application_updated(application_id, new_status.as_str(), caller);
+    }
+
+    /// Move an application's contribution to its project's aggregates from
+    /// `before` to its current outcome.
+    fn record_project_outcome(&mut self, application_id: &str, before: ApplicationOutcome) {
+        let app = self.application(application_id).unwrap();
+        let after = app.outcome();
+        if before == after {
+            return;
+        }
+        if let Some(project) = self.project_mut(&app.project_id) {
+            project.record_outcome(before, after);
+        }
+    }
+
+    /// Move an application's share of its program's commitments from
+    /// `before` to `after`.
+    fn record_commitment(&mut self, program_id: &str, before: u128, after: u128) {
+        if before == after {
+            return;
+        }
+        let committed = self.commitments.get(program_id).copied().unwrap_or(0);
+        self.commitments
+            .insert(program_id.to_string(), committed + after - before);
+    }
+
+    /// Credit a deposit to a program's escrow in `token_id` (`None` for
+    /// NEAR). The first deposit also sets the program's payout token.
+    fn internal_deposit(
+        &mut self,
+        program_id: &str,
+        token_id: Option<AccountId>,
+        funder: &AccountId,
+        amount: u128,
+    ) {
+        let program = self
+            .program(program_id)
+            .unwrap_or_else(|| ContractError::ProgramNotFound(program_id.to_string()).panic());
+        if program.status == ProgramStatus::Closed {
+            ContractError::InvalidParams("program is closed".to_string()).panic();
+        }
+        if amount == 0 {
+            ContractError::InvalidParams("deposit must be positive".to_string()).panic();
+        }
+
+        if !self.payout_tokens.contains_key(program_id) {
+            self.payout_tokens
+                .insert(program_id.to_string(), token_id.clone());
+        }
+        let escrow = storage::escrow_mut(&mut self.escrows, program_id, &token_id);
+        escrow.deposited += amount;
+        events::emit_program_funded(
+            program_id,
+            token_id.as_ref(),
+            funder,
+            amount,
+            escrow.deposited,
+        );
+    }
+
+    fn escrow_view(
+        &self,
+        program_id: &str,
+        token_id: Option<AccountId>,
+        escrow: &Escrow,
+    ) -> EscrowView {
+        let committed = self.committed_funds(program_id, &token_id);
+        EscrowView {
+            program_id: program_id.to_string(),
+            token_id,
+            deposited: U128(escrow.deposited),
+            paid_out: U128(escrow.paid_out),
+            in_flight: U128(escrow.in_flight),
+            reclaimed: U128(escrow.reclaimed),
+            committed: U128(committed),
+            available: U128(escrow.balance().saturating_sub(committed)),
+        }
+    }
+
+    /// Send `amount` of NEAR or a NEP-141 token.
+    fn transfer(
+        &self,
+        token_id: &Option<AccountId>,
+        recipient: AccountId,
+        amount: u128,
+    ) -> Promise {
+        match token_id {
+            None => Promise::new(recipient).transfer(NearToken::from_yoctonear(amount)),
+            Some(token) => ft::ext_ft::ext(token.clone())
+                .with_attached_deposit(NearToken::from_yoctonear(1))
+                .with_static_gas(ft::GAS_FOR_FT_TRANSFER)
+                .ft_transfer(recipient, U128(amount), None),
+        }
+    }
+
+    /// Pay a new application or milestone payout in the program's payout
+    /// token.
+    fn pay_out(
+        &mut self,
+        program_id: &str,
+        application_id: &str,
+        milestone_index: Option<u32>,
+        recipient: AccountId,
+        amount: u128,
+    ) {
+        let token_id = self.payout_tokens.get(program_id).cloned().unwrap();
+        self.start_payout(
+            program_id,
+            token_id,
+            application_id,
+            milestone_index,
+            recipient,
+            amount,
+        );
+    }
+
+    /// Reserve `amount` from the program's escrow in `token_id` and send
+    /// it to the recipient, settling in `on_payout_complete`.
+    fn start_payout(
+        &mut self,
+        program_id: &str,
+        token_id: Option<AccountId>,
+        application_id: &str,
+        milestone_index: Option<u32>,
+        recipient: AccountId,
+        amount: u128,
+    ) {
+        let escrow = storage::escrow_mut(&mut self.escrows, program_id, &token_id);
+        if escrow.balance() < amount {
+            ContractError::InsufficientEscrow {
+                program_id: program_id.to_string(),
+                available: escrow.balance(),
+                requested: amount,
+            }
+            .panic();
+        }
+        escrow.in_flight += amount;
+
+        let key = storage::payout_key(application_id, milestone_index);
+        let payout = Payout {
+            program_id: program_id.to_string(),
+            application_id: application_id.to_string(),
+            milestone_index,
+            token_id: token_id.clone(),
+            recipient: recipient.clone(),
+            amount: U128(amount),
+            status: PayoutStatus::InFlight,
+            updated_at: env::block_timestamp(),
+        };
+        events::emit_payout(&key, &payout);
+        self.payouts.insert(key.clone(), payout);
+
+        self.transfer(&token_id, recipient, amount)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_TRANSFER_CALLBACK)
+                    .on_payout_complete(key),
+            )
+            .detach();
+    }
+
+    /// Panics unless `amount` can fund an approved application of an
+    /// escrowed program: positive, at most its request, and within the
+    /// escrow not committed to the program's other applications.
+    fn assert_payout_available(&self, program_id: &str, application_id: &str, amount: u128) {
+        let requested = self.application(application_id).unwrap().requested_amount.0;
+        if amount == 0 {
+            ContractError::InvalidParams("funded_amount must be positive".to_string()).panic();
+        }
+        if amount > requested {
+            ContractError::InvalidParams(format!(
+                "funded_amount {amount} exceeds the requested {requested}"
+            ))
+            .panic();
+        }
+
+        let token_id = self.payout_tokens.get(program_id).cloned().unwrap();
+        let balance = self
+            .escrows
+            .get(program_id)
+            .and_then(|balances| balances.get(&token_id))
+            .map_or(0, Escrow::balance);
+        // The application's own request is among the committed funds
+        let committed_elsewhere = self.committed_funds(program_id, &token_id) - requested;
+        let available = balance.saturating_sub(committed_elsewhere);
+        if amount > available {
+            ContractError::InsufficientEscrow {
+                program_id: program_id.to_string(),
+                available,
+                requested: amount,
+            }
+            .panic();
+        }
+    }
+
+    /// Amount of `token_id` owed from escrow to the program's applications
+    /// but not yet sent: failed payouts in that token and, if it is the
+    /// payout token, the program's running commitments.
+    fn committed_funds(&self, program_id: &str, token_id: &Option<AccountId>) -> u128 {
+        let failed = self
+            .escrows
+            .get(program_id)
+            .and_then(|balances| balances.get(token_id))
+            .map_or(0, |escrow| escrow.failed);
+        if self.payout_tokens.get(program_id) != Some(token_id) {
+            return failed;
+        }
+        failed + self.commitments.get(program_id).copied().unwrap_or(0)
+    }
+
+    fn qf_round(&self, round_id: &str) -> QfRound {
+        self.qf_rounds
+            .get(round_id)
+            .cloned()
+            .unwrap_or_else(|| ContractError::RoundNotFound(round_id.to_string()).panic())
+    }
+
+    /// Add (or, to undo a failed donation, remove) `amount` from a donor's
+    /// contribution to an application and update the round totals.
+    fn adjust_contribution(
+        &mut self,
+        round_id: &str,
+        application_id: &str,
+        donor: &AccountId,
+        amount: u128,
+        add: bool,
+    ) {
+        let key = (
+            round_id.to_string(),
+            application_id.to_string(),
+            donor.clone(),
+        );
+        let old = self.qf_contributions.get(&key).copied().unwrap_or(0);
+        let new = if add {
+            old + amount
+        } else {
+            old.saturating_sub(amount)
+        };
+        if new == 0 {
+            self.qf_contributions.remove(&key);
+        } else {
+            self.qf_contributions.insert(key, new);
+        }
+
+        let tally = storage::qf_tally_mut(&mut self.qf_tallies, round_id, application_id);
+        tally.contributions = tally.contributions - old + new;
+        tally.sqrt_sum = tally.sqrt_sum - qf::isqrt(old) + qf::isqrt(new);
+        match (old, new) {
+            (0, n) if n > 0 => tally.donors += 1,
+            (o, 0) if o > 0 => tally.donors -= 1,
+            _ => {}
+        }
+
+        let round = self.qf_rounds.get_mut(round_id).unwrap();
+        round.total_donations = U128(round.total_donations.0 - old + new);
+    }
+
+    /// CLR matches of a round's tallies, in iteration order, capped by the
+    /// pool.
+    fn clr_matches(tallies: &IterableMap<String, QfTally>, pool: u128) -> Vec<u128> {
+        let raw: Vec<u128> = tallies
+            .values()
+            .map(|t| qf::raw_match(t.sqrt_sum, t.contributions))
+            .collect();
+        qf::cap_matches(&raw, pool)
+    }
+
+    /// Average of an application's review scores.
+    fn application_score(&self, application_id: &str) -> ApplicationScore {
+        let (count, total) = self
+            .application_reviews
+            .get(application_id)
+            .map(|reviews| {
+                reviews.values().fold((0u32, 0u64), |(n, sum), r| {
+                    (n + 1, sum + u64::from(r.score_bps))
+                })
+            })
+            .unwrap_or_default();
+        ApplicationScore {
+            application_id: application_id.to_string(),
+            review_count: count,
+            average_score_bps: if count == 0 {
+                0
+            } else {
+                (total / u64::from(count)) as u32
+            },
+        }
+    }
+
+    /// Whether `account` owns the project or is listed on its team.
+    fn is_project_member(&self, project_id: &str, account: &AccountId) -> bool {
+        self.project(project_id).is_some_and(|project| {
+            &project.registered_by == account
+                || project
+                    .team_members
+                    .iter()
+                    .any(|m| &m.account_id == account)
+        })
+    }
+
+    /// Parse an ISO-8601 program deadline, panicking if malformed.
+    fn parse_deadline(deadline: &str) -> u64 {
+        types::parse_iso8601(deadline).unwrap_or_else(|| {
+            ContractError::InvalidParams(format!("deadline is not ISO-8601: {deadline}")).panic()
+        })
+    }
+
+    /// Panics unless `account` owns the project (`registered_by`).
+    fn assert_project_owner(&self, project_id: &str, account: &AccountId) {
+        let project = self
+            .project(project_id)
+            .unwrap_or_else(|| ContractError::ProjectNotFound(project_id.to_string()).panic());
+        if &project.registered_by != account {
+            ContractError::Unauthorized.panic();
+        }
+    }
+
+    fn is_team_member_or_invited(&self, project_id: &str, account: &AccountId) -> bool {
+        self.project(project_id)
+            .is_some_and(|p| p.team_members.iter().any(|m| &m.account_id == account))
+            || self
+                .team_invites
+                .get(project_id)
+                .is_some_and(|invites| invites.contains_key(account))
+    }
+
+    /// Panics unless `account` is the program's steward (`registered_by`).
+    fn assert_program_registrar(&self, program_id: &str, account: &AccountId) {
+        let program = self
+            .program(program_id)
+            .unwrap_or_else(|| ContractError::ProgramNotFound(program_id.to_string()).panic());
+        if &program.registered_by != account {
+            ContractError::Unauthorized.panic();
+        }
+    }
+
+    /// Whether `account` may review the program's applications: its
+    /// registrar or a delegated reviewer.
+    fn is_program_reviewer(&self, program_id: &str, account: &AccountId) -> bool {
+        let is_registrar = self
+            .program(program_id)
+            .is_some_and(|p| &p.registered_by == account);
+        is_registrar
+            || self
+                .program_reviewers
+                .get(program_id)
+                .is_some_and(|set| set.contains(account))
+    }
+
+    fn assert_not_paused(&self) {
+        if self.is_paused {
+            ContractError::ContractPaused.panic();
+        }
+    }
+}
+
+use near_sdk::borsh;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, PromiseResult};
+
+    fn owner() -> AccountId {
+        "owner.testnet".parse().unwrap()
+    }
+
+    fn alice() -> AccountId {
+        "alice.testnet".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob.testnet".parse().unwrap()
+    }
+
+    fn carol() -> AccountId {
+        "carol.testnet".parse().unwrap()
+    }
+
+    fn dave() -> AccountId {
+        "dave.testnet".parse().unwrap()
     }
 
     fn setup_context(predecessor: &AccountId) {
@@ -420,202 +2287,1711 @@ mod tests {
         testing_env!(context);
     }
 
-    fn init_contract() -> GrantRegistry {
-        setup_context(&owner());
-        GrantRegistry::new(owner())
+    fn setup_deposit(predecessor: &AccountId, yocto: u128) {
+        let context = VMContextBuilder::new()
+            .predecessor_account_id(predecessor.clone())
+            .attached_deposit(NearToken::from_yoctonear(yocto))
+            .block_timestamp(1_700_000_000 * 1_000_000_000)
+            .build();
+        testing_env!(context);
+    }
+
+    fn setup_at(predecessor: &AccountId, yocto: u128, timestamp: u64) {
+        let context = VMContextBuilder::new()
+            .predecessor_account_id(predecessor.clone())
+            .attached_deposit(NearToken::from_yoctonear(yocto))
+            .block_timestamp(timestamp)
+            .build();
+        testing_env!(context);
+    }
+
+    /// Context for a callback invoked by the contract itself, with the
+    /// outcome of the promise it follows.
+    fn setup_callback(result: PromiseResult) {
+        let context = VMContextBuilder::new()
+            .predecessor_account_id(accounts(0))
+            .current_account_id(accounts(0))
+            .build();
+        testing_env!(
+            context,
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    fn init_contract() -> GrantRegistry {
+        setup_context(&owner());
+        GrantRegistry::new(owner())
+    }
+
+    fn register_test_program(contract: &mut GrantRegistry) -> String {
+        setup_context(&alice());
+        let program_id = "gitcoin-gg20".to_string();
+        contract.register_program(
+            program_id.clone(),
+            "Gitcoin GG20".into(),
+            "Gitcoin Grants Round 20".into(),
+            "Gitcoin".into(),
+            vec![Chain::Ethereum, Chain::Near],
+            vec![Category::PublicGoods, Category::Infrastructure],
+            U128(1000000),
+            Some(U128(1000)),
+            Some(U128(50000)),
+            Some("2025-12-31T23:59:59Z".into()),
+            "https://gitcoin.co".into(),
+            Some("https://gitcoin.co/apply".into()),
+            ProgramStatus::Active,
+        );
+        program_id
+    }
+
+    fn register_test_project(contract: &mut GrantRegistry) -> String {
+        setup_context(&alice());
+        let project_id = "shade-studio".to_string();
+        contract.register_project(
+            project_id.clone(),
+            "Shade Studio".into(),
+            "Privacy-first DAO governance toolkit".into(),
+            Some("https://shade.studio".into()),
+            vec![TeamMember {
+                account_id: alice(),
+                name: "Alice".into(),
+                role: "Lead Developer".into(),
+                profile_url: None,
+            }],
+        );
+        project_id
+    }
+
+    #[test]
+    fn test_init() {
+        let contract = init_contract();
+        assert_eq!(contract.owner, owner());
+        assert!(!contract.is_paused);
+
+        let stats = contract.get_ecosystem_stats();
+        assert_eq!(stats.total_programs, 0);
+        assert_eq!(stats.total_projects, 0);
+        assert_eq!(stats.total_applications, 0);
+    }
+
+    #[test]
+    fn test_register_program() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        let program = contract.get_program(program_id).unwrap();
+        assert_eq!(program.name, "Gitcoin GG20");
+        assert_eq!(program.organization, "Gitcoin");
+        assert_eq!(program.chains.len(), 2);
+        assert_eq!(program.categories.len(), 2);
+        assert_eq!(contract.get_ecosystem_stats().total_programs, 1);
+        assert_eq!(contract.get_ecosystem_stats().active_programs, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Program already exists")]
+    fn test_duplicate_program() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        register_test_program(&mut contract);
+    }
+
+    #[test]
+    fn test_search_programs() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+
+        // No filters
+        let results = contract.search_programs(None, None, None, None, None);
+        assert_eq!(results.len(), 1);
+
+        // Filter by category
+        let results = contract.search_programs(Some(Category::PublicGoods), None, None, None, None);
+        assert_eq!(results.len(), 1);
+
+        // Filter by non-matching category
+        let results = contract.search_programs(Some(Category::Gaming), None, None, None, None);
+        assert_eq!(results.len(), 0);
+
+        // Filter by chain
+        let results = contract.search_programs(None, Some(Chain::Near), None, None, None);
+        assert_eq!(results.len(), 1);
+
+        // Filter by status
+        let results = contract.search_programs(None, None, Some(ProgramStatus::Active), None, None);
+        assert_eq!(results.len(), 1);
+
+        let results = contract.search_programs(None, None, Some(ProgramStatus::Closed), None, None);
+        assert_eq!(results.len(), 0);
+    }
+
+    #[test]
+    fn test_register_project() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+
+        let project = contract.get_project(project_id).unwrap();
+        assert_eq!(project.name, "Shade Studio");
+        assert_eq!(project.team_members.len(), 1);
+        assert_eq!(contract.get_ecosystem_stats().total_projects, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Project already exists")]
+    fn test_duplicate_project() {
+        let mut contract = init_contract();
+        register_test_project(&mut contract);
+        register_test_project(&mut contract);
+    }
+
+    #[test]
+    fn test_record_application() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        let project_id = register_test_project(&mut contract);
+
+        setup_context(&alice());
+        contract.record_application(
+            "app-1".into(),
+            program_id.clone(),
+            project_id.clone(),
+            "Privacy Dashboard Grant".into(),
+            U128(25000),
+        );
+
+        let stats = contract.get_ecosystem_stats();
+        assert_eq!(stats.total_applications, 1);
+
+        let program = contract.get_program(program_id).unwrap();
+        assert_eq!(program.application_count, 1);
+
+        let project = contract.get_project(project_id.clone()).unwrap();
+        assert_eq!(project.application_count, 1);
+
+        let history = contract.get_project_history(project_id);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].title, "Privacy Dashboard Grant");
+    }
+
+    #[test]
+    fn test_update_application() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        let project_id = register_test_project(&mut contract);
+
+        setup_context(&alice());
+        contract.record_application(
+            "app-1".into(),
+            program_id.clone(),
+            project_id,
+            "Grant Proposal".into(),
+            U128(10000),
+        );
+
+        as_reviewer(&mut contract);
+        contract.update_application("app-1".into(), ApplicationStatus::Approved, None);
+        contract.update_application("app-1".into(), ApplicationStatus::Funded, Some(U128(10000)));
+
+        let program = contract.get_program(program_id).unwrap();
+        assert_eq!(program.funded_count, 1);
+        assert_eq!(contract.get_ecosystem_stats().total_funded, U128(10000));
+    }
+
+    /// alice applies to gitcoin-gg20 from shade-studio for `amount`.
+    fn apply_for(contract: &mut GrantRegistry, amount: u128) {
+        setup_context(&alice());
+        contract.record_application(
+            "app-1".into(),
+            "gitcoin-gg20".into(),
+            "shade-studio".into(),
+            "Grant Proposal".into(),
+            U128(amount),
+        );
+    }
+
+    #[test]
+    fn test_program_deadline_is_parsed() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        let program = contract.get_program(program_id.clone()).unwrap();
+        assert_eq!(program.deadline, Some(1_767_225_599 * 1_000_000_000));
+
+        let update = ProgramUpdate {
+            deadline: Some(Some("2026-06-30".into())),
+            ..Default::default()
+        };
+        contract.update_program(program_id.clone(), update);
+        let program = contract.get_program(program_id).unwrap();
+        assert_eq!(program.deadline, Some(1_782_863_999_999_999_999));
+    }
+
+    #[test]
+    #[should_panic(expected = "deadline is not ISO-8601")]
+    fn test_program_deadline_must_be_iso8601() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        let update = ProgramUpdate {
+            deadline: Some(Some("end of Q4".into())),
+            ..Default::default()
+        };
+        contract.update_program(program_id, update);
+    }
+
+    #[test]
+    #[should_panic(expected = "Program not accepting applications: gitcoin-gg20 is paused")]
+    fn test_application_requires_active_program() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        register_test_project(&mut contract);
+        contract.set_program_status(program_id, ProgramStatus::Paused);
+
+        apply_for(&mut contract, 10000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Program deadline has passed: gitcoin-gg20")]
+    fn test_application_after_deadline() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        register_test_project(&mut contract);
+
+        setup_at(&alice(), 0, 1_767_225_600 * 1_000_000_000);
+        contract.record_application(
+            "app-1".into(),
+            "gitcoin-gg20".into(),
+            "shade-studio".into(),
+            "Grant Proposal".into(),
+            U128(10000),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Requested amount 999 outside program bounds [1000, 50000]")]
+    fn test_application_below_min_amount() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        register_test_project(&mut contract);
+
+        apply_for(&mut contract, 999);
+    }
+
+    #[test]
+    #[should_panic(expected = "Requested amount 50001 outside program bounds [1000, 50000]")]
+    fn test_application_above_max_amount() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        register_test_project(&mut contract);
+
+        apply_for(&mut contract, 50001);
+    }
+
+    #[test]
+    fn test_application_at_max_amount() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        register_test_project(&mut contract);
+
+        apply_for(&mut contract, 50000);
+        assert!(contract.get_application("app-1".into()).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a team member: bob.testnet")]
+    fn test_application_requires_team_membership() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        register_test_project(&mut contract);
+
+        setup_context(&bob());
+        contract.record_application(
+            "app-1".into(),
+            "gitcoin-gg20".into(),
+            "shade-studio".into(),
+            "Grant Proposal".into(),
+            U128(10000),
+        );
+    }
+
+    #[test]
+    fn test_project_aggregates_follow_transitions() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        let project_id = register_test_project(&mut contract);
+
+        setup_context(&alice());
+        for id in ["app-1", "app-2"] {
+            contract.record_application(
+                id.into(),
+                program_id.clone(),
+                project_id.clone(),
+                "Grant Proposal".into(),
+                U128(10000),
+            );
+        }
+
+        as_reviewer(&mut contract);
+        contract.update_application("app-1".into(), ApplicationStatus::Approved, None);
+        contract.update_application("app-1".into(), ApplicationStatus::Funded, Some(U128(8000)));
+        contract.update_application("app-2".into(), ApplicationStatus::Approved, None);
+        let project = contract.get_project(project_id.clone()).unwrap();
+        assert_eq!(project.total_funded, U128(8000));
+        assert_eq!(project.success_rate, 100);
+
+        // Reversing an approval counts against the project
+        contract.update_application("app-2".into(), ApplicationStatus::Rejected, None);
+        let project = contract.get_project(project_id.clone()).unwrap();
+        assert_eq!(project.total_funded, U128(8000));
+        assert_eq!((project.successful_count, project.decided_count), (1, 2));
+        assert_eq!(project.success_rate, 50);
+
+        let audit = contract.recompute_project_aggregates(project_id);
+        assert_eq!(audit.total_funded, project.total_funded);
+        assert_eq!(audit.application_count, project.application_count);
+        assert_eq!(audit.success_rate, project.success_rate);
+    }
+
+    #[test]
+    fn test_withdrawn_application_leaves_success_rate() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        as_reviewer(&mut contract);
+        contract.update_application(app_id.clone(), ApplicationStatus::Approved, None);
+        assert_eq!(
+            contract
+                .get_project("shade-studio".into())
+                .unwrap()
+                .success_rate,
+            100
+        );
+
+        setup_context(&bob());
+        contract.update_application(app_id, ApplicationStatus::Withdrawn, None);
+        let project = contract.get_project("shade-studio".into()).unwrap();
+        assert_eq!((project.decided_count, project.success_rate), (0, 0));
+    }
+
+    /// Bob's (or another applicant's) application from alice's project,
+    /// joining its team first.
+    fn record_test_application(contract: &mut GrantRegistry, applicant: &AccountId) -> String {
+        let program_id = register_test_program(contract);
+        let project_id = register_test_project(contract);
+        if applicant != &alice() {
+            contract.add_team_member(
+                project_id.clone(),
+                team_member(applicant.clone(), "Applicant"),
+            );
+            setup_context(applicant);
+            contract.accept_team_invite(project_id.clone());
+        }
+        setup_context(applicant);
+        contract.record_application(
+            "app-1".into(),
+            program_id,
+            project_id,
+            "Grant Proposal".into(),
+            U128(10000),
+        );
+        "app-1".into()
+    }
+
+    fn application_status(contract: &GrantRegistry, id: &str) -> ApplicationStatus {
+        contract.applications.get(id).unwrap().status.clone()
+    }
+
+    /// Have alice delegate carol, who is on no project team, as a reviewer
+    /// of gitcoin-gg20, and act as carol.
+    fn as_reviewer(contract: &mut GrantRegistry) {
+        setup_context(&alice());
+        contract.add_program_reviewer("gitcoin-gg20".into(), carol());
+        setup_context(&carol());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid status transition: submitted -> funded")]
+    fn test_update_application_rejects_skipped_status() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        setup_context(&alice());
+        contract.update_application(app_id, ApplicationStatus::Funded, Some(U128(10000)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid status transition: completed -> draft")]
+    fn test_update_application_terminal_status() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        as_reviewer(&mut contract);
+        contract.update_application(app_id.clone(), ApplicationStatus::Approved, None);
+        contract.update_application(app_id.clone(), ApplicationStatus::Funded, None);
+        contract.update_application(app_id.clone(), ApplicationStatus::Completed, None);
+        contract.update_application(app_id, ApplicationStatus::Draft, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_applicant_cannot_approve() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        setup_context(&bob());
+        contract.update_application(app_id, ApplicationStatus::Approved, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_stranger_cannot_review() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        setup_context(&owner());
+        contract.update_application(app_id, ApplicationStatus::UnderReview, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_registrar_cannot_withdraw() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        setup_context(&alice());
+        contract.update_application(app_id, ApplicationStatus::Withdrawn, None);
+    }
+
+    #[test]
+    fn test_applicant_withdraws() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        setup_context(&bob());
+        contract.update_application(app_id.clone(), ApplicationStatus::Withdrawn, None);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::Withdrawn
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Conflict of interest")]
+    fn test_team_member_cannot_decide_application() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        // alice stewards the program and is on shade-studio's team
+        setup_context(&alice());
+        contract.update_application(app_id, ApplicationStatus::Approved, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "funded_amount is only accepted")]
+    fn test_funded_amount_requires_funded_status() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        as_reviewer(&mut contract);
+        contract.update_application(app_id, ApplicationStatus::Approved, Some(U128(10000)));
+    }
+
+    #[test]
+    fn test_delegated_reviewer() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+
+        setup_context(&alice());
+        contract.add_program_reviewer("gitcoin-gg20".into(), owner());
+        assert_eq!(
+            contract.get_program_reviewers("gitcoin-gg20".into()),
+            vec![owner()]
+        );
+
+        setup_context(&owner());
+        contract.update_application(app_id.clone(), ApplicationStatus::UnderReview, None);
+        contract.update_application(app_id.clone(), ApplicationStatus::Approved, None);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::Approved
+        );
+
+        setup_context(&alice());
+        contract.remove_program_reviewer("gitcoin-gg20".into(), owner());
+        assert!(contract
+            .get_program_reviewers("gitcoin-gg20".into())
+            .is_empty());
+        assert!(!contract.is_program_reviewer("gitcoin-gg20", &owner()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_only_registrar_adds_reviewers() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+
+        setup_context(&bob());
+        contract.add_program_reviewer("gitcoin-gg20".into(), bob());
+    }
+
+    #[test]
+    fn test_update_program() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        setup_context(&alice());
+        contract.update_program(
+            program_id.clone(),
+            ProgramUpdate {
+                name: Some("Gitcoin GG21".into()),
+                max_amount: Some(Some(U128(75000))),
+                ..Default::default()
+            },
+        );
+
+        let program = contract.get_program(program_id).unwrap();
+        assert_eq!(program.name, "Gitcoin GG21");
+        assert_eq!(program.max_amount, Some(U128(75000)));
+        assert_eq!(program.organization, "Gitcoin");
+    }
+
+    #[test]
+    fn test_update_program_clears_optional_fields() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        setup_context(&alice());
+        contract.update_program(
+            program_id.clone(),
+            ProgramUpdate {
+                min_amount: Some(None),
+                deadline: Some(None),
+                application_url: Some(None),
+                ..Default::default()
+            },
+        );
+
+        let program = contract.get_program(program_id).unwrap();
+        assert_eq!(program.min_amount, None);
+        assert_eq!(program.max_amount, Some(U128(50000)));
+        assert_eq!(program.deadline, None);
+        assert_eq!(program.application_url, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_update_program_not_steward() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        setup_context(&bob());
+        contract.update_program(
+            program_id,
+            ProgramUpdate {
+                name: Some("Hijacked".into()),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_set_program_status_tracks_active_count() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+        assert_eq!(contract.get_ecosystem_stats().active_programs, 1);
+
+        setup_context(&alice());
+        contract.set_program_status(program_id.clone(), ProgramStatus::Paused);
+        assert_eq!(contract.get_ecosystem_stats().active_programs, 0);
+
+        contract.set_program_status(program_id.clone(), ProgramStatus::Active);
+        assert_eq!(contract.get_ecosystem_stats().active_programs, 1);
+
+        contract.set_program_status(program_id.clone(), ProgramStatus::Closed);
+        assert_eq!(contract.get_ecosystem_stats().active_programs, 0);
+        assert_eq!(
+            contract.get_program(program_id).unwrap().status,
+            ProgramStatus::Closed
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid status transition: closed -> active")]
+    fn test_closed_program_cannot_reopen() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        setup_context(&alice());
+        contract.set_program_status(program_id.clone(), ProgramStatus::Closed);
+        contract.set_program_status(program_id, ProgramStatus::Active);
+    }
+
+    #[test]
+    fn test_transfer_program() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        setup_context(&alice());
+        contract.transfer_program(program_id.clone(), bob());
+        assert_eq!(
+            contract
+                .get_program(program_id.clone())
+                .unwrap()
+                .registered_by,
+            bob()
+        );
+
+        setup_context(&bob());
+        contract.set_program_status(program_id.clone(), ProgramStatus::Paused);
+        assert!(!contract.is_program_reviewer(&program_id, &alice()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_previous_steward_loses_control() {
+        let mut contract = init_contract();
+        let program_id = register_test_program(&mut contract);
+
+        setup_context(&alice());
+        contract.transfer_program(program_id.clone(), bob());
+        contract.set_program_status(program_id, ProgramStatus::Paused);
     }
 
-    fn register_test_program(contract: &mut GrantRegistry) -> String {
+    const COMMENT_HASH: &str = "5e8ff9bf55ba3508199d22e984129be6f3b0b1c5e1d3b2b2a8c1b9a6e0f1c2d3";
+
+    /// Records bob's application and appoints carol and dave to review it
+    /// against a two-criterion rubric needing two reviews.
+    fn setup_review(contract: &mut GrantRegistry) -> String {
+        let app_id = record_test_application(contract, &bob());
         setup_context(&alice());
-        let program_id = "gitcoin-gg20".to_string();
-        contract.register_program(
-            program_id.clone(),
-            "Gitcoin GG20".into(),
-            "Gitcoin Grants Round 20".into(),
-            "Gitcoin".into(),
-            vec![Chain::Ethereum, Chain::Near],
-            vec![Category::PublicGoods, Category::Infrastructure],
-            "1000000".into(),
-            Some("1000".into()),
-            Some("50000".into()),
-            Some("2025-12-31T23:59:59Z".into()),
-            "https://gitcoin.co".into(),
-            Some("https://gitcoin.co/apply".into()),
-            ProgramStatus::Active,
+        contract.add_program_reviewer("gitcoin-gg20".into(), carol());
+        contract.add_program_reviewer("gitcoin-gg20".into(), dave());
+        contract.set_program_rubric(
+            "gitcoin-gg20".into(),
+            Rubric {
+                criteria: vec![
+                    RubricCriterion {
+                        name: "impact".into(),
+                        max_score: 10,
+                        weight: 2,
+                    },
+                    RubricCriterion {
+                        name: "team".into(),
+                        max_score: 10,
+                        weight: 1,
+                    },
+                ],
+                min_reviews: 2,
+                approve_threshold_bps: 7_000,
+                reject_threshold_bps: 4_000,
+            },
         );
-        program_id
+        app_id
     }
 
-    fn register_test_project(contract: &mut GrantRegistry) -> String {
+    fn review(contract: &mut GrantRegistry, reviewer: &AccountId, app_id: &str, scores: Vec<u32>) {
+        setup_context(reviewer);
+        contract.submit_review(app_id.to_string(), scores, COMMENT_HASH.into());
+    }
+
+    #[test]
+    fn test_reviews_auto_approve() {
+        let mut contract = init_contract();
+        let app_id = setup_review(&mut contract);
+
+        review(&mut contract, &carol(), &app_id, vec![9, 6]);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::UnderReview
+        );
+
+        review(&mut contract, &dave(), &app_id, vec![8, 8]);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::Approved
+        );
+
+        let score = contract.get_application_score(app_id.clone());
+        assert_eq!(score.review_count, 2);
+        // carol (2*9000 + 6000) / 3 = 8000, dave 8000
+        assert_eq!(score.average_score_bps, 8_000);
+        assert_eq!(contract.get_application_reviews(app_id).len(), 2);
+    }
+
+    #[test]
+    fn test_reviews_auto_reject() {
+        let mut contract = init_contract();
+        let app_id = setup_review(&mut contract);
+
+        review(&mut contract, &carol(), &app_id, vec![2, 5]);
+        review(&mut contract, &dave(), &app_id, vec![3, 3]);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_reviews_between_thresholds_stay_under_review() {
+        let mut contract = init_contract();
+        let app_id = setup_review(&mut contract);
+
+        review(&mut contract, &carol(), &app_id, vec![5, 5]);
+        review(&mut contract, &dave(), &app_id, vec![6, 6]);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::UnderReview
+        );
+
+        // Revising a review replaces it rather than adding another
+        review(&mut contract, &dave(), &app_id, vec![10, 10]);
+        let score = contract.get_application_score(app_id.clone());
+        assert_eq!(score.review_count, 2);
+        assert_eq!(score.average_score_bps, 7_500);
+        assert_eq!(
+            application_status(&contract, &app_id),
+            ApplicationStatus::Approved
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Conflict of interest")]
+    fn test_team_member_cannot_review() {
+        let mut contract = init_contract();
+        let app_id = setup_review(&mut contract);
+
+        // alice stewards the program and is on shade-studio's team
+        review(&mut contract, &alice(), &app_id, vec![10, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_non_reviewer_cannot_review() {
+        let mut contract = init_contract();
+        let app_id = setup_review(&mut contract);
+
+        review(&mut contract, &owner(), &app_id, vec![10, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Program has no review rubric")]
+    fn test_review_requires_rubric() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
         setup_context(&alice());
-        let project_id = "shade-studio".to_string();
-        contract.register_project(
-            project_id.clone(),
-            "Shade Studio".into(),
-            "Privacy-first DAO governance toolkit".into(),
-            Some("https://shade.studio".into()),
-            vec![TeamMember {
-                account_id: "alice.testnet".into(),
-                name: "Alice".into(),
-                role: "Lead Developer".into(),
-                profile_url: None,
-            }],
+        contract.add_program_reviewer("gitcoin-gg20".into(), carol());
+
+        review(&mut contract, &carol(), &app_id, vec![10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds")]
+    fn test_review_score_out_of_range() {
+        let mut contract = init_contract();
+        let app_id = setup_review(&mut contract);
+
+        review(&mut contract, &carol(), &app_id, vec![11, 0]);
+    }
+
+    const DELIVERABLE_HASH: &str =
+        "a3f1c2d4e5b6a7980112233445566778899aabbccddeeff00112233445566778";
+
+    fn milestone_input(title: &str, amount: u128) -> MilestoneInput {
+        MilestoneInput {
+            title: title.into(),
+            amount: U128(amount),
+            due_at: 1_800_000_000 * 1_000_000_000,
+            deliverable_hash: DELIVERABLE_HASH.into(),
+        }
+    }
+
+    /// Bob's 10000 application split into two tranches, approved by carol
+    /// as reviewer.
+    fn setup_milestones(contract: &mut GrantRegistry) -> String {
+        let app_id = record_test_application(contract, &bob());
+        contract.set_milestones(
+            app_id.clone(),
+            vec![
+                milestone_input("MVP", 4000),
+                milestone_input("Launch", 6000),
+            ],
+        );
+        as_reviewer(contract);
+        contract.update_application(app_id.clone(), ApplicationStatus::Approved, None);
+        app_id
+    }
+
+    #[test]
+    fn test_milestones_drive_funding() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+
+        setup_context(&bob());
+        contract.submit_milestone(app_id.clone(), 0, COMMENT_HASH.into());
+        assert_eq!(
+            contract
+                .get_pending_milestones("gitcoin-gg20".into(), Some(MilestoneStatus::Submitted))
+                .len(),
+            1
+        );
+
+        setup_context(&carol());
+        contract.review_milestone(app_id.clone(), 0, true);
+        let app = contract.get_application(app_id.clone()).unwrap();
+        assert_eq!(app.status, ApplicationStatus::Funded);
+        assert_eq!(app.funded_amount, Some(U128(4000)));
+        assert_eq!(
+            contract
+                .get_program("gitcoin-gg20".into())
+                .unwrap()
+                .funded_count,
+            1
+        );
+        assert_eq!(
+            contract
+                .get_project("shade-studio".into())
+                .unwrap()
+                .total_funded,
+            U128(4000)
+        );
+
+        let pending = contract.get_pending_milestones("gitcoin-gg20".into(), None);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].index, 1);
+
+        setup_context(&bob());
+        contract.submit_milestone(app_id.clone(), 1, COMMENT_HASH.into());
+        setup_context(&carol());
+        contract.review_milestone(app_id.clone(), 1, true);
+
+        let app = contract.get_application(app_id).unwrap();
+        assert_eq!(app.status, ApplicationStatus::Completed);
+        assert_eq!(app.funded_amount, Some(U128(10000)));
+        assert!(app.completed_at.is_some());
+        assert_eq!(contract.get_ecosystem_stats().total_funded, U128(10000));
+        assert!(contract
+            .get_pending_milestones("gitcoin-gg20".into(), None)
+            .is_empty());
+        let audit = contract.recompute_project_aggregates("shade-studio".into());
+        assert_eq!(audit.total_funded, U128(10000));
+        assert_eq!(
+            contract
+                .get_project("shade-studio".into())
+                .unwrap()
+                .total_funded,
+            U128(10000)
+        );
+    }
+
+    #[test]
+    fn test_rejected_milestone_can_be_resubmitted() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+
+        setup_context(&bob());
+        contract.submit_milestone(app_id.clone(), 0, COMMENT_HASH.into());
+        setup_context(&carol());
+        contract.review_milestone(app_id.clone(), 0, false);
+
+        let app = contract.get_application(app_id.clone()).unwrap();
+        assert_eq!(app.milestones[0].status, MilestoneStatus::Rejected);
+        assert_eq!(app.status, ApplicationStatus::Approved);
+
+        setup_context(&bob());
+        contract.submit_milestone(app_id.clone(), 0, COMMENT_HASH.into());
+        let app = contract.get_application(app_id).unwrap();
+        assert_eq!(app.milestones[0].status, MilestoneStatus::Submitted);
+    }
+
+    #[test]
+    #[should_panic(expected = "milestone amounts sum to 9000, requested 10000")]
+    fn test_milestones_must_cover_request() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+        contract.set_milestones(
+            app_id,
+            vec![
+                milestone_input("MVP", 4000),
+                milestone_input("Launch", 5000),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "status follows milestone approvals")]
+    fn test_milestone_application_cannot_be_funded_directly() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+
+        setup_context(&carol());
+        contract.update_application(app_id, ApplicationStatus::Funded, Some(U128(10000)));
+    }
+
+    #[test]
+    #[should_panic(expected = "not approved")]
+    fn test_milestone_evidence_requires_approval() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+        contract.set_milestones(app_id.clone(), vec![milestone_input("All", 10000)]);
+        contract.submit_milestone(app_id, 0, COMMENT_HASH.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "has no pending submission")]
+    fn test_milestone_review_requires_submission() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+
+        setup_context(&carol());
+        contract.review_milestone(app_id, 0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "application is rejected, not approved")]
+    fn test_milestone_review_of_rejected_application() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+        setup_context(&bob());
+        contract.submit_milestone(app_id.clone(), 0, COMMENT_HASH.into());
+        setup_context(&carol());
+        contract.update_application(app_id.clone(), ApplicationStatus::Rejected, None);
+        contract.review_milestone(app_id, 0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "application is withdrawn, not approved")]
+    fn test_milestone_review_of_withdrawn_application() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+        setup_context(&bob());
+        contract.submit_milestone(app_id.clone(), 0, COMMENT_HASH.into());
+        contract.update_application(app_id.clone(), ApplicationStatus::Withdrawn, None);
+
+        setup_context(&carol());
+        contract.review_milestone(app_id, 0, false);
+    }
+
+    fn escrow(contract: &GrantRegistry) -> EscrowView {
+        contract
+            .get_program_escrow("gitcoin-gg20".into(), None)
+            .unwrap()
+    }
+
+    fn approve_milestone(contract: &mut GrantRegistry, app_id: &str, index: u32) {
+        setup_context(&bob());
+        contract.submit_milestone(app_id.to_string(), index, COMMENT_HASH.into());
+        setup_context(&carol());
+        contract.review_milestone(app_id.to_string(), index, true);
+    }
+
+    #[test]
+    fn test_escrow_pays_milestones() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+        setup_deposit(&alice(), 12_000);
+        contract.fund_program("gitcoin-gg20".into());
+        assert_eq!(escrow(&contract).committed, U128(10_000));
+        assert_eq!(escrow(&contract).available, U128(2_000));
+
+        approve_milestone(&mut contract, &app_id, 0);
+        let payout = contract.get_payout(app_id.clone(), Some(0)).unwrap();
+        assert_eq!(payout.status, PayoutStatus::InFlight);
+        assert_eq!(payout.recipient, bob());
+        assert_eq!(escrow(&contract).in_flight, U128(4_000));
+        assert_eq!(escrow(&contract).committed, U128(6_000));
+
+        setup_callback(PromiseResult::Successful(vec![]));
+        assert!(contract.on_payout_complete(storage::payout_key(&app_id, Some(0))));
+        let view = escrow(&contract);
+        assert_eq!(view.paid_out, U128(4_000));
+        assert_eq!(view.in_flight, U128(0));
+        assert_eq!(view.available, U128(2_000));
+    }
+
+    #[test]
+    fn test_failed_payout_rolls_back_and_retries() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+        setup_deposit(&alice(), 10_000);
+        contract.fund_program("gitcoin-gg20".into());
+        approve_milestone(&mut contract, &app_id, 0);
+
+        setup_callback(PromiseResult::Failed);
+        assert!(!contract.on_payout_complete(storage::payout_key(&app_id, Some(0))));
+        let view = escrow(&contract);
+        assert_eq!(view.paid_out, U128(0));
+        assert_eq!(view.in_flight, U128(0));
+        // The failed tranche is still owed
+        assert_eq!(view.committed, U128(10_000));
+        assert_eq!(
+            contract.get_payout(app_id.clone(), Some(0)).unwrap().status,
+            PayoutStatus::Failed
+        );
+
+        setup_context(&bob());
+        contract.retry_payout(app_id.clone(), Some(0));
+        assert_eq!(
+            contract.get_payout(app_id, Some(0)).unwrap().status,
+            PayoutStatus::InFlight
+        );
+        assert_eq!(escrow(&contract).in_flight, U128(4_000));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Insufficient escrow for gitcoin-gg20: 3000 available, 4000 requested"
+    )]
+    fn test_payout_cannot_exceed_deposits() {
+        let mut contract = init_contract();
+        let app_id = setup_milestones(&mut contract);
+        setup_deposit(&alice(), 3_000);
+        contract.fund_program("gitcoin-gg20".into());
+
+        approve_milestone(&mut contract, &app_id, 0);
+    }
+
+    #[test]
+    fn test_escrow_pays_funded_application() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+        setup_deposit(&owner(), 10_000);
+        contract.fund_program("gitcoin-gg20".into());
+
+        as_reviewer(&mut contract);
+        contract.update_application(app_id.clone(), ApplicationStatus::Approved, None);
+        contract.update_application(app_id.clone(), ApplicationStatus::Funded, Some(U128(8000)));
+
+        let payout = contract.get_payout(app_id, None).unwrap();
+        assert_eq!(payout.amount, U128(8_000));
+        assert_eq!(escrow(&contract).in_flight, U128(8_000));
+    }
+
+    /// Bob's 10000 application and a second one for 5000, both approved by
+    /// carol, with 12000 in escrow.
+    fn setup_two_approved(contract: &mut GrantRegistry) -> (String, String) {
+        let first = record_test_application(contract, &bob());
+        contract.record_application(
+            "app-2".into(),
+            "gitcoin-gg20".into(),
+            "shade-studio".into(),
+            "Follow-up".into(),
+            U128(5000),
+        );
+        setup_deposit(&owner(), 12_000);
+        contract.fund_program("gitcoin-gg20".into());
+
+        as_reviewer(contract);
+        contract.update_application(first.clone(), ApplicationStatus::Approved, None);
+        contract.update_application("app-2".into(), ApplicationStatus::Approved, None);
+        (first, "app-2".into())
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Insufficient escrow for gitcoin-gg20: 7000 available, 10000 requested"
+    )]
+    fn test_funding_cannot_take_other_commitments() {
+        let mut contract = init_contract();
+        let (first, _) = setup_two_approved(&mut contract);
+
+        contract.update_application(first, ApplicationStatus::Funded, Some(U128(10_000)));
+    }
+
+    #[test]
+    fn test_funding_within_uncommitted_escrow() {
+        let mut contract = init_contract();
+        let (first, second) = setup_two_approved(&mut contract);
+
+        contract.update_application(first, ApplicationStatus::Funded, Some(U128(7_000)));
+        contract.update_application(second, ApplicationStatus::Funded, Some(U128(5_000)));
+        let view = escrow(&contract);
+        assert_eq!(view.in_flight, U128(12_000));
+        assert_eq!(view.committed, U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "funded_amount must be positive")]
+    fn test_escrowed_funding_rejects_zero() {
+        let mut contract = init_contract();
+        let (first, _) = setup_two_approved(&mut contract);
+
+        contract.update_application(first, ApplicationStatus::Funded, Some(U128(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "funded_amount 10001 exceeds the requested 10000")]
+    fn test_escrowed_funding_capped_at_request() {
+        let mut contract = init_contract();
+        let (first, _) = setup_two_approved(&mut contract);
+
+        contract.update_application(first, ApplicationStatus::Funded, Some(U128(10_001)));
+    }
+
+    #[test]
+    #[should_panic(expected = "escrowed programs need a funded_amount")]
+    fn test_escrowed_funding_needs_amount() {
+        let mut contract = init_contract();
+        let app_id = record_test_application(&mut contract, &bob());
+        setup_deposit(&owner(), 10_000);
+        contract.fund_program("gitcoin-gg20".into());
+
+        as_reviewer(&mut contract);
+        contract.update_application(app_id.clone(), ApplicationStatus::Approved, None);
+        contract.update_application(app_id, ApplicationStatus::Funded, None);
+    }
+
+    #[test]
+    fn test_reclaim_uncommitted_funds_after_close() {
+        let mut contract = init_contract();
+        setup_milestones(&mut contract);
+        setup_deposit(&alice(), 15_000);
+        contract.fund_program("gitcoin-gg20".into());
+
+        setup_context(&alice());
+        contract.set_program_status("gitcoin-gg20".into(), ProgramStatus::Closed);
+        let _ = contract.reclaim_program_funds("gitcoin-gg20".into(), None);
+        let view = escrow(&contract);
+        assert_eq!(view.reclaimed, U128(5_000));
+        assert_eq!(view.available, U128(0));
+
+        setup_callback(PromiseResult::Failed);
+        assert!(!contract.on_funds_reclaimed("gitcoin-gg20".into(), None, alice(), U128(5_000)));
+        assert_eq!(escrow(&contract).reclaimed, U128(0));
+    }
+
+    /// Escrow owed to gitcoin-gg20's applications, rebuilt by walking
+    /// every application and payout of the program.
+    fn recompute_committed(contract: &GrantRegistry) -> u128 {
+        let failed = |app_id: &str, index| {
+            contract
+                .payouts
+                .get(&storage::payout_key(app_id, index))
+                .filter(|p| p.status == PayoutStatus::Failed)
+                .map_or(0, |p| p.amount.0)
+        };
+        let mut committed = 0;
+        for app_id in contract.program_application_ids("gitcoin-gg20") {
+            let app = contract.application(&app_id).unwrap();
+            committed += app.committed();
+            committed += failed(&app_id, None);
+            for index in 0..app.milestones.len() as u32 {
+                committed += failed(&app_id, Some(index));
+            }
+        }
+        committed
+    }
+
+    #[test]
+    fn test_commitments_match_recompute() {
+        let mut contract = init_contract();
+        let milestones_app = setup_milestones(&mut contract);
+        setup_context(&bob());
+        contract.record_application(
+            "app-2".into(),
+            "gitcoin-gg20".into(),
+            "shade-studio".into(),
+            "Follow-up".into(),
+            U128(5000),
+        );
+        setup_deposit(&owner(), 20_000);
+        contract.fund_program("gitcoin-gg20".into());
+        let check = |contract: &GrantRegistry, expected: u128| {
+            assert_eq!(escrow(contract).committed, U128(expected));
+            assert_eq!(recompute_committed(contract), expected);
+        };
+        check(&contract, 10_000);
+
+        setup_context(&carol());
+        contract.update_application("app-2".into(), ApplicationStatus::Approved, None);
+        check(&contract, 15_000);
+        contract.update_application("app-2".into(), ApplicationStatus::Funded, Some(U128(5000)));
+        check(&contract, 10_000);
+
+        // A failed payout stays owed until it is retried
+        setup_callback(PromiseResult::Failed);
+        contract.on_payout_complete(storage::payout_key("app-2", None));
+        check(&contract, 15_000);
+
+        approve_milestone(&mut contract, &milestones_app, 0);
+        check(&contract, 11_000);
+
+        setup_context(&bob());
+        contract.retry_payout("app-2".into(), None);
+        check(&contract, 6_000);
+        setup_callback(PromiseResult::Successful(vec![]));
+        contract.on_payout_complete(storage::payout_key("app-2", None));
+        check(&contract, 6_000);
+        assert_eq!(escrow(&contract).available, U128(5_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "only be reclaimed after the program closes")]
+    fn test_reclaim_requires_closed_program() {
+        let mut contract = init_contract();
+        register_test_program(&mut contract);
+        setup_deposit(&alice(), 15_000);
+        contract.fund_program("gitcoin-gg20".into());
+
+        setup_context(&alice());
+        let _ = contract.reclaim_program_funds("gitcoin-gg20".into(), None);
+    }
+
+    /// Stand-in for a NEP-141 token contract: delivers `ft_transfer_call`
+    /// to the registry as the token would, and reads back the
+    /// `ft_transfer` calls the registry sends it.
+    struct MockFt {
+        id: AccountId,
+    }
+
+    impl MockFt {
+        fn usdc() -> Self {
+            Self {
+                id: "usdc.testnet".parse().unwrap(),
+            }
+        }
+
+        /// Returns the amount the registry hands back as unused.
+        fn transfer_call(
+            &self,
+            contract: &mut GrantRegistry,
+            sender: &AccountId,
+            amount: u128,
+            msg: &str,
+        ) -> u128 {
+            setup_context(&self.id);
+            match contract.ft_on_transfer(sender.clone(), U128(amount), msg.to_string()) {
+                PromiseOrValue::Value(unused) => unused.0,
+                PromiseOrValue::Promise(_) => panic!("expected an immediate value"),
+            }
+        }
+
+        /// `(receiver, amount)` of each `ft_transfer` sent to this token.
+        fn received_transfers(&self) -> Vec<(AccountId, u128)> {
+            near_sdk::test_utils::get_created_receipts()
+                .into_iter()
+                .filter(|receipt| receipt.receiver_id == self.id)
+                .flat_map(|receipt| receipt.actions)
+                .filter_map(|action| match action {
+                    near_sdk::mock::MockAction::FunctionCallWeight {
+                        method_name,
+                        args,
+                        attached_deposit,
+                        ..
+                    } if method_name == b"ft_transfer" => {
+                        assert_eq!(attached_deposit, NearToken::from_yoctonear(1));
+                        let args: serde_json::Value = serde_json::from_slice(&args).unwrap();
+                        Some((
+                            args["receiver_id"].as_str().unwrap().parse().unwrap(),
+                            args["amount"].as_str().unwrap().parse().unwrap(),
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    const FUND_GG20: &str = r#"{"program_id":"gitcoin-gg20"}"#;
+
+    fn whitelist(contract: &mut GrantRegistry, token: &MockFt) {
+        setup_context(&owner());
+        contract.add_ft_token(token.id.clone());
+    }
+
+    #[test]
+    fn test_ft_funding_and_payout() {
+        let mut contract = init_contract();
+        let usdc = MockFt::usdc();
+        whitelist(&mut contract, &usdc);
+        let app_id = setup_milestones(&mut contract);
+
+        assert_eq!(
+            usdc.transfer_call(&mut contract, &alice(), 10_000, FUND_GG20),
+            0
         );
-        project_id
+        assert_eq!(
+            contract.get_program_payout_token("gitcoin-gg20".into()),
+            Some(Some(usdc.id.clone()))
+        );
+        let view = contract
+            .get_program_escrow("gitcoin-gg20".into(), Some(usdc.id.clone()))
+            .unwrap();
+        assert_eq!(view.deposited, U128(10_000));
+        assert_eq!(view.available, U128(0));
+
+        approve_milestone(&mut contract, &app_id, 0);
+        assert_eq!(usdc.received_transfers(), vec![(bob(), 4_000)]);
+        let payout = contract.get_payout(app_id.clone(), Some(0)).unwrap();
+        assert_eq!(payout.token_id, Some(usdc.id.clone()));
+
+        setup_callback(PromiseResult::Successful(vec![]));
+        assert!(contract.on_payout_complete(storage::payout_key(&app_id, Some(0))));
+        let view = contract
+            .get_program_escrow("gitcoin-gg20".into(), Some(usdc.id.clone()))
+            .unwrap();
+        assert_eq!(view.paid_out, U128(4_000));
     }
 
     #[test]
-    fn test_init() {
-        let contract = init_contract();
-        assert_eq!(contract.owner, owner());
-        assert!(!contract.is_paused);
+    fn test_ft_and_near_balances_are_separate() {
+        let mut contract = init_contract();
+        let usdc = MockFt::usdc();
+        whitelist(&mut contract, &usdc);
+        let app_id = setup_milestones(&mut contract);
 
-        let stats = contract.get_ecosystem_stats();
-        assert_eq!(stats.total_programs, 0);
-        assert_eq!(stats.total_projects, 0);
-        assert_eq!(stats.total_applications, 0);
+        usdc.transfer_call(&mut contract, &alice(), 10_000, FUND_GG20);
+        setup_deposit(&owner(), 500);
+        contract.fund_program("gitcoin-gg20".into());
+
+        let balances = contract.get_program_balances("gitcoin-gg20".into());
+        assert_eq!(balances.len(), 2);
+        let near = contract
+            .get_program_escrow("gitcoin-gg20".into(), None)
+            .unwrap();
+        // NEAR isn't the payout token, so none of it is committed
+        assert_eq!(near.available, U128(500));
+
+        approve_milestone(&mut contract, &app_id, 0);
+        let near = contract
+            .get_program_escrow("gitcoin-gg20".into(), None)
+            .unwrap();
+        assert_eq!(near.in_flight, U128(0));
+        assert_eq!(usdc.received_transfers().len(), 1);
     }
 
     #[test]
-    fn test_register_program() {
+    fn test_failed_ft_payout_rolls_back() {
         let mut contract = init_contract();
-        let program_id = register_test_program(&mut contract);
+        let usdc = MockFt::usdc();
+        whitelist(&mut contract, &usdc);
+        let app_id = setup_milestones(&mut contract);
+        usdc.transfer_call(&mut contract, &alice(), 10_000, FUND_GG20);
+        approve_milestone(&mut contract, &app_id, 0);
 
-        let program = contract.get_program(program_id).unwrap();
-        assert_eq!(program.name, "Gitcoin GG20");
-        assert_eq!(program.organization, "Gitcoin");
-        assert_eq!(program.chains.len(), 2);
-        assert_eq!(program.categories.len(), 2);
-        assert_eq!(contract.get_ecosystem_stats().total_programs, 1);
-        assert_eq!(contract.get_ecosystem_stats().active_programs, 1);
+        setup_callback(PromiseResult::Failed);
+        assert!(!contract.on_payout_complete(storage::payout_key(&app_id, Some(0))));
+        let view = contract
+            .get_program_escrow("gitcoin-gg20".into(), Some(usdc.id.clone()))
+            .unwrap();
+        assert_eq!(view.in_flight, U128(0));
+        assert_eq!(view.paid_out, U128(0));
+
+        setup_context(&carol());
+        contract.retry_payout(app_id, Some(0));
+        assert_eq!(usdc.received_transfers(), vec![(bob(), 4_000)]);
     }
 
     #[test]
-    #[should_panic(expected = "Program already exists")]
-    fn test_duplicate_program() {
+    #[should_panic(expected = "Token not whitelisted: usdc.testnet")]
+    fn test_ft_funding_requires_whitelist() {
         let mut contract = init_contract();
         register_test_program(&mut contract);
+        MockFt::usdc().transfer_call(&mut contract, &alice(), 10_000, FUND_GG20);
+    }
+
+    #[test]
+    #[should_panic(expected = "msg must be")]
+    fn test_ft_funding_requires_program_message() {
+        let mut contract = init_contract();
+        let usdc = MockFt::usdc();
+        whitelist(&mut contract, &usdc);
         register_test_program(&mut contract);
+        usdc.transfer_call(&mut contract, &alice(), 10_000, "gitcoin-gg20");
     }
 
     #[test]
-    fn test_search_programs() {
+    fn test_reclaim_ft_after_close() {
         let mut contract = init_contract();
+        let usdc = MockFt::usdc();
+        whitelist(&mut contract, &usdc);
         register_test_program(&mut contract);
+        usdc.transfer_call(&mut contract, &alice(), 7_000, FUND_GG20);
 
-        // No filters
-        let results = contract.search_programs(None, None, None, None, None);
-        assert_eq!(results.len(), 1);
+        setup_context(&alice());
+        contract.set_program_status("gitcoin-gg20".into(), ProgramStatus::Closed);
+        let _ = contract.reclaim_program_funds("gitcoin-gg20".into(), Some(usdc.id.clone()));
+        assert_eq!(usdc.received_transfers(), vec![(alice(), 7_000)]);
+    }
 
-        // Filter by category
-        let results = contract.search_programs(
-            Some(Category::PublicGoods),
-            None,
-            None,
-            None,
+    const NEAR: u128 = 10u128.pow(24);
+    const ROUND_START: u64 = 1_600_000_000 * 1_000_000_000;
+    const ROUND_END: u64 = 1_800_000_000 * 1_000_000_000;
+
+    /// Two approved applications in gitcoin-gg20 (bob's app-1 and owner's
+    /// app-2) and a round over them with a 1 NEAR matching pool.
+    fn setup_round(contract: &mut GrantRegistry, sybil_filter: Option<SybilFilter>) {
+        record_test_application(contract, &bob());
+        setup_context(&owner());
+        contract.register_project(
+            "zk-tools".into(),
+            "ZK Tools".into(),
+            "Proof tooling".into(),
             None,
+            vec![],
         );
-        assert_eq!(results.len(), 1);
+        contract.record_application(
+            "app-2".into(),
+            "gitcoin-gg20".into(),
+            "zk-tools".into(),
+            "Tooling".into(),
+            U128(5000),
+        );
+        as_reviewer(contract);
+        contract.update_application("app-1".into(), ApplicationStatus::Approved, None);
+        contract.update_application("app-2".into(), ApplicationStatus::Approved, None);
+        setup_context(&alice());
+        contract.create_qf_round(
+            "gg20-qf".into(),
+            "gitcoin-gg20".into(),
+            ROUND_START,
+            ROUND_END,
+            U128(NEAR),
+            sybil_filter,
+        );
+    }
 
-        // Filter by non-matching category
-        let results = contract.search_programs(Some(Category::Gaming), None, None, None, None);
-        assert_eq!(results.len(), 0);
+    /// Donate and resolve the forward to the applicant successfully.
+    fn donate(contract: &mut GrantRegistry, donor: &AccountId, app_id: &str, yocto: u128) {
+        setup_deposit(donor, yocto);
+        contract.donate("gg20-qf".into(), app_id.into());
+        setup_callback(PromiseResult::Successful(vec![]));
+        contract.on_donation_forwarded("gg20-qf".into(), app_id.into(), donor.clone(), U128(yocto));
+    }
 
-        // Filter by chain
-        let results = contract.search_programs(None, Some(Chain::Near), None, None, None);
-        assert_eq!(results.len(), 1);
+    fn match_of(contract: &GrantRegistry, app_id: &str) -> u128 {
+        contract
+            .get_qf_matches("gg20-qf".into())
+            .into_iter()
+            .find(|m| m.application_id == app_id)
+            .map_or(0, |m| m.match_amount.0)
+    }
 
-        // Filter by status
-        let results = contract.search_programs(
-            None,
-            None,
-            Some(ProgramStatus::Active),
-            None,
-            None,
-        );
-        assert_eq!(results.len(), 1);
+    #[test]
+    fn test_qf_matches_favour_broad_support() {
+        let mut contract = init_contract();
+        setup_round(&mut contract, None);
 
-        let results = contract.search_programs(
-            None,
-            None,
-            Some(ProgramStatus::Closed),
-            None,
-            None,
-        );
-        assert_eq!(results.len(), 0);
+        // app-1: two donors of 0.25 → (2·0.5)² − 0.5 = 0.5 NEAR raw match
+        donate(&mut contract, &carol(), "app-1", NEAR / 4);
+        donate(&mut contract, &dave(), "app-1", NEAR / 4);
+        // app-2: one donor of 0.5 → no match
+        donate(&mut contract, &carol(), "app-2", NEAR / 2);
+
+        assert_eq!(match_of(&contract, "app-1"), NEAR / 2);
+        assert_eq!(match_of(&contract, "app-2"), 0);
+        let round = contract.get_qf_round("gg20-qf".into()).unwrap();
+        assert_eq!(round.total_donations, U128(NEAR));
+
+        // A repeat donation grows the donor's contribution, not the donor count
+        donate(&mut contract, &dave(), "app-1", NEAR / 4);
+        let app1 = contract
+            .get_qf_matches("gg20-qf".into())
+            .into_iter()
+            .find(|m| m.application_id == "app-1")
+            .unwrap();
+        assert_eq!(app1.donors, 2);
+        assert_eq!(app1.contributions, U128(3 * NEAR / 4));
     }
 
     #[test]
-    fn test_register_project() {
+    fn test_qf_matches_capped_by_pool_and_settled() {
         let mut contract = init_contract();
-        let project_id = register_test_project(&mut contract);
+        setup_round(&mut contract, None);
 
-        let project = contract.get_project(project_id).unwrap();
-        assert_eq!(project.name, "Shade Studio");
-        assert_eq!(project.team_members.len(), 1);
-        assert_eq!(contract.get_ecosystem_stats().total_projects, 1);
+        // Each application: four donors of 1 NEAR → (4)² − 4 = 12 NEAR raw
+        for app_id in ["app-1", "app-2"] {
+            for donor in [
+                carol(),
+                dave(),
+                "erin.testnet".parse().unwrap(),
+                "frank.testnet".parse().unwrap(),
+            ] {
+                donate(&mut contract, &donor, app_id, NEAR);
+            }
+        }
+        assert_eq!(match_of(&contract, "app-1"), NEAR / 2);
+        assert_eq!(match_of(&contract, "app-2"), NEAR / 2);
+
+        setup_at(&alice(), 0, ROUND_END);
+        contract.settle_qf_round("gg20-qf".into());
+        let round = contract.get_qf_round("gg20-qf".into()).unwrap();
+        assert!(round.settled_at.is_some());
+        assert_eq!(round.total_matched, U128(NEAR));
+        assert_eq!(match_of(&contract, "app-1"), NEAR / 2);
     }
 
     #[test]
-    #[should_panic(expected = "Project already exists")]
-    fn test_duplicate_project() {
+    #[should_panic(expected = "QF round not open")]
+    fn test_qf_settle_waits_for_round_end() {
         let mut contract = init_contract();
-        register_test_project(&mut contract);
-        register_test_project(&mut contract);
+        setup_round(&mut contract, None);
+
+        setup_context(&alice());
+        contract.settle_qf_round("gg20-qf".into());
     }
 
     #[test]
-    fn test_record_application() {
+    #[should_panic(expected = "QF round not open: gg20-qf")]
+    fn test_qf_donation_outside_window() {
         let mut contract = init_contract();
-        let program_id = register_test_program(&mut contract);
-        let project_id = register_test_project(&mut contract);
+        setup_round(&mut contract, None);
 
-        setup_context(&alice());
-        contract.record_application(
+        setup_at(&carol(), NEAR, ROUND_END);
+        contract.donate("gg20-qf".into(), "app-1".into());
+    }
+
+    #[test]
+    #[should_panic(expected = "project members cannot donate to themselves")]
+    fn test_qf_no_self_donation() {
+        let mut contract = init_contract();
+        setup_round(&mut contract, None);
+
+        donate(&mut contract, &bob(), "app-1", NEAR);
+    }
+
+    #[test]
+    fn test_qf_failed_forward_refunds_donor() {
+        let mut contract = init_contract();
+        setup_round(&mut contract, None);
+        setup_deposit(&carol(), NEAR);
+        contract.donate("gg20-qf".into(), "app-1".into());
+
+        setup_callback(PromiseResult::Failed);
+        assert!(!contract.on_donation_forwarded(
+            "gg20-qf".into(),
             "app-1".into(),
-            program_id.clone(),
-            project_id.clone(),
-            "Privacy Dashboard Grant".into(),
-            "25000".into(),
+            carol(),
+            U128(NEAR)
+        ));
+        let refunds: Vec<_> = near_sdk::test_utils::get_created_receipts()
+            .into_iter()
+            .filter(|r| r.receiver_id == carol())
+            .collect();
+        assert_eq!(refunds.len(), 1);
+        let app1 = contract.get_qf_matches("gg20-qf".into());
+        assert_eq!(app1[0].contributions, U128(0));
+        assert_eq!(app1[0].donors, 0);
+        assert_eq!(
+            contract
+                .get_qf_round("gg20-qf".into())
+                .unwrap()
+                .total_donations,
+            U128(0)
         );
+    }
 
-        let stats = contract.get_ecosystem_stats();
-        assert_eq!(stats.total_applications, 1);
+    #[test]
+    #[should_panic(expected = "gg20-qf has 1 donations still being forwarded")]
+    fn test_qf_settle_waits_for_forwards() {
+        let mut contract = init_contract();
+        setup_round(&mut contract, None);
+        donate(&mut contract, &carol(), "app-1", NEAR);
+        setup_deposit(&dave(), NEAR);
+        contract.donate("gg20-qf".into(), "app-1".into());
+        assert_eq!(
+            contract
+                .get_qf_round("gg20-qf".into())
+                .unwrap()
+                .pending_forwards,
+            1
+        );
 
-        let program = contract.get_program(program_id).unwrap();
-        assert_eq!(program.application_count, 1);
+        setup_at(&alice(), 0, ROUND_END);
+        contract.settle_qf_round("gg20-qf".into());
+    }
 
-        let project = contract.get_project(project_id.clone()).unwrap();
-        assert_eq!(project.application_count, 1);
+    fn verifier_filter() -> SybilFilter {
+        SybilFilter {
+            verifier: "zk-verifier.testnet".parse().unwrap(),
+            circuit_type: "verified-builder".into(),
+        }
+    }
 
-        let history = contract.get_project_history(project_id);
-        assert_eq!(history.len(), 1);
-        assert_eq!(history[0].title, "Privacy Dashboard Grant");
+    fn verifier_credential(owner: &AccountId, circuit_type: &str) -> qf::VerifierCredential {
+        qf::VerifierCredential {
+            owner: owner.clone(),
+            circuit_type: circuit_type.into(),
+        }
     }
 
     #[test]
-    fn test_update_application() {
+    #[should_panic(expected = "Donor has no sybil-check credential: carol.testnet")]
+    fn test_qf_sybil_filter_blocks_unverified() {
         let mut contract = init_contract();
-        let program_id = register_test_program(&mut contract);
-        let project_id = register_test_project(&mut contract);
+        setup_round(&mut contract, Some(verifier_filter()));
 
-        setup_context(&alice());
-        contract.record_application(
-            "app-1".into(),
-            program_id.clone(),
-            project_id,
-            "Grant Proposal".into(),
-            "10000".into(),
-        );
+        donate(&mut contract, &carol(), "app-1", NEAR);
+    }
 
-        contract.update_application(
-            "app-1".into(),
-            ApplicationStatus::Funded,
-            Some("10000".into()),
+    #[test]
+    fn test_qf_sybil_filter_accepts_valid_credential() {
+        let mut contract = init_contract();
+        setup_round(&mut contract, Some(verifier_filter()));
+
+        setup_context(&carol());
+        let _ = contract.verify_qf_donor("gg20-qf".into(), "cred-1".into());
+        setup_callback(PromiseResult::Successful(vec![]));
+        assert!(contract.on_qf_donor_verified(
+            "gg20-qf".into(),
+            carol(),
+            "cred-1".into(),
+            Ok(Some(verifier_credential(&carol(), "verified-builder"))),
+            Ok(Some(true)),
+        ));
+        assert!(contract.is_qf_donor_verified("gg20-qf".into(), carol()));
+
+        donate(&mut contract, &carol(), "app-1", NEAR);
+        assert_eq!(
+            contract
+                .get_qf_round("gg20-qf".into())
+                .unwrap()
+                .total_donations,
+            U128(NEAR)
         );
+    }
 
-        let program = contract.get_program(program_id).unwrap();
-        assert_eq!(program.funded_count, 1);
+    #[test]
+    fn test_qf_sybil_filter_rejects_bad_credentials() {
+        let mut contract = init_contract();
+        setup_round(&mut contract, Some(verifier_filter()));
+
+        setup_callback(PromiseResult::Successful(vec![]));
+        // Someone else's credential
+        assert!(!contract.on_qf_donor_verified(
+            "gg20-qf".into(),
+            carol(),
+            "cred-1".into(),
+            Ok(Some(verifier_credential(&dave(), "verified-builder"))),
+            Ok(Some(true)),
+        ));
+        // Wrong circuit
+        assert!(!contract.on_qf_donor_verified(
+            "gg20-qf".into(),
+            carol(),
+            "cred-1".into(),
+            Ok(Some(verifier_credential(&carol(), "team-attestation"))),
+            Ok(Some(true)),
+        ));
+        // Revoked or expired
+        assert!(!contract.on_qf_donor_verified(
+            "gg20-qf".into(),
+            carol(),
+            "cred-1".into(),
+            Ok(Some(verifier_credential(&carol(), "verified-builder"))),
+            Ok(Some(false)),
+        ));
+        assert!(!contract.is_qf_donor_verified("gg20-qf".into(), carol()));
+    }
+
+    #[test]
+    fn test_get_program_applications() {
+        let mut contract = init_contract();
+        record_test_application(&mut contract, &bob());
+
+        let apps = contract.get_program_applications("gitcoin-gg20".into());
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].applicant_account_id, bob());
     }
 
     #[test]
@@ -630,7 +4006,7 @@ mod tests {
             "nonexistent".into(),
             project_id,
             "Test".into(),
-            "1000".into(),
+            U128(1000),
         );
     }
 
@@ -650,7 +4026,7 @@ mod tests {
                 "Org".into(),
                 vec![],
                 vec![],
-                "0".into(),
+                U128(0),
                 None,
                 None,
                 None,
@@ -674,7 +4050,7 @@ mod tests {
             "First project by Alice".into(),
             None,
             vec![TeamMember {
-                account_id: "alice.testnet".into(),
+                account_id: alice(),
                 name: "Alice".into(),
                 role: "Lead".into(),
                 profile_url: None,
@@ -686,7 +4062,7 @@ mod tests {
             "Second project by Alice".into(),
             None,
             vec![TeamMember {
-                account_id: "alice.testnet".into(),
+                account_id: alice(),
                 name: "Alice".into(),
                 role: "Lead".into(),
                 profile_url: None,
@@ -701,7 +4077,7 @@ mod tests {
             "First project by Bob".into(),
             None,
             vec![TeamMember {
-                account_id: "bob.testnet".into(),
+                account_id: bob(),
                 name: "Bob".into(),
                 role: "Lead".into(),
                 profile_url: None,
@@ -728,4 +4104,210 @@ mod tests {
         // Total count is still correct
         assert_eq!(contract.get_ecosystem_stats().total_projects, 3);
     }
+
+    fn team_member(account_id: AccountId, name: &str) -> TeamMember {
+        TeamMember {
+            account_id,
+            name: name.into(),
+            role: "Engineer".into(),
+            profile_url: None,
+        }
+    }
+
+    #[test]
+    fn test_team_member_joins_only_after_accepting_invite() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+
+        contract.add_team_member(project_id.clone(), team_member(bob(), "Bob"));
+        assert!(!contract.is_project_member(&project_id, &bob()));
+        assert_eq!(contract.get_team_invites(project_id.clone()).len(), 1);
+
+        setup_context(&bob());
+        contract.accept_team_invite(project_id.clone());
+        assert!(contract.is_project_member(&project_id, &bob()));
+        assert!(contract.get_team_invites(project_id.clone()).is_empty());
+
+        setup_context(&alice());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.add_team_member(project_id.clone(), team_member(bob(), "Bob"));
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_register_project_invites_other_members() {
+        let mut contract = init_contract();
+        setup_context(&alice());
+        contract.register_project(
+            "proj-team".into(),
+            "Team Project".into(),
+            "Registered with a co-founder".into(),
+            None,
+            vec![team_member(alice(), "Alice"), team_member(bob(), "Bob")],
+        );
+
+        let project = contract.get_project("proj-team".into()).unwrap();
+        assert_eq!(project.team_members.len(), 1);
+        assert_eq!(project.team_members[0].account_id, alice());
+        let invites = contract.get_team_invites("proj-team".into());
+        assert_eq!(invites.len(), 1);
+        assert_eq!(invites[0].account_id, bob());
+    }
+
+    #[test]
+    fn test_only_owner_invites_team_members() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+
+        setup_context(&bob());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.add_team_member(project_id.clone(), team_member(carol(), "Carol"));
+        }));
+        assert!(result.is_err());
+
+        setup_context(&carol());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.accept_team_invite(project_id.clone());
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_and_remove_team_member() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+        contract.add_team_member(project_id.clone(), team_member(bob(), "Bob"));
+        setup_context(&bob());
+        contract.accept_team_invite(project_id.clone());
+
+        // The member edits their own entry
+        contract.update_team_member(
+            project_id.clone(),
+            bob(),
+            TeamMemberUpdate {
+                role: Some("Designer".into()),
+                profile_url: Some(Some("https://bob.dev".into())),
+                ..Default::default()
+            },
+        );
+        let project = contract.get_project(project_id.clone()).unwrap();
+        let entry = project
+            .team_members
+            .iter()
+            .find(|m| m.account_id == bob())
+            .unwrap();
+        assert_eq!(entry.name, "Bob");
+        assert_eq!(entry.role, "Designer");
+        assert_eq!(entry.profile_url.as_deref(), Some("https://bob.dev"));
+
+        // Others cannot
+        setup_context(&carol());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let update = TeamMemberUpdate {
+                name: Some("Mallory".into()),
+                ..Default::default()
+            };
+            contract.update_team_member(project_id.clone(), bob(), update);
+        }));
+        assert!(result.is_err());
+
+        // The owner removes them; removing again fails
+        setup_context(&alice());
+        contract.remove_team_member(project_id.clone(), bob());
+        assert!(!contract.is_project_member(&project_id, &bob()));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.remove_team_member(project_id.clone(), bob());
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_team_member_clears_profile_url() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+        let update = TeamMemberUpdate {
+            profile_url: Some(Some("https://alice.dev".into())),
+            ..Default::default()
+        };
+        contract.update_team_member(project_id.clone(), alice(), update);
+
+        // An omitted URL is kept; null clears it
+        let update: TeamMemberUpdate = near_sdk::serde_json::from_str(r#"{"role":"CTO"}"#).unwrap();
+        contract.update_team_member(project_id.clone(), alice(), update);
+        let project = contract.get_project(project_id.clone()).unwrap();
+        assert_eq!(project.team_members[0].role, "CTO");
+        assert_eq!(
+            project.team_members[0].profile_url.as_deref(),
+            Some("https://alice.dev")
+        );
+
+        let update: TeamMemberUpdate =
+            near_sdk::serde_json::from_str(r#"{"profile_url":null}"#).unwrap();
+        contract.update_team_member(project_id.clone(), alice(), update);
+        let project = contract.get_project(project_id).unwrap();
+        assert_eq!(project.team_members[0].role, "CTO");
+        assert_eq!(project.team_members[0].profile_url, None);
+    }
+
+    #[test]
+    fn test_invitee_declines_invite() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+        contract.add_team_member(project_id.clone(), team_member(bob(), "Bob"));
+
+        setup_context(&bob());
+        contract.remove_team_member(project_id.clone(), bob());
+        assert!(contract.get_team_invites(project_id.clone()).is_empty());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.accept_team_invite(project_id.clone());
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_project_ownership_transfer() {
+        let mut contract = init_contract();
+        let project_id = register_test_project(&mut contract);
+
+        contract.propose_project_owner(project_id.clone(), bob());
+        assert_eq!(
+            contract.get_pending_project_owner(project_id.clone()),
+            Some(bob())
+        );
+        // Nothing changes until the new owner accepts
+        assert_eq!(
+            contract
+                .get_project(project_id.clone())
+                .unwrap()
+                .registered_by,
+            alice()
+        );
+
+        setup_context(&carol());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.accept_project_ownership(project_id.clone());
+        }));
+        assert!(result.is_err());
+
+        setup_context(&bob());
+        contract.accept_project_ownership(project_id.clone());
+        assert_eq!(
+            contract
+                .get_project(project_id.clone())
+                .unwrap()
+                .registered_by,
+            bob()
+        );
+        assert_eq!(contract.get_pending_project_owner(project_id.clone()), None);
+        assert!(contract.get_projects_by_owner(alice()).is_empty());
+        assert_eq!(contract.get_projects_by_owner(bob())[0].id, project_id);
+
+        // The previous owner has lost owner rights
+        setup_context(&alice());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.add_team_member(project_id.clone(), team_member(carol(), "Carol"));
+        }));
+        assert!(result.is_err());
+    }
 }
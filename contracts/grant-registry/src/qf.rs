@@ -0,0 +1,141 @@
+//! Quadratic funding (CLR) matching math and the zk-verifier interface used
+//! for sybil filtering.
+//!
+//! Amounts are integers in the token's smallest unit (yoctoNEAR for
+//! donations), which serves as the fixed-point scale: a contribution's
+//! square root is taken in that unit, so one NEAR contributes `10^12`.
+
+use near_sdk::serde::Deserialize;
+use near_sdk::{ext_contract, AccountId, Gas};
+
+/// Gas for each zk-verifier view call of a donor check.
+pub const GAS_FOR_CREDENTIAL_VIEW: Gas = Gas::from_tgas(10);
+/// Gas for the donor check callback.
+pub const GAS_FOR_DONOR_CALLBACK: Gas = Gas::from_tgas(10);
+
+/// The zk-verifier views used to check a donor's credential.
+#[ext_contract(ext_zk_verifier)]
+#[allow(dead_code)]
+pub trait ZkVerifier {
+    fn get_credential(&self, credential_id: String) -> Option<VerifierCredential>;
+    fn is_credential_valid(&self, credential_id: String) -> Option<bool>;
+}
+
+/// Fields of a zk-verifier credential the sybil check reads.
+#[derive(Debug, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VerifierCredential {
+    pub owner: AccountId,
+    pub circuit_type: String,
+}
+
+/// Integer square root, rounded down.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from an upper bound; decreases monotonically to the root.
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// `a * b / c` with a 256-bit intermediate, rounded down. `c` must be
+/// non-zero and the result must fit in a `u128`.
+pub fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    const MASK: u128 = u64::MAX as u128;
+    // Full 256-bit product as (hi, lo).
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+    let lo_lo = a_lo * b_lo;
+    let mid1 = a_hi * b_lo;
+    let mid2 = a_lo * b_hi;
+    let (mid, mid_carry) = mid1.overflowing_add(mid2);
+    let (lo, lo_carry) = lo_lo.overflowing_add(mid << 64);
+    let hi = a_hi * b_hi + (mid >> 64) + ((mid_carry as u128) << 64) + lo_carry as u128;
+
+    // Restoring long division of (hi, lo) by c.
+    let mut remainder = hi % c;
+    let mut quotient = 0u128;
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((lo >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+    quotient
+}
+
+/// Unscaled CLR match of one application: `(Σ√c)² − Σc`, saturating.
+pub fn raw_match(sqrt_sum: u128, contributions: u128) -> u128 {
+    sqrt_sum
+        .saturating_mul(sqrt_sum)
+        .saturating_sub(contributions)
+}
+
+/// Scale raw matches down pro rata when they exceed the matching pool.
+pub fn cap_matches(raw: &[u128], pool: u128) -> Vec<u128> {
+    let total = raw.iter().fold(0u128, |sum, r| sum.saturating_add(*r));
+    if total <= pool {
+        return raw.to_vec();
+    }
+    raw.iter().map(|r| mul_div(*r, pool, total)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_zk_verifier_credential_json() {
+        let json = r#"{"id":"c1","owner":"alice.testnet","circuit_type":"verified-builder",
+            "public_signals":["1"],"verified_at":1,"expires_at":2,"claim":null}"#;
+        let credential: VerifierCredential = serde_json::from_str(json).unwrap();
+        assert_eq!(credential.owner.as_str(), "alice.testnet");
+        assert_eq!(credential.circuit_type, "verified-builder");
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(10u128.pow(24)), 10u128.pow(12));
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn mul_div_handles_wide_products() {
+        assert_eq!(mul_div(6, 7, 3), 14);
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), u128::MAX);
+        let e36 = 10u128.pow(36);
+        assert_eq!(
+            mul_div(3 * e36, 10u128.pow(25), 4 * e36),
+            75 * 10u128.pow(23)
+        );
+    }
+
+    #[test]
+    fn clr_favours_many_small_donors() {
+        // 4 donors of 1 vs 1 donor of 4: (4·1)² − 4 = 12 vs (2)² − 4 = 0
+        assert_eq!(raw_match(4, 4), 12);
+        assert_eq!(raw_match(2, 4), 0);
+    }
+
+    #[test]
+    fn matches_are_capped_by_pool() {
+        assert_eq!(cap_matches(&[12, 4], 100), vec![12, 4]);
+        assert_eq!(cap_matches(&[12, 4], 8), vec![6, 2]);
+        let capped = cap_matches(&[1, 1, 1], 2);
+        assert!(capped.iter().sum::<u128>() <= 2);
+    }
+}
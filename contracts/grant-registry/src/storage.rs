@@ -1,8 +1,10 @@
 use near_sdk::borsh::{self, BorshSerialize};
-use near_sdk::store::{LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::store::{IterableMap, IterableSet, LookupMap};
 use near_sdk::AccountId;
+use std::borrow::Borrow;
 
-use crate::types::{Application, Program, Project};
+use crate::migration::LegacyIdSet;
+use crate::types::{Application, Escrow, Program, Project, QfTally, Review, TeamMember};
 
 /// Storage key prefixes — each must be unique to avoid collisions.
 #[derive(BorshSerialize)]
@@ -12,25 +14,108 @@ pub enum StorageKey {
     Projects,
     Applications,
     ApplicationsByProject,
-    ProjectApplications { project_hash: Vec<u8> },
+    /// Pre-v2 index set; its prefix is read back from stored state
+    #[allow(dead_code)]
+    ProjectApplications {
+        project_hash: Vec<u8>,
+    },
     ApplicationsByProgram,
-    ProgramApplications { program_hash: Vec<u8> },
+    /// Pre-v2 index set; its prefix is read back from stored state
+    #[allow(dead_code)]
+    ProgramApplications {
+        program_hash: Vec<u8>,
+    },
     ProjectsByOwner,
-    OwnerProjects { owner_hash: Vec<u8> },
+    /// Pre-v2 index set; its prefix is read back from stored state
+    #[allow(dead_code)]
+    OwnerProjects {
+        owner_hash: Vec<u8>,
+    },
+    ProgramReviewers,
+    ReviewerSet {
+        program_hash: Vec<u8>,
+    },
+    ProgramRubrics,
+    ApplicationReviews,
+    ReviewSet {
+        application_hash: Vec<u8>,
+    },
+    Escrows,
+    ProgramEscrow {
+        program_hash: Vec<u8>,
+    },
+    Payouts,
+    Commitments,
+    PayoutTokens,
+    FtTokens,
+    QfRounds,
+    QfTallies,
+    RoundTallies {
+        round_hash: Vec<u8>,
+    },
+    QfContributions,
+    QfVerifiedDonors,
+    TeamInvites,
+    ProjectInvites {
+        project_hash: Vec<u8>,
+    },
+    PendingProjectOwners,
+    /// Collections in the current layout; the unsuffixed variants above
+    /// hold the pre-v2 ones
+    ProgramsV2,
+    ProjectsV2,
+    ApplicationsV2,
+    ApplicationsByProjectV2,
+    ProjectApplicationsV2 {
+        project_hash: Vec<u8>,
+    },
+    ApplicationsByProgramV2,
+    ProgramApplicationsV2 {
+        program_hash: Vec<u8>,
+    },
+    ProjectsByOwnerV2,
+    OwnerProjectsV2 {
+        owner_hash: Vec<u8>,
+    },
+}
+
+/// Escrow balance of a program in one token, created empty if missing.
+pub fn escrow_mut<'a>(
+    escrows: &'a mut LookupMap<String, IterableMap<Option<AccountId>, Escrow>>,
+    program_id: &str,
+    token_id: &Option<AccountId>,
+) -> &'a mut Escrow {
+    if escrows.get(program_id).is_none() {
+        let prefix = StorageKey::ProgramEscrow {
+            program_hash: near_sdk::env::sha256(program_id.as_bytes()),
+        };
+        let new_map = IterableMap::new(borsh::to_vec(&prefix).unwrap());
+        escrows.insert(program_id.to_string(), new_map);
+    }
+    let balances = escrows.get_mut(program_id).unwrap();
+    if !balances.contains_key(token_id) {
+        balances.insert(token_id.clone(), Escrow::default());
+    }
+    balances.get_mut(token_id).unwrap()
+}
+
+/// Key of the payout for an application, or for one of its milestones.
+pub fn payout_key(application_id: &str, milestone_index: Option<u32>) -> String {
+    match milestone_index {
+        Some(index) => format!("{application_id}#{index}"),
+        None => application_id.to_string(),
+    }
 }
 
 /// Store a program in the registry.
-pub fn store_program(
-    programs: &mut UnorderedMap<String, Program>,
-    program: Program,
-) {
+pub fn store_program(programs: &mut IterableMap<String, Program>, program: Program) {
     programs.insert(program.id.clone(), program);
 }
 
 /// Store a project and update the owner index.
 pub fn store_project(
     projects: &mut LookupMap<String, Project>,
-    projects_by_owner: &mut LookupMap<AccountId, UnorderedSet<String>>,
+    projects_by_owner: &mut LookupMap<AccountId, IterableSet<String>>,
     project: Project,
 ) {
     let project_id = project.id.clone();
@@ -38,10 +123,10 @@ pub fn store_project(
 
     // Populate owner index before main insert for clarity of intent
     if projects_by_owner.get(&owner).is_none() {
-        let prefix = StorageKey::OwnerProjects {
+        let prefix = StorageKey::OwnerProjectsV2 {
             owner_hash: near_sdk::env::sha256(owner.as_bytes()),
         };
-        let new_set = UnorderedSet::new(borsh::to_vec(&prefix).unwrap());
+        let new_set = IterableSet::new(borsh::to_vec(&prefix).unwrap());
         projects_by_owner.insert(owner.clone(), new_set);
     }
     let owner_set = projects_by_owner.get_mut(&owner).unwrap();
@@ -50,30 +135,55 @@ pub fn store_project(
     projects.insert(project_id, project);
 }
 
-/// Get all projects owned by an account.
-pub fn get_projects_by_owner(
-    projects: &LookupMap<String, Project>,
-    projects_by_owner: &LookupMap<AccountId, UnorderedSet<String>>,
-    owner: &AccountId,
-) -> Vec<Project> {
-    let Some(owner_set) = projects_by_owner.get(owner) else {
-        return vec![];
-    };
-
-    let mut result = Vec::new();
-    for project_id in owner_set.iter() {
-        if let Some(project) = projects.get(project_id) {
-            result.push(project.clone());
-        }
+/// Move a project between owners in the owner index.
+pub fn move_project_owner(
+    projects_by_owner: &mut LookupMap<AccountId, IterableSet<String>>,
+    legacy_projects_by_owner: &mut LookupMap<AccountId, LegacyIdSet>,
+    project_id: &str,
+    old_owner: &AccountId,
+    new_owner: &AccountId,
+) {
+    if let Some(old_set) = projects_by_owner.get_mut(old_owner) {
+        old_set.remove(project_id);
+    }
+    if let Some(old_set) = legacy_projects_by_owner.get_mut(old_owner) {
+        old_set.remove(project_id);
+    }
+    if projects_by_owner.get(new_owner).is_none() {
+        let prefix = StorageKey::OwnerProjectsV2 {
+            owner_hash: near_sdk::env::sha256(new_owner.as_bytes()),
+        };
+        let new_set = IterableSet::new(borsh::to_vec(&prefix).unwrap());
+        projects_by_owner.insert(new_owner.clone(), new_set);
     }
-    result
+    projects_by_owner
+        .get_mut(new_owner)
+        .unwrap()
+        .insert(project_id.to_string());
+}
+
+/// Record a team invite for a project.
+pub fn store_team_invite(
+    team_invites: &mut LookupMap<String, IterableMap<AccountId, TeamMember>>,
+    project_id: &str,
+    member: TeamMember,
+) {
+    if team_invites.get(project_id).is_none() {
+        let prefix = StorageKey::ProjectInvites {
+            project_hash: near_sdk::env::sha256(project_id.as_bytes()),
+        };
+        let new_map = IterableMap::new(borsh::to_vec(&prefix).unwrap());
+        team_invites.insert(project_id.to_string(), new_map);
+    }
+    let invites = team_invites.get_mut(project_id).unwrap();
+    invites.insert(member.account_id.clone(), member);
 }
 
 /// Store an application and update indexes.
 pub fn store_application(
     applications: &mut LookupMap<String, Application>,
-    apps_by_project: &mut LookupMap<String, UnorderedSet<String>>,
-    apps_by_program: &mut LookupMap<String, UnorderedSet<String>>,
+    apps_by_project: &mut LookupMap<String, IterableSet<String>>,
+    apps_by_program: &mut LookupMap<String, IterableSet<String>>,
     application: Application,
 ) {
     let app_id = application.id.clone();
@@ -84,10 +194,10 @@ pub fn store_application(
 
     // Update project → applications index
     if apps_by_project.get(&project_id).is_none() {
-        let prefix = StorageKey::ProjectApplications {
+        let prefix = StorageKey::ProjectApplicationsV2 {
             project_hash: near_sdk::env::sha256(project_id.as_bytes()),
         };
-        let new_set = UnorderedSet::new(borsh::to_vec(&prefix).unwrap());
+        let new_set = IterableSet::new(borsh::to_vec(&prefix).unwrap());
         apps_by_project.insert(project_id.clone(), new_set);
     }
     let project_set = apps_by_project.get_mut(&project_id).unwrap();
@@ -95,52 +205,96 @@ pub fn store_application(
 
     // Update program → applications index
     if apps_by_program.get(&program_id).is_none() {
-        let prefix = StorageKey::ProgramApplications {
+        let prefix = StorageKey::ProgramApplicationsV2 {
             program_hash: near_sdk::env::sha256(program_id.as_bytes()),
         };
-        let new_set = UnorderedSet::new(borsh::to_vec(&prefix).unwrap());
+        let new_set = IterableSet::new(borsh::to_vec(&prefix).unwrap());
         apps_by_program.insert(program_id.clone(), new_set);
     }
     let program_set = apps_by_program.get_mut(&program_id).unwrap();
     program_set.insert(app_id);
 }
 
-/// Get all applications for a project.
-pub fn get_applications_by_project(
-    applications: &LookupMap<String, Application>,
-    apps_by_project: &LookupMap<String, UnorderedSet<String>>,
-    project_id: &str,
-) -> Vec<Application> {
-    let Some(app_set) = apps_by_project.get(project_id) else {
-        return vec![];
-    };
-
-    let mut result = Vec::new();
-    for app_id in app_set.iter() {
-        if let Some(app) = applications.get(app_id) {
-            result.push(app.clone());
-        }
+/// Add a delegated reviewer for a program.
+pub fn add_program_reviewer(
+    program_reviewers: &mut LookupMap<String, IterableSet<AccountId>>,
+    program_id: &str,
+    reviewer: AccountId,
+) {
+    if program_reviewers.get(program_id).is_none() {
+        let prefix = StorageKey::ReviewerSet {
+            program_hash: near_sdk::env::sha256(program_id.as_bytes()),
+        };
+        let new_set = IterableSet::new(borsh::to_vec(&prefix).unwrap());
+        program_reviewers.insert(program_id.to_string(), new_set);
     }
-    result
+    program_reviewers
+        .get_mut(program_id)
+        .unwrap()
+        .insert(reviewer);
 }
 
-/// Get all applications for a program.
-pub fn get_applications_by_program(
-    applications: &LookupMap<String, Application>,
-    apps_by_program: &LookupMap<String, UnorderedSet<String>>,
+/// Remove a delegated reviewer. Returns whether it was present.
+pub fn remove_program_reviewer(
+    program_reviewers: &mut LookupMap<String, IterableSet<AccountId>>,
     program_id: &str,
-) -> Vec<Application> {
-    let Some(app_set) = apps_by_program.get(program_id) else {
-        return vec![];
-    };
-
-    let mut result = Vec::new();
-    for app_id in app_set.iter() {
-        if let Some(app) = applications.get(app_id) {
-            result.push(app.clone());
-        }
+    reviewer: &AccountId,
+) -> bool {
+    program_reviewers
+        .get_mut(program_id)
+        .is_some_and(|set| set.remove(reviewer))
+}
+
+/// Store a review, replacing the reviewer's earlier one if any.
+pub fn store_review(
+    reviews: &mut LookupMap<String, IterableMap<AccountId, Review>>,
+    application_id: &str,
+    review: Review,
+) {
+    if reviews.get(application_id).is_none() {
+        let prefix = StorageKey::ReviewSet {
+            application_hash: near_sdk::env::sha256(application_id.as_bytes()),
+        };
+        let new_map = IterableMap::new(borsh::to_vec(&prefix).unwrap());
+        reviews.insert(application_id.to_string(), new_map);
     }
-    result
+    let app_reviews = reviews.get_mut(application_id).unwrap();
+    app_reviews.insert(review.reviewer.clone(), review);
+}
+
+/// Tally of an application in a round, created empty if missing.
+pub fn qf_tally_mut<'a>(
+    tallies: &'a mut LookupMap<String, IterableMap<String, QfTally>>,
+    round_id: &str,
+    application_id: &str,
+) -> &'a mut QfTally {
+    if tallies.get(round_id).is_none() {
+        let prefix = StorageKey::RoundTallies {
+            round_hash: near_sdk::env::sha256(round_id.as_bytes()),
+        };
+        let new_map = IterableMap::new(borsh::to_vec(&prefix).unwrap());
+        tallies.insert(round_id.to_string(), new_map);
+    }
+    let round = tallies.get_mut(round_id).unwrap();
+    if !round.contains_key(application_id) {
+        round.insert(application_id.to_string(), QfTally::default());
+    }
+    round.get_mut(application_id).unwrap()
+}
+
+/// IDs listed under `key` in an index, including its pre-v2 set.
+pub fn index_ids<K, Q>(
+    index: &LookupMap<K, IterableSet<String>>,
+    legacy: &LookupMap<K, LegacyIdSet>,
+    key: &Q,
+) -> Vec<String>
+where
+    K: BorshSerialize + Ord + Borrow<Q>,
+    Q: BorshSerialize + ToOwned<Owned = K> + ?Sized,
+{
+    let current = index.get(key).into_iter().flat_map(|set| set.iter());
+    let legacy = legacy.get(key).into_iter().flat_map(|set| set.iter());
+    current.chain(legacy).cloned().collect()
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Deserializer, Serialize};
 use near_sdk::AccountId;
 
 /// Blockchain ecosystem.
@@ -43,6 +44,22 @@ pub enum ProgramStatus {
     Paused,
 }
 
+impl ProgramStatus {
+    /// Closing a program is final; every other change is allowed.
+    pub fn can_transition_to(&self, next: &ProgramStatus) -> bool {
+        self != next && *self != ProgramStatus::Closed
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Upcoming => "upcoming",
+            Self::Closed => "closed",
+            Self::Paused => "paused",
+        }
+    }
+}
+
 /// Application lifecycle status.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde", rename_all = "kebab-case")]
@@ -54,18 +71,96 @@ pub enum ApplicationStatus {
     Rejected,
     Funded,
     Completed,
+    Withdrawn,
 }
 
-/// Team member on a project.
+impl ApplicationStatus {
+    /// Statuses reachable from this one. Terminal statuses have none.
+    pub fn allowed_transitions(&self) -> &'static [ApplicationStatus] {
+        use ApplicationStatus::*;
+        match self {
+            Draft => &[Submitted, Withdrawn],
+            Submitted => &[UnderReview, Approved, Rejected, Withdrawn],
+            UnderReview => &[Approved, Rejected, Withdrawn],
+            Approved => &[Funded, Rejected, Withdrawn],
+            Funded => &[Completed],
+            Rejected | Completed | Withdrawn => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &ApplicationStatus) -> bool {
+        self.allowed_transitions().contains(next)
+    }
+
+    /// Whether the applicant (rather than the program's registrar or
+    /// reviewers) makes this transition: submitting or withdrawing.
+    pub fn is_applicant_transition(next: &ApplicationStatus) -> bool {
+        matches!(
+            next,
+            ApplicationStatus::Submitted | ApplicationStatus::Withdrawn
+        )
+    }
+
+    /// Whether the application counts towards its project's success rate.
+    pub fn is_successful(&self) -> bool {
+        matches!(self, Self::Approved | Self::Funded | Self::Completed)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Submitted => "submitted",
+            Self::UnderReview => "under-review",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Funded => "funded",
+            Self::Completed => "completed",
+            Self::Withdrawn => "withdrawn",
+        }
+    }
+}
+
+/// Team member on a project. Listed on the project once the account
+/// accepts its invite.
 #[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TeamMember {
-    pub account_id: String,
+    pub account_id: AccountId,
     pub name: String,
     pub role: String,
     pub profile_url: Option<String>,
 }
 
+/// Mutable team member fields for `update_team_member`. Omitted fields are
+/// left unchanged; `null` clears the profile URL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TeamMemberUpdate {
+    pub name: Option<String>,
+    pub role: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub profile_url: Option<Option<String>>,
+}
+
+impl TeamMemberUpdate {
+    /// Apply the update to a listed member or a pending invite.
+    pub fn apply(self, member: &mut TeamMember) {
+        if let Some(name) = self.name {
+            member.name = name;
+        }
+        if let Some(role) = self.role {
+            member.role = role;
+        }
+        if let Some(profile_url) = self.profile_url {
+            member.profile_url = profile_url;
+        }
+    }
+}
+
 /// A grant program registered in the registry.
 #[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -76,10 +171,12 @@ pub struct Program {
     pub organization: String,
     pub chains: Vec<Chain>,
     pub categories: Vec<Category>,
-    pub funding_pool: String,
-    pub min_amount: Option<String>,
-    pub max_amount: Option<String>,
-    pub deadline: Option<String>,
+    pub funding_pool: U128,
+    pub min_amount: Option<U128>,
+    pub max_amount: Option<U128>,
+    /// Last moment applications are accepted, nanoseconds since the Unix
+    /// epoch.
+    pub deadline: Option<u64>,
     pub website: String,
     pub application_url: Option<String>,
     pub status: ProgramStatus,
@@ -89,6 +186,86 @@ pub struct Program {
     pub funded_count: u64,
 }
 
+/// Mutable program fields for `update_program`. Omitted fields are left
+/// unchanged; `null` clears an optional field.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProgramUpdate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub organization: Option<String>,
+    pub chains: Option<Vec<Chain>>,
+    pub categories: Option<Vec<Category>>,
+    pub funding_pool: Option<U128>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_amount: Option<Option<U128>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_amount: Option<Option<U128>>,
+    /// ISO-8601, as for `register_program`.
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deadline: Option<Option<String>>,
+    pub website: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub application_url: Option<Option<String>>,
+}
+
+/// Read a present field, `null` included, as `Some`; serde's `default`
+/// leaves an absent one `None`.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    <Option<T> as Deserialize>::deserialize(deserializer).map(Some)
+}
+
+impl ProgramUpdate {
+    /// Apply the update, returning the names of the fields it set or
+    /// cleared. The caller checks that `deadline` parses.
+    pub fn apply(self, program: &mut Program) -> Vec<String> {
+        let mut changed = Vec::new();
+        macro_rules! set {
+            ($field:ident) => {
+                if let Some(value) = self.$field {
+                    program.$field = value;
+                    changed.push(stringify!($field).to_string());
+                }
+            };
+        }
+        set!(name);
+        set!(description);
+        set!(organization);
+        set!(chains);
+        set!(categories);
+        set!(funding_pool);
+        set!(min_amount);
+        set!(max_amount);
+        if let Some(value) = self.deadline {
+            program.deadline = value.as_deref().and_then(parse_iso8601);
+            changed.push("deadline".to_string());
+        }
+        set!(website);
+        set!(application_url);
+        changed
+    }
+}
+
 /// A project registered in the registry.
 #[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -100,8 +277,49 @@ pub struct Project {
     pub team_members: Vec<TeamMember>,
     pub registered_by: AccountId,
     pub registered_at: u64,
-    pub total_funded: String,
+    pub total_funded: U128,
+    pub application_count: u64,
+    /// Approved, funded or completed applications.
+    pub successful_count: u64,
+    /// Successful plus rejected applications.
+    pub decided_count: u64,
+    /// `successful_count` as a percentage of `decided_count`.
+    pub success_rate: u32,
+}
+
+impl Project {
+    /// Replace one application's contribution to the aggregates.
+    pub fn record_outcome(&mut self, before: ApplicationOutcome, after: ApplicationOutcome) {
+        self.total_funded = U128(self.total_funded.0 - before.funded + after.funded);
+        self.successful_count = self.successful_count - before.successful + after.successful;
+        self.decided_count = self.decided_count - before.decided + after.decided;
+        self.success_rate = success_rate(self.successful_count, self.decided_count);
+    }
+}
+
+/// Percentage of decided applications that succeeded; 0 when none are
+/// decided.
+pub fn success_rate(successful: u64, decided: u64) -> u32 {
+    (successful * 100).checked_div(decided).unwrap_or(0) as u32
+}
+
+/// An application's contribution to its project's aggregates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApplicationOutcome {
+    pub funded: u128,
+    pub successful: u64,
+    pub decided: u64,
+}
+
+/// Project aggregates rebuilt from its applications.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProjectAggregates {
+    pub project_id: String,
+    pub total_funded: U128,
     pub application_count: u64,
+    pub successful_count: u64,
+    pub decided_count: u64,
     pub success_rate: u32,
 }
 
@@ -114,11 +332,398 @@ pub struct Application {
     pub project_id: String,
     pub applicant_account_id: AccountId,
     pub title: String,
-    pub requested_amount: String,
+    pub requested_amount: U128,
     pub status: ApplicationStatus,
     pub submitted_at: Option<u64>,
-    pub funded_amount: Option<String>,
+    pub funded_amount: Option<U128>,
     pub completed_at: Option<u64>,
+    /// Payout tranches. When present, `Funded` and `Completed` follow
+    /// milestone approvals instead of being set directly.
+    pub milestones: Vec<Milestone>,
+}
+
+impl Application {
+    /// What this application currently adds to its project's aggregates.
+    pub fn outcome(&self) -> ApplicationOutcome {
+        let successful = self.status.is_successful();
+        ApplicationOutcome {
+            funded: self.funded_amount.map_or(0, |a| a.0),
+            successful: successful as u64,
+            decided: (successful || self.status == ApplicationStatus::Rejected) as u64,
+        }
+    }
+
+    /// What this application is owed from its program's escrow but not yet
+    /// sent, failed payouts aside: its request once approved, or its
+    /// unapproved milestones once approved or funded.
+    pub fn committed(&self) -> u128 {
+        if self.milestones.is_empty() {
+            return match self.status {
+                ApplicationStatus::Approved => self.requested_amount.0,
+                _ => 0,
+            };
+        }
+        if !matches!(
+            self.status,
+            ApplicationStatus::Approved | ApplicationStatus::Funded
+        ) {
+            return 0;
+        }
+        self.milestones
+            .iter()
+            .filter(|m| m.status != MilestoneStatus::Approved)
+            .map(|m| m.amount.0)
+            .sum()
+    }
+}
+
+/// Review state of a milestone's delivery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "kebab-case")]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Approved,
+    Rejected,
+}
+
+/// A payout tranche of an application.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Milestone {
+    pub title: String,
+    pub amount: U128,
+    /// Due date, nanoseconds since the Unix epoch.
+    pub due_at: u64,
+    /// Hex sha256 of the agreed deliverable specification.
+    pub deliverable_hash: String,
+    pub status: MilestoneStatus,
+    /// Hex sha256 of the delivery evidence, once submitted.
+    pub evidence_hash: Option<String>,
+    pub submitted_at: Option<u64>,
+    pub reviewed_at: Option<u64>,
+}
+
+/// Milestone definition supplied by the applicant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MilestoneInput {
+    pub title: String,
+    pub amount: U128,
+    pub due_at: u64,
+    pub deliverable_hash: String,
+}
+
+/// A milestone awaiting approval, located by application and index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingMilestone {
+    pub application_id: String,
+    pub index: u32,
+    pub milestone: Milestone,
+}
+
+/// A program's escrow balance in one token, in its smallest unit.
+#[derive(Debug, Clone, Default, BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Escrow {
+    pub deposited: u128,
+    pub paid_out: u128,
+    /// Sent but not yet confirmed by the transfer callback.
+    pub in_flight: u128,
+    pub reclaimed: u128,
+    /// Payouts whose transfer failed, still owed until retried.
+    pub failed: u128,
+}
+
+impl Escrow {
+    /// Balance not yet paid, in flight or reclaimed.
+    pub fn balance(&self) -> u128 {
+        self.deposited - self.paid_out - self.in_flight - self.reclaimed
+    }
+}
+
+/// Escrow balances of a program. `committed` is owed to approved
+/// applications but not yet paid; `available` is what remains after it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowView {
+    pub program_id: String,
+    /// `None` for NEAR.
+    pub token_id: Option<AccountId>,
+    pub deposited: U128,
+    pub paid_out: U128,
+    pub in_flight: U128,
+    pub reclaimed: U128,
+    pub committed: U128,
+    pub available: U128,
+}
+
+/// Delivery state of an escrow payout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "kebab-case")]
+pub enum PayoutStatus {
+    InFlight,
+    Paid,
+    Failed,
+}
+
+/// An escrow payout for an application or one of its milestones.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub program_id: String,
+    pub application_id: String,
+    pub milestone_index: Option<u32>,
+    /// `None` for NEAR.
+    pub token_id: Option<AccountId>,
+    pub recipient: AccountId,
+    pub amount: U128,
+    pub status: PayoutStatus,
+    pub updated_at: u64,
+}
+
+/// Credential a donor must hold in zk-verifier to count in a QF round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SybilFilter {
+    pub verifier: AccountId,
+    /// zk-verifier circuit type, e.g. `verified-builder`.
+    pub circuit_type: String,
+}
+
+/// A quadratic funding round of a program. Donations in NEAR go straight
+/// to applicants; the matching pool is split by CLR at settlement.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QfRound {
+    pub id: String,
+    pub program_id: String,
+    /// Donation window, nanoseconds since the Unix epoch, end exclusive.
+    pub starts_at: u64,
+    pub ends_at: u64,
+    /// Amount the program pledges to split by CLR. It is not escrowed or
+    /// checked against the program's funds: the contract records the
+    /// matches and paying them is up to the program.
+    pub matching_pool: U128,
+    pub sybil_filter: Option<SybilFilter>,
+    pub total_donations: U128,
+    /// Donations whose transfer to the applicant has not resolved yet. The
+    /// round cannot settle while any are pending.
+    pub pending_forwards: u32,
+    pub total_matched: U128,
+    pub settled_at: Option<u64>,
+}
+
+/// Running donation totals of one application in a round.
+#[derive(Debug, Clone, Default, BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct QfTally {
+    pub contributions: u128,
+    /// Sum over donors of the square root of their total contribution.
+    pub sqrt_sum: u128,
+    pub donors: u32,
+    /// Set at settlement.
+    pub matched: u128,
+}
+
+/// An application's donations and (projected or settled) match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QfMatch {
+    pub application_id: String,
+    pub contributions: U128,
+    pub donors: u32,
+    pub match_amount: U128,
+}
+
+/// Whether `value` is a hex-encoded sha256 digest.
+pub fn is_sha256_hex(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Parse an ISO-8601 date (`2025-12-31`) or date-time with a UTC offset
+/// (`2025-12-31T23:59:59Z`, `2025-12-31T12:00:00.5+02:00`) into nanoseconds
+/// since the Unix epoch. A bare date means the end of that day, UTC.
+pub fn parse_iso8601(value: &str) -> Option<u64> {
+    fn digits(s: &str, len: usize) -> Option<i64> {
+        if s.len() != len || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        s.parse().ok()
+    }
+
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let mut parts = date.split('-');
+    let year = digits(parts.next()?, 4)?;
+    let month = digits(parts.next()?, 2)?;
+    let day = digits(parts.next()?, 2)?;
+    if parts.next().is_some() || !(1..=12).contains(&month) {
+        return None;
+    }
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1..=month_days).contains(&day) {
+        return None;
+    }
+
+    // Days since 1970-01-01 (proleptic Gregorian), counting years from March.
+    let y = if month <= 2 { year - 1 } else { year };
+    let (era, yoe) = (y.div_euclid(400), y.rem_euclid(400));
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let days = era * 146_097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719_468;
+
+    let (seconds, nanos) = match time {
+        None => (86_399, 999_999_999),
+        Some(time) => {
+            let (clock, offset) = if let Some(clock) = time.strip_suffix('Z') {
+                (clock, 0)
+            } else {
+                let at = time.rfind(['+', '-'])?;
+                let (clock, zone) = time.split_at(at);
+                let (hours, minutes) = zone[1..].split_once(':')?;
+                let (hours, minutes) = (digits(hours, 2)?, digits(minutes, 2)?);
+                if hours > 23 || minutes > 59 {
+                    return None;
+                }
+                let offset = hours * 3600 + minutes * 60;
+                (
+                    clock,
+                    if zone.starts_with('-') {
+                        -offset
+                    } else {
+                        offset
+                    },
+                )
+            };
+            let (clock, fraction) = match clock.split_once('.') {
+                Some((clock, fraction)) => (clock, Some(fraction)),
+                None => (clock, None),
+            };
+            let mut fields = clock.split(':');
+            let hour = digits(fields.next()?, 2)?;
+            let minute = digits(fields.next()?, 2)?;
+            let second = fields.next().map_or(Some(0), |s| digits(s, 2))?;
+            if fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+                return None;
+            }
+            let nanos = match fraction {
+                Some(f) if (1..=9).contains(&f.len()) => {
+                    digits(f, f.len())? * 10i64.pow(9 - f.len() as u32)
+                }
+                Some(_) => return None,
+                None => 0,
+            };
+            (hour * 3600 + minute * 60 + second - offset, nanos)
+        }
+    };
+
+    let total = days * 86_400 + seconds;
+    u64::try_from(total)
+        .ok()?
+        .checked_mul(1_000_000_000)?
+        .checked_add(nanos as u64)
+}
+
+/// One scored criterion of a program's review rubric.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RubricCriterion {
+    pub name: String,
+    pub max_score: u32,
+    pub weight: u32,
+}
+
+/// A program's review rubric and the thresholds that move applications
+/// automatically once `min_reviews` reviews are in. Thresholds are in
+/// basis points of the weighted maximum score.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Rubric {
+    pub criteria: Vec<RubricCriterion>,
+    pub min_reviews: u32,
+    pub approve_threshold_bps: u32,
+    pub reject_threshold_bps: u32,
+}
+
+impl Rubric {
+    /// Check the rubric is usable, returning a description of the problem.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.criteria.is_empty() {
+            return Err("rubric needs at least one criterion".into());
+        }
+        if self
+            .criteria
+            .iter()
+            .any(|c| c.max_score == 0 || c.weight == 0)
+        {
+            return Err("criteria need a non-zero max_score and weight".into());
+        }
+        if self.min_reviews == 0 {
+            return Err("min_reviews must be at least 1".into());
+        }
+        if self.approve_threshold_bps > 10_000
+            || self.reject_threshold_bps >= self.approve_threshold_bps
+        {
+            return Err("thresholds must satisfy reject < approve <= 10000".into());
+        }
+        Ok(())
+    }
+
+    /// Weighted score of one review in basis points, or an error if the
+    /// scores don't fit the rubric.
+    pub fn score_bps(&self, scores: &[u32]) -> Result<u32, String> {
+        if scores.len() != self.criteria.len() {
+            return Err(format!(
+                "expected {} scores, got {}",
+                self.criteria.len(),
+                scores.len()
+            ));
+        }
+        let mut weighted: u64 = 0;
+        let mut total_weight: u64 = 0;
+        for (criterion, &score) in self.criteria.iter().zip(scores) {
+            if score > criterion.max_score {
+                return Err(format!(
+                    "score for {} exceeds {}",
+                    criterion.name, criterion.max_score
+                ));
+            }
+            weighted += u64::from(score) * u64::from(criterion.weight) * 10_000
+                / u64::from(criterion.max_score);
+            total_weight += u64::from(criterion.weight);
+        }
+        Ok((weighted / total_weight) as u32)
+    }
+}
+
+/// A reviewer's scored review of an application.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Review {
+    pub reviewer: AccountId,
+    pub scores: Vec<u32>,
+    /// Hex sha256 of the off-chain review comment.
+    pub comment_hash: String,
+    pub score_bps: u32,
+    pub submitted_at: u64,
+}
+
+/// Aggregate of an application's reviews.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApplicationScore {
+    pub application_id: String,
+    pub review_count: u32,
+    pub average_score_bps: u32,
 }
 
 /// Ecosystem-wide statistics.
@@ -127,7 +732,7 @@ pub struct Application {
 pub struct EcosystemStats {
     pub total_programs: u64,
     pub total_projects: u64,
-    pub total_funded: String,
+    pub total_funded: U128,
     pub total_applications: u64,
     pub active_programs: u64,
 }
@@ -136,6 +741,19 @@ pub struct EcosystemStats {
 mod tests {
     use super::*;
 
+    #[test]
+    fn program_update_distinguishes_null_from_absent() {
+        let update: ProgramUpdate =
+            serde_json::from_str(r#"{"max_amount": null, "deadline": "2026-06-30"}"#).unwrap();
+        assert_eq!(update.min_amount, None);
+        assert_eq!(update.max_amount, Some(None));
+        assert_eq!(update.deadline, Some(Some("2026-06-30".to_string())));
+
+        let json = serde_json::to_string(&update).unwrap();
+        let back: ProgramUpdate = serde_json::from_str(&json).unwrap();
+        assert_eq!((back.min_amount, back.max_amount), (None, Some(None)));
+    }
+
     #[test]
     fn chain_serde_roundtrip() {
         let chain = Chain::Near;
@@ -163,6 +781,96 @@ mod tests {
         assert_eq!(parsed, status);
     }
 
+    #[test]
+    fn program_status_transitions() {
+        assert!(ProgramStatus::Active.can_transition_to(&ProgramStatus::Paused));
+        assert!(ProgramStatus::Upcoming.can_transition_to(&ProgramStatus::Closed));
+        assert!(!ProgramStatus::Active.can_transition_to(&ProgramStatus::Active));
+        assert!(!ProgramStatus::Closed.can_transition_to(&ProgramStatus::Active));
+    }
+
+    fn test_rubric() -> Rubric {
+        Rubric {
+            criteria: vec![
+                RubricCriterion {
+                    name: "impact".into(),
+                    max_score: 10,
+                    weight: 3,
+                },
+                RubricCriterion {
+                    name: "feasibility".into(),
+                    max_score: 5,
+                    weight: 1,
+                },
+            ],
+            min_reviews: 2,
+            approve_threshold_bps: 7_000,
+            reject_threshold_bps: 4_000,
+        }
+    }
+
+    #[test]
+    fn rubric_score_is_weighted() {
+        let rubric = test_rubric();
+        assert!(rubric.validate().is_ok());
+        assert_eq!(rubric.score_bps(&[10, 5]).unwrap(), 10_000);
+        assert_eq!(rubric.score_bps(&[0, 0]).unwrap(), 0);
+        // (3 * 5000 + 1 * 10000) / 4
+        assert_eq!(rubric.score_bps(&[5, 5]).unwrap(), 6_250);
+        assert!(rubric.score_bps(&[11, 0]).is_err());
+        assert!(rubric.score_bps(&[1]).is_err());
+    }
+
+    #[test]
+    fn rubric_validation() {
+        let mut rubric = test_rubric();
+        rubric.reject_threshold_bps = 7_000;
+        assert!(rubric.validate().is_err());
+
+        let mut rubric = test_rubric();
+        rubric.criteria[0].weight = 0;
+        assert!(rubric.validate().is_err());
+    }
+
+    #[test]
+    fn escrow_balance() {
+        let escrow = Escrow {
+            deposited: 100,
+            paid_out: 30,
+            in_flight: 20,
+            reclaimed: 0,
+            // Failed payouts are still held
+            failed: 10,
+        };
+        assert_eq!(escrow.balance(), 50);
+    }
+
+    #[test]
+    fn application_status_transitions() {
+        use ApplicationStatus::*;
+        assert!(Submitted.can_transition_to(&UnderReview));
+        assert!(Approved.can_transition_to(&Funded));
+        assert!(Funded.can_transition_to(&Completed));
+        assert!(!Completed.can_transition_to(&Draft));
+        assert!(!Submitted.can_transition_to(&Funded));
+        assert!(!Funded.can_transition_to(&Withdrawn));
+        for status in [
+            Draft,
+            Submitted,
+            UnderReview,
+            Approved,
+            Rejected,
+            Funded,
+            Completed,
+            Withdrawn,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{}\"", status.as_str())
+            );
+        }
+    }
+
     #[test]
     fn application_status_serde_roundtrip() {
         let status = ApplicationStatus::UnderReview;
@@ -171,4 +879,44 @@ mod tests {
         let parsed: ApplicationStatus = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, status);
     }
+
+    #[test]
+    fn parses_iso8601_deadlines() {
+        const S: u64 = 1_000_000_000;
+        assert_eq!(parse_iso8601("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_iso8601("2025-12-31T23:59:59Z"),
+            Some(1_767_225_599 * S)
+        );
+        assert_eq!(
+            parse_iso8601("2025-12-31"),
+            Some(1_767_225_599 * S + 999_999_999)
+        );
+        assert_eq!(
+            parse_iso8601("2024-02-29T12:00:00+02:00"),
+            Some(1_709_200_800 * S)
+        );
+        assert_eq!(
+            parse_iso8601("2024-02-29T12:00:00.25-01:30"),
+            Some(1_709_213_400 * S + 250_000_000)
+        );
+        assert_eq!(parse_iso8601("2024-03-01T08:30Z"), Some(1_709_281_800 * S));
+    }
+
+    #[test]
+    fn rejects_malformed_iso8601() {
+        for bad in [
+            "",
+            "Dec 31 2025",
+            "2025-13-01",
+            "2023-02-29",
+            "2025-12-31T24:00:00Z",
+            "2025-12-31T23:59:59",
+            "2025-12-31T23:59:59.Z",
+            "1969-12-31T00:00:00Z",
+            "25-12-31",
+        ] {
+            assert_eq!(parse_iso8601(bad), None, "{bad}");
+        }
+    }
 }
@@ -0,0 +1,686 @@
+//! Generates real Groth16 proof fixtures for E2E testing.
+//!
+//! Each circuit is written in Rust R1CS, set up and proved with a seeded RNG,
+//! and turned into pre-formatted JSON args for the `near call`s in
+//! `scripts/e2e-test-contracts.sh`. Output is deterministic for a given seed.
+//!
+//! Circuits:
+//!   square       x * x == y
+//!   multi-input  sum, product and sum of squares of two private values
+//!   range        committed private value is at least a public minimum
+//!   merkle       private leaf is a member of a depth-4 tree with public root
+//!
+//! Output files per circuit `<name>` (written to ../../scripts/fixtures/):
+//!   zk-<name>-circuit.json              — raw VK, proof and public signals
+//!   zk-<name>-set-vk-args.json          — set_verification_key
+//!   zk-<name>-verify-args.json          — verify_proof, valid, stores a credential
+//!   zk-<name>-verify-invalid-args.json  — verify_proof, last signal off by one
+//!   zk-<name>-view-args.json            — verify_proof_view, valid
+//!   zk-<name>-batch-<i>-args.json       — verify_proof for independent witnesses
+//!   zk-<name>-renew-args.json           — fresh proof of the same statement
+//!   zk-<name>-revoke-args.json          — revoke_credential, `__CREDENTIAL_ID__` placeholder
+//! plus `manifest.tsv` listing the generated circuits for the E2E script.
+//!
+//! The contract has no batch or compressed-proof entry point, so batch
+//! fixtures are independent `verify_proof` calls.
+//!
+//! Usage:
+//!   cd contracts/zk-verifier
+//!   cargo run --features fixtures --bin generate-fixtures -- \
+//!     [--circuit square|multi-input|range|merkle|all] [--seed N] [--out DIR]
+
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ff::{One, Zero};
+use ark_groth16::{Groth16 as ArkGroth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, LinearCombination,
+    SynthesisError, Variable,
+};
+use ark_snark::SNARK;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BATCH_SIZE: usize = 3;
+const MERKLE_DEPTH: usize = 4;
+const RANGE_BITS: usize = 32;
+const MIMC_ROUNDS: u64 = 8;
+
+// ---------------------------------------------------------------------------
+// R1CS helpers
+// ---------------------------------------------------------------------------
+
+/// A linear combination together with its assigned value.
+#[derive(Clone)]
+struct Wire {
+    lc: LinearCombination<Fr>,
+    val: Fr,
+}
+
+impl Wire {
+    fn constant(val: Fr) -> Self {
+        Self {
+            lc: LinearCombination::from((val, Variable::One)),
+            val,
+        }
+    }
+
+    fn input(cs: &ConstraintSystemRef<Fr>, val: Fr) -> Result<Self, SynthesisError> {
+        let var = cs.new_input_variable(|| Ok(val))?;
+        Ok(Self {
+            lc: LinearCombination::from((Fr::one(), var)),
+            val,
+        })
+    }
+
+    fn witness(cs: &ConstraintSystemRef<Fr>, val: Fr) -> Result<Self, SynthesisError> {
+        let var = cs.new_witness_variable(|| Ok(val))?;
+        Ok(Self {
+            lc: LinearCombination::from((Fr::one(), var)),
+            val,
+        })
+    }
+
+    fn add(&self, other: &Wire) -> Wire {
+        Wire {
+            lc: self.lc.clone() + &other.lc,
+            val: self.val + other.val,
+        }
+    }
+
+    fn sub(&self, other: &Wire) -> Wire {
+        Wire {
+            lc: self.lc.clone() - &other.lc,
+            val: self.val - other.val,
+        }
+    }
+
+    fn scale(&self, k: Fr) -> Wire {
+        Wire {
+            lc: self.lc.clone() * k,
+            val: self.val * k,
+        }
+    }
+
+    /// Allocate `self * other` as a new witness.
+    fn mul(&self, cs: &ConstraintSystemRef<Fr>, other: &Wire) -> Result<Wire, SynthesisError> {
+        let out = Wire::witness(cs, self.val * other.val)?;
+        cs.enforce_constraint(self.lc.clone(), other.lc.clone(), out.lc.clone())?;
+        Ok(out)
+    }
+
+    fn enforce_equal(
+        &self,
+        cs: &ConstraintSystemRef<Fr>,
+        other: &Wire,
+    ) -> Result<(), SynthesisError> {
+        cs.enforce_constraint(
+            self.lc.clone(),
+            LinearCombination::from((Fr::one(), Variable::One)),
+            other.lc.clone(),
+        )
+    }
+
+    fn enforce_boolean(&self, cs: &ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let minus_one = self.sub(&Wire::constant(Fr::one()));
+        cs.enforce_constraint(self.lc.clone(), minus_one.lc, LinearCombination::zero())
+    }
+}
+
+/// Public `valid` output fixed to 1, matching the project circuits' first signal.
+fn valid_output(cs: &ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+    Wire::input(cs, Fr::one())?.enforce_equal(cs, &Wire::constant(Fr::one()))
+}
+
+fn mimc_constant(round: u64) -> Fr {
+    Fr::from(round * 7 + 1)
+}
+
+/// Toy MiMC-style compression (x^7, few rounds). Fixture use only — not a
+/// secure hash.
+fn mimc_native(x: Fr, k: Fr) -> Fr {
+    let mut x = x;
+    for round in 0..MIMC_ROUNDS {
+        let t = x + k + mimc_constant(round);
+        let t2 = t * t;
+        let t4 = t2 * t2;
+        x = t4 * t2 * t;
+    }
+    x + k
+}
+
+fn mimc_gadget(cs: &ConstraintSystemRef<Fr>, x: &Wire, k: &Wire) -> Result<Wire, SynthesisError> {
+    let mut x = x.clone();
+    for round in 0..MIMC_ROUNDS {
+        let t = x.add(k).add(&Wire::constant(mimc_constant(round)));
+        let t2 = t.mul(cs, &t)?;
+        let t4 = t2.mul(cs, &t2)?;
+        let t6 = t4.mul(cs, &t2)?;
+        x = t6.mul(cs, &t)?;
+    }
+    Ok(x.add(k))
+}
+
+// ---------------------------------------------------------------------------
+// Circuits
+// ---------------------------------------------------------------------------
+
+/// x * x == y  (public: y)
+#[derive(Clone)]
+struct SquareCircuit {
+    x: Fr,
+}
+
+impl ConstraintSynthesizer<Fr> for SquareCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let x = Wire::witness(&cs, self.x)?;
+        let y = Wire::input(&cs, self.x * self.x)?;
+        x.mul(&cs, &x)?.enforce_equal(&cs, &y)
+    }
+}
+
+/// Private x, y  (public: valid, x + y, x * y, x^2 + y^2)
+#[derive(Clone)]
+struct MultiInputCircuit {
+    x: Fr,
+    y: Fr,
+}
+
+impl ConstraintSynthesizer<Fr> for MultiInputCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        valid_output(&cs)?;
+        let sum = Wire::input(&cs, self.x + self.y)?;
+        let product = Wire::input(&cs, self.x * self.y)?;
+        let squares = Wire::input(&cs, self.x * self.x + self.y * self.y)?;
+
+        let x = Wire::witness(&cs, self.x)?;
+        let y = Wire::witness(&cs, self.y)?;
+        x.add(&y).enforce_equal(&cs, &sum)?;
+        x.mul(&cs, &y)?.enforce_equal(&cs, &product)?;
+        x.mul(&cs, &x)?
+            .add(&y.mul(&cs, &y)?)
+            .enforce_equal(&cs, &squares)
+    }
+}
+
+/// Committed value is at least `min`: value - min fits in RANGE_BITS bits.
+/// (public: valid, mimc(value, salt), min)
+#[derive(Clone)]
+struct RangeCircuit {
+    value: u64,
+    salt: Fr,
+    min: u64,
+}
+
+impl ConstraintSynthesizer<Fr> for RangeCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        valid_output(&cs)?;
+        let value_fr = Fr::from(self.value);
+        let commitment = Wire::input(&cs, mimc_native(value_fr, self.salt))?;
+        let min = Wire::input(&cs, Fr::from(self.min))?;
+
+        let value = Wire::witness(&cs, value_fr)?;
+        let salt = Wire::witness(&cs, self.salt)?;
+        mimc_gadget(&cs, &value, &salt)?.enforce_equal(&cs, &commitment)?;
+
+        let diff = self.value.wrapping_sub(self.min);
+        let mut recomposed = Wire::constant(Fr::zero());
+        for i in 0..RANGE_BITS {
+            let bit = Wire::witness(&cs, Fr::from((diff >> i) & 1))?;
+            bit.enforce_boolean(&cs)?;
+            recomposed = recomposed.add(&bit.scale(Fr::from(1u64 << i)));
+        }
+        recomposed.enforce_equal(&cs, &value.sub(&min))
+    }
+}
+
+/// Private leaf secret at `index` in a depth-4 tree  (public: valid, root)
+#[derive(Clone)]
+struct MerkleCircuit {
+    secrets: Vec<Fr>,
+    index: usize,
+}
+
+impl MerkleCircuit {
+    fn leaf(secret: Fr) -> Fr {
+        mimc_native(secret, Fr::zero())
+    }
+
+    /// Root and authentication path (sibling per level, leaf upwards).
+    fn root_and_path(&self) -> (Fr, Vec<Fr>) {
+        let mut level: Vec<Fr> = self.secrets.iter().map(|s| Self::leaf(*s)).collect();
+        let mut index = self.index;
+        let mut path = Vec::with_capacity(MERKLE_DEPTH);
+        for _ in 0..MERKLE_DEPTH {
+            path.push(level[index ^ 1]);
+            level = level
+                .chunks(2)
+                .map(|pair| mimc_native(pair[0], pair[1]))
+                .collect();
+            index /= 2;
+        }
+        (level[0], path)
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for MerkleCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let (root_val, path) = self.root_and_path();
+        valid_output(&cs)?;
+        let root = Wire::input(&cs, root_val)?;
+
+        let secret = Wire::witness(&cs, self.secrets[self.index])?;
+        let mut current = mimc_gadget(&cs, &secret, &Wire::constant(Fr::zero()))?;
+        for (level, sibling_val) in path.into_iter().enumerate() {
+            let bit = Wire::witness(&cs, Fr::from(((self.index >> level) & 1) as u64))?;
+            bit.enforce_boolean(&cs)?;
+            let sibling = Wire::witness(&cs, sibling_val)?;
+
+            // bit = 0: (current, sibling); bit = 1: (sibling, current)
+            let delta = bit.mul(&cs, &sibling.sub(&current))?;
+            let left = current.add(&delta);
+            let right = sibling.sub(&delta);
+            current = mimc_gadget(&cs, &left, &right)?;
+        }
+        current.enforce_equal(&cs, &root)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fixture generation
+// ---------------------------------------------------------------------------
+
+struct CircuitSpec {
+    name: &'static str,
+    circuit_type: &'static str,
+    description: &'static str,
+}
+
+const CIRCUITS: [CircuitSpec; 4] = [
+    CircuitSpec {
+        name: "square",
+        circuit_type: "verified-builder",
+        description: "x * x == y",
+    },
+    CircuitSpec {
+        name: "multi-input",
+        circuit_type: "grant-track-record",
+        description: "sum, product and sum of squares of private x, y",
+    },
+    CircuitSpec {
+        name: "range",
+        circuit_type: "team-attestation",
+        description: "committed private value >= public minimum",
+    },
+    CircuitSpec {
+        name: "merkle",
+        circuit_type: "verified-builder",
+        description: "private leaf is a member of a depth-4 MiMC tree",
+    },
+];
+
+/// Public inputs of a satisfied circuit, read back from its constraint system.
+fn public_inputs<C: ConstraintSynthesizer<Fr>>(circuit: C) -> Vec<Fr> {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    circuit
+        .generate_constraints(cs.clone())
+        .expect("constraints synthesize");
+    assert!(
+        cs.is_satisfied().unwrap(),
+        "fixture circuit is not satisfied"
+    );
+    let inputs = cs.borrow().unwrap().instance_assignment[1..].to_vec();
+    inputs
+}
+
+fn g1_to_strings(point: &G1Affine) -> Vec<String> {
+    vec![point.x.to_string(), point.y.to_string()]
+}
+
+/// snarkjs outputs G2 Fq2 as [c1, c0], so swap from arkworks (c0, c1).
+fn g2_to_strings(point: &G2Affine) -> Vec<Vec<String>> {
+    vec![
+        vec![point.x.c1.to_string(), point.x.c0.to_string()],
+        vec![point.y.c1.to_string(), point.y.c0.to_string()],
+    ]
+}
+
+fn vk_json(vk: &VerifyingKey<Bn254>) -> Value {
+    json!({
+        "alpha": g1_to_strings(&vk.alpha_g1),
+        "beta": g2_to_strings(&vk.beta_g2),
+        "gamma": g2_to_strings(&vk.gamma_g2),
+        "delta": g2_to_strings(&vk.delta_g2),
+        "ic": vk.gamma_abc_g1.iter().map(g1_to_strings).collect::<Vec<_>>()
+    })
+}
+
+fn proof_json(proof: &Proof<Bn254>) -> Value {
+    json!({
+        "pi_a": g1_to_strings(&proof.a),
+        "pi_b": g2_to_strings(&proof.b),
+        "pi_c": g1_to_strings(&proof.c)
+    })
+}
+
+fn signals(inputs: &[Fr]) -> Vec<String> {
+    inputs.iter().map(|x| x.to_string()).collect()
+}
+
+/// A proof checked against the reference verifier before it is written out.
+struct ProvedInstance {
+    proof: Proof<Bn254>,
+    inputs: Vec<Fr>,
+}
+
+fn prove<C: ConstraintSynthesizer<Fr> + Clone>(
+    pk: &ProvingKey<Bn254>,
+    circuit: C,
+    rng: &mut StdRng,
+) -> ProvedInstance {
+    let inputs = public_inputs(circuit.clone());
+    let proof = ArkGroth16::<Bn254>::prove(pk, circuit, rng).expect("proving succeeds");
+    assert!(
+        ArkGroth16::<Bn254>::verify(&pk.vk, &inputs, &proof).unwrap(),
+        "generated proof does not verify"
+    );
+    ProvedInstance { proof, inputs }
+}
+
+fn verify_args(
+    circuit_type: &str,
+    proof: &Proof<Bn254>,
+    signals: Vec<String>,
+    store: bool,
+    claim: Option<String>,
+    expiration: u64,
+) -> Value {
+    let mut input = json!({
+        "circuit_type": circuit_type,
+        "proof": proof_json(proof),
+        "public_signals": signals,
+        "store_credential": store,
+    });
+    if store {
+        input["custom_expiration"] = json!(expiration);
+        input["claim"] = json!(claim);
+    }
+    json!({ "input": input })
+}
+
+/// Set up `instances[0]`'s circuit, prove every instance and write the
+/// circuit's fixture files. The first instance is the primary statement;
+/// the rest feed the batch fixtures.
+fn write_circuit<C: ConstraintSynthesizer<Fr> + Clone>(
+    spec: &CircuitSpec,
+    instances: Vec<C>,
+    rng: &mut StdRng,
+    out: &Path,
+) {
+    let (pk, vk) = ArkGroth16::<Bn254>::circuit_specific_setup(instances[0].clone(), rng)
+        .expect("setup succeeds");
+    let primary = prove(&pk, instances[0].clone(), rng);
+    // Re-randomized proof of the same statement, used for renewal
+    let renewed = prove(&pk, instances[0].clone(), rng);
+    let batch: Vec<ProvedInstance> = instances
+        .iter()
+        .map(|c| prove(&pk, c.clone(), rng))
+        .collect();
+
+    let file = |kind: &str| format!("zk-{}-{kind}.json", spec.name);
+    let valid = signals(&primary.inputs);
+    let mut invalid_inputs = primary.inputs.clone();
+    *invalid_inputs
+        .last_mut()
+        .expect("circuit has public inputs") += Fr::one();
+    let invalid = signals(&invalid_inputs);
+
+    write_json(
+        out,
+        &file("circuit"),
+        &json!({
+            "circuit": spec.name,
+            "description": spec.description,
+            "circuit_type": spec.circuit_type,
+            "vk": vk_json(&vk),
+            "proof": proof_json(&primary.proof),
+            "valid_signals": valid,
+            "invalid_signals": invalid,
+        }),
+    );
+    write_json(
+        out,
+        &file("set-vk-args"),
+        &json!({ "circuit_type": spec.circuit_type, "vk": vk_json(&vk) }),
+    );
+    write_json(
+        out,
+        &file("verify-args"),
+        &verify_args(
+            spec.circuit_type,
+            &primary.proof,
+            valid.clone(),
+            true,
+            Some(format!("e2e-{}-groth16", spec.name)),
+            3600,
+        ),
+    );
+    write_json(
+        out,
+        &file("verify-invalid-args"),
+        &verify_args(
+            spec.circuit_type,
+            &primary.proof,
+            invalid,
+            true,
+            Some("should-not-be-stored".to_string()),
+            3600,
+        ),
+    );
+    write_json(
+        out,
+        &file("view-args"),
+        &verify_args(
+            spec.circuit_type,
+            &primary.proof,
+            valid.clone(),
+            false,
+            None,
+            0,
+        ),
+    );
+    for (i, instance) in batch.iter().enumerate() {
+        write_json(
+            out,
+            &file(&format!("batch-{i}-args")),
+            &verify_args(
+                spec.circuit_type,
+                &instance.proof,
+                signals(&instance.inputs),
+                false,
+                None,
+                0,
+            ),
+        );
+    }
+    write_json(
+        out,
+        &file("renew-args"),
+        &verify_args(
+            spec.circuit_type,
+            &renewed.proof,
+            valid,
+            true,
+            Some(format!("e2e-{}-groth16-renewed", spec.name)),
+            7200,
+        ),
+    );
+    write_json(
+        out,
+        &file("revoke-args"),
+        &json!({
+            "credential_id": "__CREDENTIAL_ID__",
+            "reason": "superseded by renewed credential",
+        }),
+    );
+}
+
+fn generate(spec: &CircuitSpec, rng: &mut StdRng, out: &Path) {
+    match spec.name {
+        "square" => write_circuit(
+            spec,
+            (0..BATCH_SIZE)
+                .map(|i| SquareCircuit {
+                    x: Fr::from(3 + i as u64),
+                })
+                .collect(),
+            rng,
+            out,
+        ),
+        "multi-input" => write_circuit(
+            spec,
+            (0..BATCH_SIZE)
+                .map(|i| MultiInputCircuit {
+                    x: Fr::from(5 + i as u64),
+                    y: Fr::from(11 + 2 * i as u64),
+                })
+                .collect(),
+            rng,
+            out,
+        ),
+        "range" => write_circuit(
+            spec,
+            (0..BATCH_SIZE)
+                .map(|i| RangeCircuit {
+                    value: 180 + 30 * i as u64,
+                    salt: Fr::from(0x5a17 + i as u64),
+                    min: 90,
+                })
+                .collect(),
+            rng,
+            out,
+        ),
+        "merkle" => {
+            let secrets: Vec<Fr> = (0..1u64 << MERKLE_DEPTH)
+                .map(|i| Fr::from(1000 + i))
+                .collect();
+            write_circuit(
+                spec,
+                (0..BATCH_SIZE)
+                    .map(|i| MerkleCircuit {
+                        secrets: secrets.clone(),
+                        index: 5 + 4 * i,
+                    })
+                    .collect(),
+                rng,
+                out,
+            )
+        }
+        other => unreachable!("unknown circuit {other}"),
+    }
+}
+
+fn write_json(dir: &Path, name: &str, value: &Value) {
+    let path = dir.join(name);
+    let content = serde_json::to_string(value).expect("Failed to serialize JSON");
+    fs::write(&path, &content)
+        .unwrap_or_else(|e| panic!("Failed to write {}: {e}", path.display()));
+}
+
+/// Tab-separated, one circuit per line:
+/// name, circuit_type, batch size, description.
+///
+/// Rows for circuits not generated in this run are kept from the existing
+/// manifest, so `--circuit <name>` only replaces that circuit's row.
+fn write_manifest(dir: &Path, specs: &[&CircuitSpec], seed: u64) {
+    let path = dir.join("manifest.tsv");
+    let mut rows: BTreeMap<String, String> = fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let name = line.split('\t').next()?;
+            Some((name.to_string(), line.to_string()))
+        })
+        .collect();
+    for spec in specs {
+        rows.insert(
+            spec.name.to_string(),
+            format!(
+                "{}\t{}\t{BATCH_SIZE}\t{}",
+                spec.name, spec.circuit_type, spec.description
+            ),
+        );
+    }
+
+    let mut manifest = format!(
+        "# Generated by generate-fixtures (last run: seed {seed}); files are zk-<name>-<kind>.json\n\
+         # name\tcircuit_type\tbatch\tdescription\n"
+    );
+    // Known circuits in their declared order, then any rows this build doesn't know
+    for spec in &CIRCUITS {
+        if let Some(row) = rows.remove(spec.name) {
+            manifest.push_str(&row);
+            manifest.push('\n');
+        }
+    }
+    for row in rows.values() {
+        manifest.push_str(row);
+        manifest.push('\n');
+    }
+    fs::write(&path, manifest)
+        .unwrap_or_else(|e| panic!("Failed to write {}: {e}", path.display()));
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: generate-fixtures [--circuit square|multi-input|range|merkle|all] [--seed N] [--out DIR]"
+    );
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut circuit = "all".to_string();
+    let mut seed: u64 = 42;
+    let mut out: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("scripts")
+        .join("fixtures");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--circuit" => circuit = value(),
+            "--seed" => seed = value().parse().unwrap_or_else(|_| usage()),
+            "--out" => out = PathBuf::from(value()),
+            _ => usage(),
+        }
+    }
+
+    let specs: Vec<&CircuitSpec> = CIRCUITS
+        .iter()
+        .filter(|spec| circuit == "all" || spec.name == circuit)
+        .collect();
+    if specs.is_empty() {
+        usage();
+    }
+    fs::create_dir_all(&out).expect("Failed to create fixtures dir");
+
+    for spec in &specs {
+        // Per-circuit RNG so one circuit's fixtures don't depend on which others ran
+        let index = CIRCUITS.iter().position(|c| c.name == spec.name).unwrap() as u64;
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(index));
+        generate(spec, &mut rng, &out);
+        println!(
+            "  {:<12} {} ({})",
+            spec.name, spec.description, spec.circuit_type
+        );
+    }
+    write_manifest(&out, &specs, seed);
+    println!("Generated fixtures and manifest.tsv in {}", out.display());
+}
@@ -25,6 +25,54 @@ pub enum ContractError {
     InsufficientDeposit { required: u128, attached: u128 },
     /// Verification key format is invalid
     InvalidVerificationKey(String),
+    /// Action is guarded by multisig and must go through a proposal
+    MultisigRequired(String),
+    /// Multisig approver set or threshold is invalid
+    InvalidMultisigConfig(String),
+    /// No pending proposal with the given ID
+    ProposalNotFound(u64),
+    /// Proposal passed its expiry before reaching the threshold
+    ProposalExpired(u64),
+    /// Approver has already confirmed this proposal
+    AlreadyApproved(u64),
+    /// No treasury beneficiary has been configured
+    BeneficiaryNotSet,
+    /// Withdrawal exceeds the collected treasury balance
+    InsufficientTreasury { requested: u128, available: u128 },
+    /// Caller exceeded the `verify_proof` quota; retry at the given timestamp (seconds)
+    RateLimited { retry_after: u64 },
+    /// Rate-limit quota or window is invalid
+    InvalidRateLimitConfig(String),
+    /// Circuit is paused individually
+    CircuitPaused(String),
+    /// Method group is paused individually
+    MethodPaused(String),
+    /// No credential with this ID is awaiting acceptance
+    PendingCredentialNotFound(String),
+    /// Credential ID has been revoked
+    CredentialRevoked(String),
+    /// Privacy-mode salts are malformed or the options conflict
+    InvalidPrivacyOptions(String),
+    /// Stored state version is newer than this code or unknown
+    UnsupportedStateVersion(u32),
+    /// `migrate` was called on an account with no contract state
+    StateNotInitialized,
+    /// Relay intent does not match the submitted call
+    InvalidIntent(String),
+    /// Relay intent signature does not verify against the public key
+    InvalidIntentSignature,
+    /// Relay intent is past its expiry
+    IntentExpired,
+    /// Relay intent nonce is not above the holder's last used nonce
+    StaleNonce { nonce: u64, last_used: u64 },
+    /// Public key may not sign relay intents for the holder
+    RelayKeyNotAuthorized(String),
+    /// No presentation grant with this ID
+    PresentationGrantNotFound(String),
+    /// Presentation grant is past its expiry
+    PresentationGrantExpired(String),
+    /// Presentation grant parameters are invalid
+    InvalidPresentationGrant(String),
 }
 
 impl fmt::Display for ContractError {
@@ -63,6 +111,91 @@ impl fmt::Display for ContractError {
             Self::InvalidVerificationKey(msg) => {
                 write!(f, "Invalid verification key: {msg}")
             }
+            Self::MultisigRequired(action) => {
+                write!(
+                    f,
+                    "Multisig required: {action} must be proposed and approved"
+                )
+            }
+            Self::InvalidMultisigConfig(msg) => {
+                write!(f, "Invalid multisig config: {msg}")
+            }
+            Self::ProposalNotFound(id) => {
+                write!(f, "Proposal not found: {id}")
+            }
+            Self::ProposalExpired(id) => {
+                write!(f, "Proposal has expired: {id}")
+            }
+            Self::AlreadyApproved(id) => {
+                write!(f, "Proposal already approved by caller: {id}")
+            }
+            Self::BeneficiaryNotSet => {
+                write!(f, "Treasury beneficiary is not set")
+            }
+            Self::InsufficientTreasury {
+                requested,
+                available,
+            } => {
+                write!(
+                    f,
+                    "Insufficient treasury: requested {requested} yoctoNEAR, available {available}"
+                )
+            }
+            Self::RateLimited { retry_after } => {
+                write!(f, "Rate limited: retry after {retry_after}")
+            }
+            Self::InvalidRateLimitConfig(msg) => {
+                write!(f, "Invalid rate limit config: {msg}")
+            }
+            Self::CircuitPaused(ct) => {
+                write!(f, "Circuit is paused: {ct}")
+            }
+            Self::MethodPaused(method) => {
+                write!(f, "Method group is paused: {method}")
+            }
+            Self::PendingCredentialNotFound(id) => {
+                write!(f, "Pending credential not found: {id}")
+            }
+            Self::CredentialRevoked(id) => {
+                write!(f, "Credential has been revoked: {id}")
+            }
+            Self::InvalidPrivacyOptions(msg) => {
+                write!(f, "Invalid privacy options: {msg}")
+            }
+            Self::UnsupportedStateVersion(version) => {
+                write!(f, "Unsupported state version: {version}")
+            }
+            Self::StateNotInitialized => write!(f, "Contract state is not initialized"),
+            Self::InvalidIntent(msg) => {
+                write!(f, "Invalid relay intent: {msg}")
+            }
+            Self::InvalidIntentSignature => {
+                write!(f, "Invalid relay intent signature")
+            }
+            Self::IntentExpired => {
+                write!(f, "Relay intent has expired")
+            }
+            Self::StaleNonce { nonce, last_used } => {
+                write!(
+                    f,
+                    "Stale relay nonce {nonce}: last used nonce is {last_used}"
+                )
+            }
+            Self::RelayKeyNotAuthorized(holder) => {
+                write!(
+                    f,
+                    "Public key is not authorized to sign relay intents for {holder}"
+                )
+            }
+            Self::PresentationGrantNotFound(id) => {
+                write!(f, "Presentation grant not found: {id}")
+            }
+            Self::PresentationGrantExpired(id) => {
+                write!(f, "Presentation grant has expired: {id}")
+            }
+            Self::InvalidPresentationGrant(msg) => {
+                write!(f, "Invalid presentation grant: {msg}")
+            }
         }
     }
 }
@@ -1,5 +1,5 @@
 use near_sdk::serde::Serialize;
-use near_sdk::AccountId;
+use near_sdk::{AccountId, PublicKey};
 
 const EVENT_STANDARD: &str = "shade-zk-verifier";
 const EVENT_VERSION: &str = "1.0.0";
@@ -45,6 +45,15 @@ struct CredentialStoredData {
     expires_at: u64,
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct PrivateCredentialStoredData {
+    credential_id: String,
+    owner_hash: String,
+    circuit_type: String,
+    expires_at: u64,
+}
+
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 struct CredentialRemovedData {
@@ -66,6 +75,20 @@ struct ContractPausedData {
     paused: bool,
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct CircuitPausedData {
+    circuit_type: String,
+    paused: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct MethodPausedData {
+    method: String,
+    paused: bool,
+}
+
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 struct OwnershipProposedData {
@@ -86,6 +109,129 @@ struct AdminChangedData {
     account: String,
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProposalData {
+    proposal_id: u64,
+    action: String,
+    account: String,
+    approvals: u32,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ProposalExecutedData {
+    proposal_id: u64,
+    action: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct MultisigUpdatedData {
+    approvers: Vec<String>,
+    threshold: u32,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct VerificationFeeSetData {
+    circuit_type: String,
+    fee: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct TreasuryWithdrawnData {
+    beneficiary: String,
+    amount: String,
+    success: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct StatsEpochStartedData {
+    epoch: u32,
+    started_at: u64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct CredentialPendingData {
+    credential_id: String,
+    holder: String,
+    issuer: String,
+    circuit_type: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct CredentialAcceptedData {
+    credential_id: String,
+    holder: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct CredentialRejectedData {
+    credential_id: String,
+    rejected_by: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct AutoAcceptSetData {
+    holder: String,
+    issuer: String,
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct StateMigratedData {
+    from_version: u32,
+    to_version: u32,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct RelayKeySetData {
+    account: String,
+    public_key: String,
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct IntentRelayedData {
+    holder: String,
+    relayer: String,
+    nonce: u64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct PresentationGrantedData {
+    grant_id: String,
+    holder: String,
+    grantee: String,
+    credential_ids: Vec<String>,
+    expires_at: u64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct PresentationGrantEventData {
+    grant_id: String,
+    account: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct SignalIndexSetData {
+    circuit_type: String,
+    signal_index: Option<u32>,
+}
+
 // ---------------------------------------------------------------------------
 // Public emit functions
 // ---------------------------------------------------------------------------
@@ -128,6 +274,24 @@ pub fn emit_credential_stored(
     );
 }
 
+/// Privacy-mode counterpart of `credential_stored`; carries no account ID.
+pub fn emit_private_credential_stored(
+    credential_id: &str,
+    owner_hash: &str,
+    circuit_type: &str,
+    expires_at: u64,
+) {
+    emit(
+        "private_credential_stored",
+        &PrivateCredentialStoredData {
+            credential_id: credential_id.to_string(),
+            owner_hash: owner_hash.to_string(),
+            circuit_type: circuit_type.to_string(),
+            expires_at,
+        },
+    );
+}
+
 pub fn emit_credential_removed(credential_id: &str, removed_by: &AccountId) {
     emit(
         "credential_removed",
@@ -153,6 +317,26 @@ pub fn emit_contract_paused(paused: bool) {
     emit("contract_paused", &ContractPausedData { paused });
 }
 
+pub fn emit_circuit_paused(circuit_type: &str, paused: bool) {
+    emit(
+        "circuit_paused",
+        &CircuitPausedData {
+            circuit_type: circuit_type.to_string(),
+            paused,
+        },
+    );
+}
+
+pub fn emit_method_paused(method: &str, paused: bool) {
+    emit(
+        "method_paused",
+        &MethodPausedData {
+            method: method.to_string(),
+            paused,
+        },
+    );
+}
+
 pub fn emit_ownership_proposed(current_owner: &AccountId, proposed_owner: &AccountId) {
     emit(
         "ownership_proposed",
@@ -190,3 +374,214 @@ pub fn emit_admin_removed(account: &AccountId) {
         },
     );
 }
+
+pub fn emit_proposal_created(proposal_id: u64, action: &str, proposer: &AccountId) {
+    emit(
+        "proposal_created",
+        &ProposalData {
+            proposal_id,
+            action: action.to_string(),
+            account: proposer.to_string(),
+            approvals: 1,
+        },
+    );
+}
+
+pub fn emit_proposal_approved(
+    proposal_id: u64,
+    action: &str,
+    approver: &AccountId,
+    approvals: u32,
+) {
+    emit(
+        "proposal_approved",
+        &ProposalData {
+            proposal_id,
+            action: action.to_string(),
+            account: approver.to_string(),
+            approvals,
+        },
+    );
+}
+
+pub fn emit_proposal_executed(proposal_id: u64, action: &str) {
+    emit(
+        "proposal_executed",
+        &ProposalExecutedData {
+            proposal_id,
+            action: action.to_string(),
+        },
+    );
+}
+
+/// An empty approver list with threshold 0 means multisig was disabled.
+pub fn emit_multisig_updated(approvers: &[AccountId], threshold: u32) {
+    emit(
+        "multisig_updated",
+        &MultisigUpdatedData {
+            approvers: approvers.iter().map(|a| a.to_string()).collect(),
+            threshold,
+        },
+    );
+}
+
+pub fn emit_verification_fee_set(circuit_type: &str, fee: u128) {
+    emit(
+        "verification_fee_set",
+        &VerificationFeeSetData {
+            circuit_type: circuit_type.to_string(),
+            fee: fee.to_string(),
+        },
+    );
+}
+
+pub fn emit_treasury_withdrawn(beneficiary: &AccountId, amount: u128, success: bool) {
+    emit(
+        "treasury_withdrawn",
+        &TreasuryWithdrawnData {
+            beneficiary: beneficiary.to_string(),
+            amount: amount.to_string(),
+            success,
+        },
+    );
+}
+
+pub fn emit_stats_epoch_started(epoch: u32, started_at: u64) {
+    emit(
+        "stats_epoch_started",
+        &StatsEpochStartedData { epoch, started_at },
+    );
+}
+
+pub fn emit_credential_pending(
+    credential_id: &str,
+    holder: &AccountId,
+    issuer: &AccountId,
+    circuit_type: &str,
+) {
+    emit(
+        "credential_pending",
+        &CredentialPendingData {
+            credential_id: credential_id.to_string(),
+            holder: holder.to_string(),
+            issuer: issuer.to_string(),
+            circuit_type: circuit_type.to_string(),
+        },
+    );
+}
+
+pub fn emit_credential_accepted(credential_id: &str, holder: &AccountId) {
+    emit(
+        "credential_accepted",
+        &CredentialAcceptedData {
+            credential_id: credential_id.to_string(),
+            holder: holder.to_string(),
+        },
+    );
+}
+
+/// Emitted both when the holder rejects and when the issuer cancels.
+pub fn emit_credential_rejected(credential_id: &str, rejected_by: &AccountId) {
+    emit(
+        "credential_rejected",
+        &CredentialRejectedData {
+            credential_id: credential_id.to_string(),
+            rejected_by: rejected_by.to_string(),
+        },
+    );
+}
+
+pub fn emit_auto_accept_set(holder: &AccountId, issuer: &AccountId, enabled: bool) {
+    emit(
+        "auto_accept_set",
+        &AutoAcceptSetData {
+            holder: holder.to_string(),
+            issuer: issuer.to_string(),
+            enabled,
+        },
+    );
+}
+
+pub fn emit_state_migrated(from_version: u32, to_version: u32) {
+    emit(
+        "state_migrated",
+        &StateMigratedData {
+            from_version,
+            to_version,
+        },
+    );
+}
+
+/// `signal_index` is `None` when indexing was turned off for the circuit.
+pub fn emit_signal_index_set(circuit_type: &str, signal_index: Option<u32>) {
+    emit(
+        "signal_index_set",
+        &SignalIndexSetData {
+            circuit_type: circuit_type.to_string(),
+            signal_index,
+        },
+    );
+}
+
+pub fn emit_relay_key_set(account: &AccountId, public_key: &PublicKey, enabled: bool) {
+    emit(
+        "relay_key_set",
+        &RelayKeySetData {
+            account: account.to_string(),
+            public_key: String::from(public_key),
+            enabled,
+        },
+    );
+}
+
+pub fn emit_intent_relayed(holder: &AccountId, relayer: &AccountId, nonce: u64) {
+    emit(
+        "intent_relayed",
+        &IntentRelayedData {
+            holder: holder.to_string(),
+            relayer: relayer.to_string(),
+            nonce,
+        },
+    );
+}
+
+pub fn emit_presentation_granted(
+    grant_id: &str,
+    holder: &AccountId,
+    grantee: &AccountId,
+    credential_ids: &[String],
+    expires_at: u64,
+) {
+    emit(
+        "presentation_granted",
+        &PresentationGrantedData {
+            grant_id: grant_id.to_string(),
+            holder: holder.to_string(),
+            grantee: grantee.to_string(),
+            credential_ids: credential_ids.to_vec(),
+            expires_at,
+        },
+    );
+}
+
+/// `account` is the holder who revoked the grant.
+pub fn emit_presentation_revoked(grant_id: &str, account: &AccountId) {
+    emit(
+        "presentation_revoked",
+        &PresentationGrantEventData {
+            grant_id: grant_id.to_string(),
+            account: account.to_string(),
+        },
+    );
+}
+
+/// `account` is the grantee who read the presentation.
+pub fn emit_presentation_accessed(grant_id: &str, account: &AccountId) {
+    emit(
+        "presentation_accessed",
+        &PresentationGrantEventData {
+            grant_id: grant_id.to_string(),
+            account: account.to_string(),
+        },
+    );
+}
@@ -1,27 +1,49 @@
 mod errors;
 mod events;
+mod migration;
+mod privacy;
+mod relay;
 mod storage;
 mod types;
+mod vc;
 mod verifier;
 
-use near_sdk::store::{LookupMap, LookupSet, IterableSet};
-use near_sdk::{env, near, AccountId, FunctionError, PanicOnDefault};
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::store::{IterableMap, IterableSet, LookupMap, LookupSet};
+use near_sdk::{
+    env, is_promise_success, near, AccountId, FunctionError, Gas, NearToken, PanicOnDefault,
+    Promise, PublicKey,
+};
 
 use errors::ContractError;
+use migration::CredentialV1;
 use storage::StorageKey;
+use types::CircuitMetrics;
 
 // Re-export public types for integration tests and downstream consumers
 pub use types::{
-    CircuitType, ContractConfig, ContractStats, Credential, Groth16Proof,
-    PaginatedCredentials, VerificationKey, VerifyProofInput, VerificationResult,
+    AdminAction, CircuitStats, CircuitType, ContractConfig, ContractStats, Credential,
+    CredentialFilter, CredentialPage, Groth16Proof, MethodGroup, MultisigConfig,
+    PaginatedCredentials, PendingCredential, Presentation, PresentationGrant, PrivacyOptions,
+    PrivateCredential, Proposal, PruneResult, RateLimitBucket, RateLimitConfig, RelayIntent,
+    VerifiableCredential, VerificationKey, VerificationResult, VerifyProofInput,
 };
 
 /// ZK Groth16 proof verifier and credential storage contract for NEAR.
 ///
 /// Provides:
 /// - On-chain Groth16 proof verification using arkworks (pure WASM)
-/// - Credential storage with per-owner indexing and expiration
-/// - Admin controls: pause, verification key management, config
+/// - Credential storage with per-owner, per-circuit and issue-time indexing
+///   and expiration
+/// - Admin controls: global, per-circuit and per-method pause, verification
+///   key management, config
+/// - Optional M-of-N multisig approval for sensitive admin actions
+/// - Per-circuit verification fees collected into a withdrawable treasury
+/// - Per-account `verify_proof` rate limiting with admin-managed exemptions
+/// - Opt-in privacy mode storing a signals commitment and salted owner hash
+/// - Optional per-circuit lookup of credentials by a public-signal value
+/// - Relayed verification authorized by holder-signed ed25519 intents
+/// - Holder-issued, time-limited presentation grants for named verifiers
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
 pub struct ZKVerifier {
@@ -41,6 +63,8 @@ pub struct ZKVerifier {
     verification_keys: LookupMap<String, VerificationKey>,
     /// Credentials by ID
     credentials: LookupMap<String, Credential>,
+    /// Credentials stored before state version 2, converted when read
+    legacy_credentials: LookupMap<String, CredentialV1>,
     /// Credential ID sets by owner account
     credentials_by_owner: LookupMap<AccountId, IterableSet<String>>,
     /// Total number of proof verifications performed
@@ -53,6 +77,78 @@ pub struct ZKVerifier {
     credential_nonce: u64,
     /// Set of revoked credential IDs (tombstones)
     revoked_credentials: LookupSet<String>,
+    /// M-of-N approver set; `None` means admin actions run directly
+    multisig: Option<MultisigConfig>,
+    /// Pending multisig proposals by ID
+    proposals: IterableMap<u64, Proposal>,
+    /// Monotonic nonce for proposal IDs
+    proposal_nonce: u64,
+    /// Position of the next lazy proposal-pruning scan
+    proposal_prune_cursor: u32,
+    /// Per-circuit verification fees in yoctoNEAR (absent = free)
+    verification_fees: LookupMap<String, u128>,
+    /// Collected fees not yet withdrawn (yoctoNEAR)
+    treasury_balance: u128,
+    /// Lifetime verification fee revenue (yoctoNEAR)
+    total_fees_collected: u128,
+    /// Account that receives treasury withdrawals
+    treasury_beneficiary: Option<AccountId>,
+    /// Per-account `verify_proof` quota; `None` means unlimited
+    rate_limit: Option<RateLimitConfig>,
+    /// Accounts exempt from the rate limit
+    rate_limit_exempt: LookupSet<AccountId>,
+    /// Current-window call counters by account
+    rate_limit_buckets: IterableMap<AccountId, RateLimitBucket>,
+    /// Bumped by every `set_rate_limit`; buckets from older epochs count as empty
+    rate_limit_epoch: u32,
+    /// Position of the next lazy bucket-pruning scan
+    rate_limit_prune_cursor: u32,
+    /// Circuits paused individually
+    paused_circuits: Vec<CircuitType>,
+    /// Method groups paused individually
+    paused_methods: Vec<MethodGroup>,
+    /// Verification key version per circuit type, bumped on every update
+    vk_versions: LookupMap<String, u32>,
+    /// Global issue-time index: issue sequence → credential ID
+    credentials_by_issue: LookupMap<u64, String>,
+    /// Per-circuit issue-time index: (circuit key, circuit sequence) → credential ID
+    credentials_by_circuit: LookupMap<(String, u64), String>,
+    /// Highest issued sequence per circuit type
+    circuit_issue_counts: LookupMap<String, u64>,
+    /// Verification metrics per (circuit key, reporting epoch)
+    circuit_metrics: LookupMap<(String, u32), CircuitMetrics>,
+    /// Current reporting epoch for circuit metrics
+    stats_epoch: u32,
+    /// Start time in seconds of each reporting epoch
+    stats_epoch_starts: LookupMap<u32, u64>,
+    /// Credentials issued to another holder and awaiting acceptance, by ID
+    pending_credentials: LookupMap<String, PendingCredential>,
+    /// Pending credential IDs by holder account
+    pending_by_holder: LookupMap<AccountId, IterableSet<String>>,
+    /// (holder, issuer) pairs where the holder auto-accepts the issuer's credentials
+    auto_accept: LookupSet<(AccountId, AccountId)>,
+    /// Privacy-mode credentials by ID
+    private_credentials: LookupMap<String, PrivateCredential>,
+    /// Privacy-mode credential ID sets by salted owner hash
+    private_by_owner: LookupMap<String, IterableSet<String>>,
+    /// Number of stored privacy-mode credentials
+    total_private_credentials: u64,
+    /// Public-signal position indexed per circuit key (absent = not indexed)
+    signal_index_positions: LookupMap<String, u32>,
+    /// Credential ID sets by (circuit key, signal position, signal value)
+    credentials_by_signal: LookupMap<storage::SignalKey, IterableSet<String>>,
+    /// Signal position each indexed credential was indexed under
+    credential_signal_positions: LookupMap<String, u32>,
+    /// (account, public key) pairs allowed to sign relay intents for the account
+    relay_keys: LookupSet<(AccountId, PublicKey)>,
+    /// Last used relay intent nonce per holder
+    relay_nonces: LookupMap<AccountId, u64>,
+    /// Presentation grants by ID
+    presentation_grants: LookupMap<String, PresentationGrant>,
+    /// Presentation grant ID sets by holder account
+    grants_by_holder: LookupMap<AccountId, IterableSet<String>>,
+    /// Monotonic nonce for presentation grant IDs
+    presentation_nonce: u64,
 }
 
 #[near]
@@ -64,7 +160,7 @@ impl ZKVerifier {
     /// Initialize the contract with an owner account.
     #[init]
     pub fn new(owner: AccountId) -> Self {
-        Self {
+        let mut contract = Self {
             owner,
             proposed_owner: None,
             admins: LookupSet::new(borsh::to_vec(&StorageKey::Admins).unwrap()),
@@ -74,7 +170,8 @@ impl ZKVerifier {
             verification_keys: LookupMap::new(
                 borsh::to_vec(&StorageKey::VerificationKeys).unwrap(),
             ),
-            credentials: LookupMap::new(borsh::to_vec(&StorageKey::Credentials).unwrap()),
+            credentials: LookupMap::new(borsh::to_vec(&StorageKey::CredentialsV2).unwrap()),
+            legacy_credentials: LookupMap::new(borsh::to_vec(&StorageKey::Credentials).unwrap()),
             credentials_by_owner: LookupMap::new(
                 borsh::to_vec(&StorageKey::CredentialsByOwner).unwrap(),
             ),
@@ -85,7 +182,83 @@ impl ZKVerifier {
             revoked_credentials: LookupSet::new(
                 borsh::to_vec(&StorageKey::RevokedCredentials).unwrap(),
             ),
-        }
+            multisig: None,
+            proposals: IterableMap::new(borsh::to_vec(&StorageKey::Proposals).unwrap()),
+            proposal_nonce: 0,
+            proposal_prune_cursor: 0,
+            verification_fees: LookupMap::new(
+                borsh::to_vec(&StorageKey::VerificationFees).unwrap(),
+            ),
+            treasury_balance: 0,
+            total_fees_collected: 0,
+            treasury_beneficiary: None,
+            rate_limit: None,
+            rate_limit_exempt: LookupSet::new(borsh::to_vec(&StorageKey::RateLimitExempt).unwrap()),
+            rate_limit_buckets: IterableMap::new(
+                borsh::to_vec(&StorageKey::RateLimitBuckets).unwrap(),
+            ),
+            rate_limit_epoch: 0,
+            rate_limit_prune_cursor: 0,
+            paused_circuits: Vec::new(),
+            paused_methods: Vec::new(),
+            vk_versions: LookupMap::new(
+                borsh::to_vec(&StorageKey::VerificationKeyVersions).unwrap(),
+            ),
+            credentials_by_issue: LookupMap::new(
+                borsh::to_vec(&StorageKey::CredentialsByIssue).unwrap(),
+            ),
+            credentials_by_circuit: LookupMap::new(
+                borsh::to_vec(&StorageKey::CredentialsByCircuit).unwrap(),
+            ),
+            circuit_issue_counts: LookupMap::new(
+                borsh::to_vec(&StorageKey::CircuitIssueCounts).unwrap(),
+            ),
+            circuit_metrics: LookupMap::new(borsh::to_vec(&StorageKey::CircuitMetrics).unwrap()),
+            stats_epoch: 0,
+            stats_epoch_starts: LookupMap::new(
+                borsh::to_vec(&StorageKey::StatsEpochStarts).unwrap(),
+            ),
+            pending_credentials: LookupMap::new(
+                borsh::to_vec(&StorageKey::PendingCredentials).unwrap(),
+            ),
+            pending_by_holder: LookupMap::new(borsh::to_vec(&StorageKey::PendingByHolder).unwrap()),
+            auto_accept: LookupSet::new(borsh::to_vec(&StorageKey::AutoAccept).unwrap()),
+            private_credentials: LookupMap::new(
+                borsh::to_vec(&StorageKey::PrivateCredentials).unwrap(),
+            ),
+            private_by_owner: LookupMap::new(borsh::to_vec(&StorageKey::PrivateByOwner).unwrap()),
+            total_private_credentials: 0,
+            signal_index_positions: LookupMap::new(
+                borsh::to_vec(&StorageKey::SignalIndexPositions).unwrap(),
+            ),
+            credentials_by_signal: LookupMap::new(
+                borsh::to_vec(&StorageKey::CredentialsBySignal).unwrap(),
+            ),
+            credential_signal_positions: LookupMap::new(
+                borsh::to_vec(&StorageKey::CredentialSignalPositions).unwrap(),
+            ),
+            relay_keys: LookupSet::new(borsh::to_vec(&StorageKey::RelayKeys).unwrap()),
+            relay_nonces: LookupMap::new(borsh::to_vec(&StorageKey::RelayNonces).unwrap()),
+            presentation_grants: LookupMap::new(
+                borsh::to_vec(&StorageKey::PresentationGrants).unwrap(),
+            ),
+            grants_by_holder: LookupMap::new(borsh::to_vec(&StorageKey::GrantsByHolder).unwrap()),
+            presentation_nonce: 0,
+        };
+        contract
+            .stats_epoch_starts
+            .insert(0, env::block_timestamp() / 1_000_000_000);
+        migration::write_state_version(migration::STATE_VERSION);
+        contract
+    }
+
+    /// Convert the stored state to the current layout after a code upgrade.
+    /// Reads the state version recorded beside the contract state; a missing
+    /// version means the original (v1) layout. No-op on current state.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        migration::migrate_state()
     }
 
     // =========================================================================
@@ -93,19 +266,1184 @@ impl ZKVerifier {
     // =========================================================================
 
     /// Register or update a verification key for a circuit type.
-    /// Callable by owner or admin.
+    /// Callable by owner or admin. Requires a proposal while multisig is enabled.
     pub fn set_verification_key(&mut self, circuit_type: CircuitType, vk: VerificationKey) {
         self.assert_owner_or_admin();
+        self.assert_not_multisig_guarded("set_verification_key");
+        self.internal_set_verification_key(circuit_type, vk);
+    }
+
+    /// Pause or unpause the contract.
+    /// Requires a proposal while multisig is enabled.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.assert_owner();
+        self.assert_not_multisig_guarded("set_paused");
+        self.internal_set_paused(paused);
+    }
+
+    /// Pause or unpause a single circuit's verification and storage.
+    /// Requires a proposal while multisig is enabled.
+    pub fn set_circuit_paused(&mut self, circuit_type: CircuitType, paused: bool) {
+        self.assert_owner();
+        self.assert_not_multisig_guarded("set_circuit_paused");
+        self.internal_set_circuit_paused(circuit_type, paused);
+    }
+
+    /// Pause or unpause a method group (verify, store, revoke, remove).
+    /// Requires a proposal while multisig is enabled.
+    pub fn set_method_paused(&mut self, method: MethodGroup, paused: bool) {
+        self.assert_owner();
+        self.assert_not_multisig_guarded("set_method_paused");
+        self.internal_set_method_paused(method, paused);
+    }
+
+    /// Propose ownership transfer to a new account (step 1 of 2).
+    /// Only callable by the current owner. Requires a proposal while multisig is enabled.
+    pub fn propose_owner(&mut self, new_owner: AccountId) {
+        self.assert_owner();
+        self.assert_not_multisig_guarded("propose_owner");
+        self.internal_propose_owner(new_owner);
+    }
+
+    /// Accept ownership transfer (step 2 of 2).
+    /// Only callable by the proposed new owner.
+    pub fn accept_ownership(&mut self) {
+        let caller = env::predecessor_account_id();
+        let proposed = self
+            .proposed_owner
+            .as_ref()
+            .unwrap_or_else(|| ContractError::Unauthorized.panic());
+        if &caller != proposed {
+            ContractError::Unauthorized.panic();
+        }
+        let old_owner = self.owner.clone();
+        self.owner = caller;
+        self.proposed_owner = None;
+        events::emit_ownership_transferred(&old_owner, &self.owner);
+    }
+
+    /// Add an admin account. Only callable by the owner.
+    pub fn add_admin(&mut self, account: AccountId) {
+        self.assert_owner();
+        self.admins.insert(account.clone());
+        events::emit_admin_added(&account);
+    }
+
+    /// Remove an admin account. Only callable by the owner.
+    pub fn remove_admin(&mut self, account: AccountId) {
+        self.assert_owner();
+        self.admins.remove(&account);
+        events::emit_admin_removed(&account);
+    }
+
+    /// Check if an account is an admin.
+    pub fn is_admin(&self, account: AccountId) -> bool {
+        self.admins.contains(&account)
+    }
+
+    /// Update the default credential expiration period.
+    pub fn set_default_expiration(&mut self, seconds: u64) {
+        self.assert_owner();
+        self.default_expiration_secs = seconds;
+    }
+
+    // =========================================================================
+    // Verification methods
+    // =========================================================================
+
+    /// Verify a Groth16 proof on-chain.
+    /// Optionally stores a credential if `store_credential` is true in the input.
+    /// With `issue_to` set to another account, the credential waits for that
+    /// holder's `accept_credential` and the storage deposit stays in escrow.
+    /// The caller must attach the circuit's verification fee, plus the storage
+    /// cost when storing. Any excess deposit is refunded.
+    #[payable]
+    pub fn verify_proof(&mut self, input: VerifyProofInput) -> VerificationResult {
+        self.internal_verify_proof(input, env::predecessor_account_id())
+    }
+
+    /// Verify a proof on behalf of a holder who signed `intent` with
+    /// `public_key`. The relayer pays gas, fee and storage deposit; the
+    /// credential is bound to `intent.holder`, who is also the account
+    /// rate-limited. The key must be registered by the holder with
+    /// `add_relay_key`, unless the holder is the key's implicit account.
+    #[payable]
+    pub fn verify_proof_relayed(
+        &mut self,
+        input: VerifyProofInput,
+        intent: RelayIntent,
+        public_key: PublicKey,
+        signature: Base64VecU8,
+    ) -> VerificationResult {
+        self.assert_method_enabled(MethodGroup::Verify);
+        self.consume_relay_intent(&input, &intent, &public_key, &signature.0);
+        events::emit_intent_relayed(&intent.holder, &env::predecessor_account_id(), intent.nonce);
+        self.internal_verify_proof(input, intent.holder)
+    }
+
+    /// Hash a relay intent's `proof_hash` must equal for this input.
+    pub fn get_relay_proof_hash(&self, input: VerifyProofInput) -> String {
+        relay::proof_hash(&input)
+    }
+
+    /// Allow `public_key` to sign relay intents for the caller.
+    pub fn add_relay_key(&mut self, public_key: PublicKey) {
+        if relay::ed25519_key_bytes(&public_key).is_none() {
+            ContractError::InvalidIntent("relay keys must be ed25519".to_string()).panic();
+        }
+        let account = env::predecessor_account_id();
+        self.relay_keys
+            .insert((account.clone(), public_key.clone()));
+        events::emit_relay_key_set(&account, &public_key, true);
+    }
+
+    /// Stop `public_key` from signing relay intents for the caller.
+    pub fn remove_relay_key(&mut self, public_key: PublicKey) {
+        let account = env::predecessor_account_id();
+        self.relay_keys
+            .remove(&(account.clone(), public_key.clone()));
+        events::emit_relay_key_set(&account, &public_key, false);
+    }
+
+    /// Check if `public_key` may sign relay intents for `account`.
+    pub fn is_relay_key(&self, account: AccountId, public_key: PublicKey) -> bool {
+        relay::is_implicit_account_of(&account, &public_key)
+            || self.relay_keys.contains(&(account, public_key))
+    }
+
+    /// Last relay intent nonce used by `account` (0 if none).
+    pub fn get_relay_nonce(&self, account: AccountId) -> u64 {
+        self.relay_nonces.get(&account).copied().unwrap_or(0)
+    }
+
+    /// View-only verification — does not store anything.
+    pub fn verify_proof_view(&self, input: VerifyProofInput) -> VerificationResult {
+        self.assert_method_enabled(MethodGroup::Verify);
+        self.assert_circuit_enabled(&input.circuit_type);
+
+        let gas_before = env::used_gas().as_gas();
+
+        let key = input.circuit_type.as_key();
+        let vk = self
+            .verification_keys
+            .get(&key)
+            .unwrap_or_else(|| ContractError::VerificationKeyNotFound(key).panic())
+            .clone();
+
+        let is_valid =
+            match verifier::verify_groth16_proof(&vk, &input.proof, &input.public_signals) {
+                Ok(valid) => valid,
+                Err(_) => false,
+            };
+
+        let gas_after = env::used_gas().as_gas();
+
+        VerificationResult {
+            valid: is_valid,
+            credential_id: None,
+            pending: false,
+            gas_used: gas_after.saturating_sub(gas_before),
+        }
+    }
+
+    // =========================================================================
+    // Credential queries
+    // =========================================================================
+
+    /// Get a credential by ID.
+    pub fn get_credential(&self, credential_id: String) -> Option<Credential> {
+        self.credential(&credential_id)
+    }
+
+    /// Export a credential as a W3C Verifiable Credential (JSON-LD).
+    /// The issuer is this contract and `credentialStatus` points at its
+    /// revocation registry. Returns `None` for unknown or revoked credentials.
+    pub fn get_credential_vc(&self, credential_id: String) -> Option<VerifiableCredential> {
+        self.credential(&credential_id)
+            .map(|cred| vc::credential_to_vc(&cred, &env::current_account_id()))
+    }
+
+    /// Check if a credential exists, is not expired, and is not revoked.
+    /// Covers privacy-mode credentials as well.
+    pub fn is_credential_valid(&self, credential_id: String) -> Option<bool> {
+        if self.revoked_credentials.contains(&credential_id) {
+            return Some(false);
+        }
+        if let Some(cred) = self.private_credentials.get(&credential_id) {
+            return Some(cred.expires_at > env::block_timestamp() / 1_000_000_000);
+        }
+        storage::is_credential_valid(self.credential(&credential_id).as_ref())
+    }
+
+    /// Get a privacy-mode credential by ID.
+    pub fn get_private_credential(&self, credential_id: String) -> Option<PrivateCredential> {
+        self.private_credentials.get(&credential_id).cloned()
+    }
+
+    /// Get privacy-mode credentials by salted owner hash with pagination.
+    pub fn get_private_credentials_by_owner_hash(
+        &self,
+        owner_hash: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<PrivateCredential> {
+        let Some(ids) = self.private_by_owner.get(&owner_hash) else {
+            return vec![];
+        };
+        ids.iter()
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .filter_map(|id| self.private_credentials.get(id).cloned())
+            .collect()
+    }
+
+    /// Check an off-chain opening (signals and salt revealed by the holder)
+    /// against a privacy-mode credential's commitment. Only proves what was
+    /// verified; use `is_credential_valid` for expiry and revocation.
+    pub fn verify_credential_opening(
+        &self,
+        credential_id: String,
+        public_signals: Vec<String>,
+        signals_salt: String,
+    ) -> bool {
+        let Some(cred) = self.private_credentials.get(&credential_id) else {
+            return false;
+        };
+        privacy::try_parse_salt(&signals_salt).is_some_and(|salt| {
+            privacy::signals_commitment(&public_signals, &salt) == cred.signals_commitment
+        })
+    }
+
+    /// Get credentials for an owner account with pagination.
+    pub fn get_credentials_by_owner(
+        &self,
+        owner: AccountId,
+        include_expired: Option<bool>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> PaginatedCredentials {
+        let (credentials, total) = storage::get_credentials_by_owner(
+            |id| self.credential(id),
+            &self.credentials_by_owner,
+            &owner,
+            include_expired.unwrap_or(false),
+            offset.unwrap_or(0),
+            limit.unwrap_or(50),
+        );
+        let has_more = (offset.unwrap_or(0) + credentials.len() as u32) < total;
+        PaginatedCredentials {
+            credentials,
+            total,
+            has_more,
+        }
+    }
+
+    /// Find credentials of a circuit type whose public signal at `signal_index`
+    /// equals `value`, e.g. a Merkle root or attestation commitment. Only
+    /// credentials stored while that position was indexed are found;
+    /// privacy-mode and pre-v2 credentials are never indexed.
+    pub fn find_credentials_by_signal(
+        &self,
+        circuit_type: CircuitType,
+        signal_index: u32,
+        value: String,
+        include_expired: Option<bool>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> PaginatedCredentials {
+        let key = (circuit_type.as_key(), signal_index, value);
+        let (credentials, total) = storage::paginate_credential_set(
+            |id| self.credential(id),
+            self.credentials_by_signal.get(&key),
+            include_expired.unwrap_or(false),
+            offset.unwrap_or(0),
+            limit.unwrap_or(50),
+        );
+        let has_more = (offset.unwrap_or(0) + credentials.len() as u32) < total;
+        PaginatedCredentials {
+            credentials,
+            total,
+            has_more,
+        }
+    }
+
+    /// List all credentials in issue-time order with cursor pagination.
+    /// `cursor` defaults to the start of the index.
+    pub fn list_credentials(
+        &self,
+        cursor: Option<u64>,
+        limit: Option<u32>,
+        filter: Option<CredentialFilter>,
+    ) -> CredentialPage {
+        storage::list_indexed_credentials(
+            &self.credentials,
+            &self.revoked_credentials,
+            |seq| self.credentials_by_issue.get(&seq),
+            cursor.unwrap_or(1).max(1),
+            self.credential_nonce,
+            limit.unwrap_or(50),
+            &filter.unwrap_or_default(),
+        )
+    }
+
+    /// List credentials for one circuit type in issue-time order with cursor pagination.
+    /// `cursor` defaults to the start of the circuit's index.
+    pub fn list_credentials_by_circuit(
+        &self,
+        circuit_type: CircuitType,
+        cursor: Option<u64>,
+        limit: Option<u32>,
+        filter: Option<CredentialFilter>,
+    ) -> CredentialPage {
+        let key = circuit_type.as_key();
+        let end = self.circuit_issue_counts.get(&key).copied().unwrap_or(0);
+        storage::list_indexed_credentials(
+            &self.credentials,
+            &self.revoked_credentials,
+            |seq| self.credentials_by_circuit.get(&(key.clone(), seq)),
+            cursor.unwrap_or(1).max(1),
+            end,
+            limit.unwrap_or(50),
+            &filter.unwrap_or_default(),
+        )
+    }
+
+    /// Accept a credential issued to the caller by another account.
+    /// The issuer's escrowed deposit becomes the credential's storage deposit.
+    pub fn accept_credential(&mut self, credential_id: String) -> Credential {
+        self.assert_method_enabled(MethodGroup::Store);
+        if self.revoked_credentials.contains(&credential_id) {
+            ContractError::CredentialRevoked(credential_id).panic();
+        }
+        let pending = self.take_pending_for_holder(&credential_id);
+        // A circuit paused after verification keeps its pending credentials out
+        self.assert_circuit_enabled(&pending.credential.circuit_type);
+
+        // Issue-time order follows acceptance, not verification
+        self.credential_nonce += 1;
+        let mut credential = pending.credential;
+        credential.issue_seq = self.credential_nonce;
+        credential.circuit_seq = self.insert_credential(credential.clone());
+        events::emit_credential_accepted(&credential_id, &credential.owner);
+        credential
+    }
+
+    /// Decline a credential issued to the caller. Refunds the issuer's escrow.
+    pub fn reject_credential(&mut self, credential_id: String) {
+        self.assert_method_enabled(MethodGroup::Remove);
+        let pending = self.take_pending_for_holder(&credential_id);
+        let caller = env::predecessor_account_id();
+        self.refund_pending_escrow(&pending);
+        events::emit_credential_rejected(&credential_id, &caller);
+    }
+
+    /// Withdraw a credential the caller issued that has not been accepted yet.
+    /// Refunds the escrowed deposit to the caller.
+    pub fn cancel_pending_credential(&mut self, credential_id: String) {
+        self.assert_method_enabled(MethodGroup::Remove);
+        let caller = env::predecessor_account_id();
+        let is_issuer = self
+            .pending_credentials
+            .get(&credential_id)
+            .unwrap_or_else(|| {
+                ContractError::PendingCredentialNotFound(credential_id.clone()).panic()
+            })
+            .credential
+            .issued_by
+            .as_ref()
+            == Some(&caller);
+        if !is_issuer {
+            ContractError::Unauthorized.panic();
+        }
+        let pending = storage::remove_pending_credential(
+            &mut self.pending_credentials,
+            &mut self.pending_by_holder,
+            &credential_id,
+        )
+        .unwrap();
+        self.refund_pending_escrow(&pending);
+        events::emit_credential_rejected(&credential_id, &caller);
+    }
+
+    /// Let `issuer` store credentials for the caller without a separate
+    /// `accept_credential` step, or revoke that delegation.
+    pub fn set_auto_accept(&mut self, issuer: AccountId, enabled: bool) {
+        let holder = env::predecessor_account_id();
+        if enabled {
+            self.auto_accept.insert((holder.clone(), issuer.clone()));
+        } else {
+            self.auto_accept.remove(&(holder.clone(), issuer.clone()));
+        }
+        events::emit_auto_accept_set(&holder, &issuer, enabled);
+    }
+
+    /// Check if `holder` auto-accepts credentials from `issuer`.
+    pub fn is_auto_accepted(&self, holder: AccountId, issuer: AccountId) -> bool {
+        self.auto_accept.contains(&(holder, issuer))
+    }
+
+    /// Get a credential awaiting its holder's acceptance.
+    pub fn get_pending_credential(&self, credential_id: String) -> Option<PendingCredential> {
+        self.pending_credentials.get(&credential_id).cloned()
+    }
+
+    /// List credentials awaiting acceptance by a holder.
+    pub fn get_pending_credentials_for_holder(
+        &self,
+        holder: AccountId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<PendingCredential> {
+        let Some(ids) = self.pending_by_holder.get(&holder) else {
+            return vec![];
+        };
+        ids.iter()
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .filter_map(|id| self.pending_credentials.get(id).cloned())
+            .collect()
+    }
+
+    /// Remove a credential. Only the credential owner can do this.
+    pub fn remove_credential(&mut self, credential_id: String) -> bool {
+        self.assert_method_enabled(MethodGroup::Remove);
+        let caller = env::predecessor_account_id();
+
+        // Only the owner can remove their credential
+        let is_owner = self
+            .credential(&credential_id)
+            .is_some_and(|cred| cred.owner == caller);
+        if !is_owner {
+            return false;
+        }
+
+        self.delete_credential(&credential_id);
+        events::emit_credential_removed(&credential_id, &caller);
+        true
+    }
+
+    /// Remove a privacy-mode credential. The caller proves ownership with
+    /// the owner salt used at storage time.
+    pub fn remove_private_credential(&mut self, credential_id: String, owner_salt: String) -> bool {
+        self.assert_method_enabled(MethodGroup::Remove);
+        let caller = env::predecessor_account_id();
+        let salt = privacy::parse_salt(&owner_salt);
+
+        let is_owner = self
+            .private_credentials
+            .get(&credential_id)
+            .is_some_and(|cred| cred.owner_hash == privacy::owner_hash(&caller, &salt));
+        if !is_owner {
+            return false;
+        }
+
+        self.delete_private_credential(&credential_id);
+        events::emit_credential_removed(&credential_id, &caller);
+        true
+    }
+
+    /// Remove expired credentials, walking the issue-time index from `cursor`.
+    /// Only callable by owner or admin. Inspects a bounded number of index
+    /// slots per call; continue with the returned cursor.
+    pub fn prune_expired_credentials(
+        &mut self,
+        cursor: Option<u64>,
+        limit: Option<u32>,
+    ) -> PruneResult {
+        self.assert_owner_or_admin();
+        self.assert_method_enabled(MethodGroup::Remove);
+
+        let page = storage::list_indexed_credentials(
+            &self.credentials,
+            &self.revoked_credentials,
+            |seq| self.credentials_by_issue.get(&seq),
+            cursor.unwrap_or(1).max(1),
+            self.credential_nonce,
+            u32::MAX,
+            &CredentialFilter {
+                include_expired: true,
+                include_revoked: true,
+                vk_version: None,
+            },
+        );
+
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        let limit = limit.unwrap_or(100);
+        let caller = env::predecessor_account_id();
+        let mut removed = 0;
+        let mut next_cursor = page.next_cursor;
+        for cred in page.credentials.iter().filter(|c| c.expires_at <= now_secs) {
+            if removed >= limit {
+                next_cursor = Some(cred.issue_seq);
+                break;
+            }
+            self.delete_credential(&cred.id);
+            events::emit_credential_removed(&cred.id, &caller);
+            removed += 1;
+        }
+
+        PruneResult {
+            removed,
+            next_cursor,
+        }
+    }
+
+    /// Revoke a credential. Only callable by owner or admin.
+    /// Removes the credential data and marks the ID as permanently revoked.
+    /// A credential still awaiting acceptance is dropped and its escrow refunded.
+    pub fn revoke_credential(&mut self, credential_id: String, reason: String) {
+        self.assert_owner_or_admin();
+        self.assert_method_enabled(MethodGroup::Revoke);
+        self.internal_revoke_credential(&credential_id, &reason);
+    }
+
+    /// Revoke many credentials at once. Only callable by owner or admin.
+    /// Requires a proposal while multisig is enabled.
+    pub fn revoke_credentials(&mut self, credential_ids: Vec<String>, reason: String) {
+        self.assert_owner_or_admin();
+        self.assert_not_multisig_guarded("revoke_credentials");
+        self.assert_method_enabled(MethodGroup::Revoke);
+        for credential_id in &credential_ids {
+            self.internal_revoke_credential(credential_id, &reason);
+        }
+    }
+
+    /// Check if a credential has been revoked.
+    pub fn is_credential_revoked(&self, credential_id: String) -> bool {
+        self.revoked_credentials.contains(&credential_id)
+    }
+
+    // =========================================================================
+    // Presentation grants
+    // =========================================================================
+
+    /// Let `grantee` read the listed credentials for `duration_secs` through
+    /// `get_presentation`. Every credential must belong to the caller.
+    /// Privacy-mode credentials are rejected: the grant records the holder,
+    /// which would link them to the caller's account.
+    ///
+    /// The attached deposit must cover the grant's storage. Anything beyond
+    /// it is refunded, and the charge itself is returned on revoke.
+    #[payable]
+    pub fn create_presentation_grant(
+        &mut self,
+        grantee: AccountId,
+        credential_ids: Vec<String>,
+        duration_secs: u64,
+    ) -> PresentationGrant {
+        let holder = env::predecessor_account_id();
+        if credential_ids.is_empty() || credential_ids.len() > storage::MAX_PRESENTATION_CREDENTIALS
+        {
+            ContractError::InvalidPresentationGrant(format!(
+                "must cover 1 to {} credentials",
+                storage::MAX_PRESENTATION_CREDENTIALS
+            ))
+            .panic();
+        }
+        if duration_secs == 0 {
+            ContractError::InvalidPresentationGrant("duration must be positive".to_string())
+                .panic();
+        }
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        let expires_at = now_secs.checked_add(duration_secs).unwrap_or_else(|| {
+            ContractError::InvalidPresentationGrant("duration is too long".to_string()).panic()
+        });
+
+        let mut ids: Vec<String> = Vec::with_capacity(credential_ids.len());
+        for id in credential_ids {
+            if self.private_credentials.contains_key(&id) {
+                ContractError::InvalidPresentationGrant(format!(
+                    "privacy-mode credential {id} cannot be shared"
+                ))
+                .panic();
+            }
+            let Some(cred) = self.credential(&id) else {
+                ContractError::CredentialNotFound(id).panic()
+            };
+            if cred.owner != holder {
+                ContractError::Unauthorized.panic();
+            }
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        self.presentation_nonce += 1;
+        let id = storage::generate_grant_id(&holder, &grantee, self.presentation_nonce);
+        let mut grant = PresentationGrant {
+            id: id.clone(),
+            holder: holder.clone(),
+            grantee,
+            credential_ids: ids,
+            created_at: now_secs,
+            expires_at,
+            storage_deposit: U128(0),
+        };
+
+        // Charge for the bytes the grant and its index entry take
+        let usage_before = env::storage_usage();
+        storage::store_presentation_grant(
+            &mut self.presentation_grants,
+            &mut self.grants_by_holder,
+            grant.clone(),
+        );
+        self.flush_presentation_grant(&holder);
+        let bytes = env::storage_usage().saturating_sub(usage_before);
+        let required = env::storage_byte_cost().as_yoctonear() * u128::from(bytes);
+        let deposit = env::attached_deposit().as_yoctonear();
+        if deposit < required {
+            ContractError::InsufficientDeposit {
+                required,
+                attached: deposit,
+            }
+            .panic();
+        }
+        grant.storage_deposit = U128(required);
+        self.presentation_grants.insert(id, grant.clone());
+        if deposit > required {
+            let _ = Promise::new(holder).transfer(NearToken::from_yoctonear(deposit - required));
+        }
+
+        events::emit_presentation_granted(
+            &grant.id,
+            &grant.holder,
+            &grant.grantee,
+            &grant.credential_ids,
+            grant.expires_at,
+        );
+        grant
+    }
+
+    /// Revoke a presentation grant. Only the holder who created it can do this;
+    /// expired grants can be revoked to free their storage.
+    pub fn revoke_presentation_grant(&mut self, grant_id: String) {
+        let caller = env::predecessor_account_id();
+        let holder = &self
+            .presentation_grants
+            .get(&grant_id)
+            .unwrap_or_else(|| ContractError::PresentationGrantNotFound(grant_id.clone()).panic())
+            .holder;
+        if *holder != caller {
+            ContractError::Unauthorized.panic();
+        }
+        let grant = storage::remove_presentation_grant(
+            &mut self.presentation_grants,
+            &mut self.grants_by_holder,
+            &grant_id,
+        )
+        .unwrap();
+        if grant.storage_deposit.0 > 0 {
+            let _ = Promise::new(caller.clone())
+                .transfer(NearToken::from_yoctonear(grant.storage_deposit.0));
+        }
+        events::emit_presentation_revoked(&grant_id, &caller);
+    }
+
+    /// Read the credentials covered by a grant. Only the grantee can call this,
+    /// as a transaction (views have no caller), and each read is logged.
+    /// Contract state itself stays publicly readable on-chain; the grant
+    /// controls this method, not the underlying data.
+    pub fn get_presentation(&self, grant_id: String) -> Presentation {
+        let grant = self
+            .presentation_grants
+            .get(&grant_id)
+            .unwrap_or_else(|| ContractError::PresentationGrantNotFound(grant_id.clone()).panic())
+            .clone();
+        let caller = env::predecessor_account_id();
+        if grant.grantee != caller {
+            ContractError::Unauthorized.panic();
+        }
+        if env::block_timestamp() / 1_000_000_000 >= grant.expires_at {
+            ContractError::PresentationGrantExpired(grant_id).panic();
+        }
+
+        // Removed or revoked credentials drop out of the presentation
+        let credentials = grant
+            .credential_ids
+            .iter()
+            .filter_map(|id| self.credential(id))
+            .collect();
+        events::emit_presentation_accessed(&grant_id, &caller);
+
+        Presentation { grant, credentials }
+    }
+
+    /// Get a presentation grant's metadata (not the credentials it covers).
+    pub fn get_presentation_grant(&self, grant_id: String) -> Option<PresentationGrant> {
+        self.presentation_grants.get(&grant_id).cloned()
+    }
+
+    /// List presentation grants created by a holder, including expired ones.
+    pub fn get_presentation_grants_for_holder(
+        &self,
+        holder: AccountId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<PresentationGrant> {
+        let Some(ids) = self.grants_by_holder.get(&holder) else {
+            return vec![];
+        };
+        ids.iter()
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .filter_map(|id| self.presentation_grants.get(id).cloned())
+            .collect()
+    }
+
+    // =========================================================================
+    // Rate limiting
+    // =========================================================================
+
+    /// Set the per-account `verify_proof` quota, or disable it with `None`.
+    /// Only callable by the owner.
+    pub fn set_rate_limit(&mut self, config: Option<RateLimitConfig>) {
+        self.assert_owner();
+        if let Some(cfg) = &config {
+            if cfg.max_calls == 0 || cfg.window_secs == 0 {
+                ContractError::InvalidRateLimitConfig(
+                    "max_calls and window_secs must be positive".into(),
+                )
+                .panic();
+            }
+        }
+        // Counters from the previous window length are meaningless under the
+        // new one; a new epoch voids them without touching every bucket
+        self.rate_limit_epoch = self.rate_limit_epoch.wrapping_add(1);
+        self.rate_limit = config;
+    }
+
+    /// Exempt an account from the rate limit. Callable by owner or admin.
+    pub fn add_rate_limit_exemption(&mut self, account: AccountId) {
+        self.assert_owner_or_admin();
+        self.rate_limit_exempt.insert(account);
+    }
+
+    /// Remove an account's rate-limit exemption. Callable by owner or admin.
+    pub fn remove_rate_limit_exemption(&mut self, account: AccountId) {
+        self.assert_owner_or_admin();
+        self.rate_limit_exempt.remove(&account);
+    }
+
+    /// Check if an account is exempt from the rate limit.
+    pub fn is_rate_limit_exempt(&self, account: AccountId) -> bool {
+        self.rate_limit_exempt.contains(&account)
+    }
+
+    /// Get an account's call counter for the current window, if it has one.
+    pub fn get_rate_limit_bucket(&self, account: AccountId) -> Option<RateLimitBucket> {
+        self.rate_limit_buckets
+            .get(&account)
+            .filter(|bucket| bucket.epoch == self.rate_limit_epoch)
+            .cloned()
+    }
+
+    /// Remove elapsed or superseded rate-limit buckets, scanning at most
+    /// `limit` entries. Callable by anyone; returns how many were removed.
+    pub fn prune_rate_limit_buckets(&mut self, limit: Option<u32>) -> u32 {
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        self.internal_prune_rate_limit_buckets(now_secs, limit.unwrap_or(100))
+    }
+
+    // =========================================================================
+    // Fees and treasury
+    // =========================================================================
+
+    /// Set the verification fee for a circuit type in yoctoNEAR (0 disables it).
+    /// Only callable by the owner.
+    pub fn set_verification_fee(&mut self, circuit_type: CircuitType, fee: U128) {
+        self.assert_owner();
+        let key = circuit_type.as_key();
+        if fee.0 == 0 {
+            self.verification_fees.remove(&key);
+        } else {
+            self.verification_fees.insert(key.clone(), fee.0);
+        }
+        events::emit_verification_fee_set(&key, fee.0);
+    }
+
+    /// Index credentials of a circuit type by the public signal at `signal_index`,
+    /// or stop indexing with `None`. Only credentials stored afterwards are
+    /// indexed; existing entries stay findable under the position they were
+    /// indexed at. Callable by owner or admin.
+    pub fn set_signal_index(&mut self, circuit_type: CircuitType, signal_index: Option<u32>) {
+        self.assert_owner_or_admin();
+        let key = circuit_type.as_key();
+        match signal_index {
+            Some(index) => self.signal_index_positions.insert(key.clone(), index),
+            None => self.signal_index_positions.remove(&key),
+        };
+        events::emit_signal_index_set(&key, signal_index);
+    }
+
+    /// Get the public-signal position indexed for a circuit type, if any.
+    pub fn get_signal_index(&self, circuit_type: CircuitType) -> Option<u32> {
+        self.signal_index_positions
+            .get(&circuit_type.as_key())
+            .copied()
+    }
+
+    /// Get the verification fee for a circuit type in yoctoNEAR.
+    pub fn get_verification_fee(&self, circuit_type: CircuitType) -> U128 {
+        U128(
+            self.verification_fees
+                .get(&circuit_type.as_key())
+                .copied()
+                .unwrap_or(0),
+        )
+    }
+
+    /// Set the account that receives treasury withdrawals. Only callable by the owner.
+    pub fn set_treasury_beneficiary(&mut self, beneficiary: AccountId) {
+        self.assert_owner();
+        self.treasury_beneficiary = Some(beneficiary);
+    }
+
+    /// Withdraw collected fees to the treasury beneficiary. Only callable by the owner.
+    /// Withdraws the full balance when `amount` is omitted. The balance is restored
+    /// if the transfer fails.
+    pub fn withdraw_treasury(&mut self, amount: Option<U128>) -> Promise {
+        self.assert_owner();
+        let beneficiary = self
+            .treasury_beneficiary
+            .clone()
+            .unwrap_or_else(|| ContractError::BeneficiaryNotSet.panic());
+        let amount = amount.map(|a| a.0).unwrap_or(self.treasury_balance);
+        if amount == 0 || amount > self.treasury_balance {
+            ContractError::InsufficientTreasury {
+                requested: amount,
+                available: self.treasury_balance,
+            }
+            .panic();
+        }
+
+        self.treasury_balance -= amount;
+        Promise::new(beneficiary.clone())
+            .transfer(NearToken::from_yoctonear(amount))
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas::from_tgas(5))
+                    .on_treasury_withdrawn(beneficiary, U128(amount)),
+            )
+    }
+
+    /// Callback for `withdraw_treasury`: re-credit the balance if the transfer failed.
+    #[private]
+    pub fn on_treasury_withdrawn(&mut self, beneficiary: AccountId, amount: U128) -> bool {
+        let success = is_promise_success();
+        if !success {
+            self.treasury_balance += amount.0;
+        }
+        events::emit_treasury_withdrawn(&beneficiary, amount.0, success);
+        success
+    }
+
+    // =========================================================================
+    // Multisig
+    // =========================================================================
+
+    /// Enable multisig approval for sensitive admin actions.
+    /// Only callable by the owner while multisig is disabled; once enabled,
+    /// the approver set can only change through a `SetMultisig` proposal.
+    pub fn set_multisig(&mut self, config: MultisigConfig) {
+        self.assert_owner();
+        self.assert_not_multisig_guarded("set_multisig");
+        self.internal_set_multisig(Some(config));
+    }
+
+    /// Create a proposal for a guarded admin action. Only callable by approvers.
+    /// The action is checked up front the same way execution checks it, so an
+    /// action that cannot run is rejected rather than left to collect approvals.
+    /// The proposer's approval is counted; with a threshold of 1 the action
+    /// executes immediately. Returns the proposal ID.
+    pub fn propose_action(&mut self, action: AdminAction) -> u64 {
+        let config = self.multisig.clone().unwrap_or_else(|| {
+            ContractError::InvalidMultisigConfig("multisig is not enabled".into()).panic()
+        });
+        let caller = env::predecessor_account_id();
+        if !config.approvers.contains(&caller) {
+            ContractError::Unauthorized.panic();
+        }
+        self.validate_admin_action(&action);
+
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        storage::prune_expired_proposals(
+            &mut self.proposals,
+            &mut self.proposal_prune_cursor,
+            now_secs,
+            storage::PROPOSAL_PRUNE_SCAN,
+        );
+
+        self.proposal_nonce += 1;
+        let id = self.proposal_nonce;
+        let action_name = action.as_str();
+        let proposal = Proposal {
+            id,
+            action,
+            proposer: caller.clone(),
+            approvals: vec![caller.clone()],
+            created_at: now_secs,
+            expires_at: now_secs + config.proposal_ttl_secs,
+        };
+        self.proposals.insert(id, proposal);
+        events::emit_proposal_created(id, action_name, &caller);
+
+        if config.threshold <= 1 {
+            self.execute_proposal(id);
+        }
+        id
+    }
+
+    /// Approve a pending proposal. Only callable by approvers.
+    /// Executes the action once the threshold is reached and returns whether it ran.
+    pub fn approve_proposal(&mut self, proposal_id: u64) -> bool {
+        let config = self.multisig.clone().unwrap_or_else(|| {
+            ContractError::InvalidMultisigConfig("multisig is not enabled".into()).panic()
+        });
+        let caller = env::predecessor_account_id();
+        if !config.approvers.contains(&caller) {
+            ContractError::Unauthorized.panic();
+        }
+
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .unwrap_or_else(|| ContractError::ProposalNotFound(proposal_id).panic());
+        if proposal.expires_at <= now_secs {
+            ContractError::ProposalExpired(proposal_id).panic();
+        }
+        if proposal.approvals.contains(&caller) {
+            ContractError::AlreadyApproved(proposal_id).panic();
+        }
+        proposal.approvals.push(caller.clone());
+        let approvals = proposal.approvals.len() as u32;
+        events::emit_proposal_approved(proposal_id, proposal.action.as_str(), &caller, approvals);
+
+        if approvals >= config.threshold {
+            self.execute_proposal(proposal_id);
+            return true;
+        }
+        false
+    }
+
+    /// Remove expired proposals, scanning at most `limit` entries.
+    /// Callable by anyone; returns how many were removed.
+    pub fn prune_expired_proposals(&mut self, limit: Option<u32>) -> u32 {
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        storage::prune_expired_proposals(
+            &mut self.proposals,
+            &mut self.proposal_prune_cursor,
+            now_secs,
+            limit.unwrap_or(100),
+        )
+    }
+
+    /// Get the multisig configuration, if enabled.
+    pub fn get_multisig_config(&self) -> Option<MultisigConfig> {
+        self.multisig.clone()
+    }
+
+    /// Get a pending proposal by ID.
+    pub fn get_proposal(&self, proposal_id: u64) -> Option<Proposal> {
+        self.proposals.get(&proposal_id).cloned()
+    }
+
+    /// List pending (unexpired) proposals with pagination.
+    pub fn get_pending_proposals(&self, offset: Option<u32>, limit: Option<u32>) -> Vec<Proposal> {
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        self.proposals
+            .values()
+            .filter(|p| p.expires_at > now_secs)
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // =========================================================================
+    // View methods
+    // =========================================================================
+
+    /// Get the contract configuration.
+    pub fn get_config(&self) -> ContractConfig {
+        ContractConfig {
+            owner: self.owner.clone(),
+            proposed_owner: self.proposed_owner.clone(),
+            is_paused: self.is_paused,
+            default_expiration_secs: self.default_expiration_secs,
+            storage_cost_per_credential: self.storage_cost_per_credential,
+            treasury_beneficiary: self.treasury_beneficiary.clone(),
+            rate_limit: self.rate_limit.clone(),
+            paused_circuits: self.paused_circuits.clone(),
+            paused_methods: self.paused_methods.clone(),
+        }
+    }
+
+    /// Get contract statistics.
+    pub fn get_stats(&self) -> ContractStats {
+        ContractStats {
+            total_verifications: self.total_verifications,
+            total_credentials: self.total_credentials,
+            is_paused: self.is_paused,
+            verification_keys_registered: self.vk_count,
+            total_fees_collected: U128(self.total_fees_collected),
+            treasury_balance: U128(self.treasury_balance),
+            stats_epoch: self.stats_epoch,
+            total_private_credentials: self.total_private_credentials,
+        }
+    }
+
+    /// Get the current verification key version for a circuit type (0 if never set).
+    /// Each `set_verification_key` bumps the version; credentials record the
+    /// version they were verified against.
+    pub fn get_verification_key_version(&self, circuit_type: CircuitType) -> u32 {
+        self.vk_versions
+            .get(&circuit_type.as_key())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Get verification metrics for a circuit type. Defaults to the current
+    /// reporting epoch; past epochs stay queryable after a reset.
+    pub fn get_circuit_stats(&self, circuit_type: CircuitType, epoch: Option<u32>) -> CircuitStats {
+        let epoch = epoch.unwrap_or(self.stats_epoch);
+        let metrics = self
+            .circuit_metrics
+            .get(&(circuit_type.as_key(), epoch))
+            .cloned()
+            .unwrap_or_default();
+        let count = metrics.valid_proofs + metrics.invalid_proofs;
+        CircuitStats {
+            circuit_type,
+            epoch,
+            epoch_started_at: self.stats_epoch_starts.get(&epoch).copied().unwrap_or(0),
+            valid_proofs: metrics.valid_proofs,
+            invalid_proofs: metrics.invalid_proofs,
+            credentials_issued: metrics.credentials_issued,
+            revocations: metrics.revocations,
+            min_gas_used: metrics.min_gas_used,
+            avg_gas_used: if count == 0 {
+                0
+            } else {
+                (metrics.total_gas_used / count as u128) as u64
+            },
+            max_gas_used: metrics.max_gas_used,
+        }
+    }
+
+    /// Start a new per-circuit reporting epoch. Counters restart from zero;
+    /// earlier epochs remain readable via `get_circuit_stats`. Only callable by the owner.
+    pub fn reset_circuit_stats(&mut self) -> u32 {
+        self.assert_owner();
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        self.stats_epoch += 1;
+        self.stats_epoch_starts.insert(self.stats_epoch, now_secs);
+        events::emit_stats_epoch_started(self.stats_epoch, now_secs);
+        self.stats_epoch
+    }
+
+    /// Check if a verification key is registered for a circuit type.
+    pub fn has_verification_key(&self, circuit_type: CircuitType) -> bool {
+        self.verification_keys.get(&circuit_type.as_key()).is_some()
+    }
+
+    /// Get the required storage deposit for storing a credential.
+    pub fn get_storage_cost(&self) -> String {
+        self.storage_cost_per_credential.to_string()
+    }
+
+    // =========================================================================
+    // Internal helpers
+    // =========================================================================
+
+    fn assert_owner(&self) {
+        if env::predecessor_account_id() != self.owner {
+            ContractError::Unauthorized.panic();
+        }
+    }
+
+    fn assert_owner_or_admin(&self) {
+        let caller = env::predecessor_account_id();
+        if caller != self.owner && !self.admins.contains(&caller) {
+            ContractError::Unauthorized.panic();
+        }
+    }
+
+    fn assert_not_paused(&self) {
+        if self.is_paused {
+            ContractError::ContractPaused.panic();
+        }
+    }
+
+    /// Count the caller against the rate limit and lazily prune a few stale buckets.
+    fn apply_rate_limit(&mut self, caller: &AccountId) {
+        let Some(cfg) = self.rate_limit.clone() else {
+            return;
+        };
+        if self.rate_limit_exempt.contains(caller) {
+            return;
+        }
+        let now_secs = env::block_timestamp() / 1_000_000_000;
+        self.internal_prune_rate_limit_buckets(now_secs, storage::RATE_LIMIT_PRUNE_SCAN);
+        storage::consume_rate_limit(
+            &mut self.rate_limit_buckets,
+            &cfg,
+            self.rate_limit_epoch,
+            caller,
+            now_secs,
+        );
+    }
+
+    /// Prune stale buckets, scanning at most `max_scan` entries. With no rate
+    /// limit every bucket is stale.
+    fn internal_prune_rate_limit_buckets(&mut self, now_secs: u64, max_scan: u32) -> u32 {
+        let window_secs = self.rate_limit.as_ref().map_or(0, |cfg| cfg.window_secs);
+        storage::prune_rate_limit_buckets(
+            &mut self.rate_limit_buckets,
+            &mut self.rate_limit_prune_cursor,
+            self.rate_limit_epoch,
+            window_secs,
+            now_secs,
+            max_scan,
+        )
+    }
+
+    /// Global pause first (it overrides everything), then the method group flag.
+    fn assert_method_enabled(&self, method: MethodGroup) {
         self.assert_not_paused();
+        if self.paused_methods.contains(&method) {
+            ContractError::MethodPaused(method.as_str().to_string()).panic();
+        }
+    }
+
+    fn assert_circuit_enabled(&self, circuit_type: &CircuitType) {
+        if self.paused_circuits.contains(circuit_type) {
+            ContractError::CircuitPaused(circuit_type.as_key()).panic();
+        }
+    }
+
+    /// Metrics for a circuit in the current reporting epoch, created on first use.
+    fn circuit_metrics_mut(&mut self, circuit_key: &str) -> &mut CircuitMetrics {
+        self.circuit_metrics
+            .entry((circuit_key.to_string(), self.stats_epoch))
+            .or_default()
+    }
+
+    fn assert_not_multisig_guarded(&self, action: &str) {
+        if self.multisig.is_some() {
+            ContractError::MultisigRequired(action.to_string()).panic();
+        }
+    }
 
-        // Basic validation: IC must have at least 1 element
-        if vk.ic.is_empty() {
-            ContractError::InvalidVerificationKey("IC array must not be empty".into()).panic();
-        }
+    fn internal_set_verification_key(&mut self, circuit_type: CircuitType, vk: VerificationKey) {
+        self.assert_not_paused();
+        storage::validate_verification_key(&vk);
 
         let key = circuit_type.as_key();
         let is_new = self.verification_keys.get(&key).is_none();
-        self.verification_keys.insert(key, vk);
+        self.verification_keys.insert(key.clone(), vk);
+        let version = self.vk_versions.get(&key).copied().unwrap_or(0) + 1;
+        self.vk_versions.insert(key, version);
 
         let updated = !is_new;
         if is_new {
@@ -115,72 +1453,47 @@ impl ZKVerifier {
         events::emit_verification_key_set(&circuit_type.as_key(), updated);
     }
 
-    /// Pause or unpause the contract.
-    pub fn set_paused(&mut self, paused: bool) {
-        self.assert_owner();
+    fn internal_set_paused(&mut self, paused: bool) {
         self.is_paused = paused;
         events::emit_contract_paused(paused);
     }
 
-    /// Propose ownership transfer to a new account (step 1 of 2).
-    /// Only callable by the current owner.
-    pub fn propose_owner(&mut self, new_owner: AccountId) {
-        self.assert_owner();
-        events::emit_ownership_proposed(&self.owner, &new_owner);
-        self.proposed_owner = Some(new_owner);
-    }
-
-    /// Accept ownership transfer (step 2 of 2).
-    /// Only callable by the proposed new owner.
-    pub fn accept_ownership(&mut self) {
-        let caller = env::predecessor_account_id();
-        let proposed = self.proposed_owner.as_ref().unwrap_or_else(|| {
-            ContractError::Unauthorized.panic()
-        });
-        if &caller != proposed {
-            ContractError::Unauthorized.panic();
+    fn internal_set_circuit_paused(&mut self, circuit_type: CircuitType, paused: bool) {
+        let key = circuit_type.as_key();
+        self.paused_circuits.retain(|c| c != &circuit_type);
+        if paused {
+            self.paused_circuits.push(circuit_type);
         }
-        let old_owner = self.owner.clone();
-        self.owner = caller;
-        self.proposed_owner = None;
-        events::emit_ownership_transferred(&old_owner, &self.owner);
-    }
-
-    /// Add an admin account. Only callable by the owner.
-    pub fn add_admin(&mut self, account: AccountId) {
-        self.assert_owner();
-        self.admins.insert(account.clone());
-        events::emit_admin_added(&account);
-    }
-
-    /// Remove an admin account. Only callable by the owner.
-    pub fn remove_admin(&mut self, account: AccountId) {
-        self.assert_owner();
-        self.admins.remove(&account);
-        events::emit_admin_removed(&account);
+        events::emit_circuit_paused(&key, paused);
     }
 
-    /// Check if an account is an admin.
-    pub fn is_admin(&self, account: AccountId) -> bool {
-        self.admins.contains(&account)
+    fn internal_set_method_paused(&mut self, method: MethodGroup, paused: bool) {
+        self.paused_methods.retain(|m| m != &method);
+        if paused {
+            self.paused_methods.push(method);
+        }
+        events::emit_method_paused(method.as_str(), paused);
     }
 
-    /// Update the default credential expiration period.
-    pub fn set_default_expiration(&mut self, seconds: u64) {
-        self.assert_owner();
-        self.default_expiration_secs = seconds;
+    fn internal_propose_owner(&mut self, new_owner: AccountId) {
+        events::emit_ownership_proposed(&self.owner, &new_owner);
+        self.proposed_owner = Some(new_owner);
     }
 
-    // =========================================================================
-    // Verification methods
-    // =========================================================================
-
-    /// Verify a Groth16 proof on-chain.
-    /// Optionally stores a credential if `store_credential` is true in the input.
-    /// When storing, the caller must attach sufficient deposit.
-    #[payable]
-    pub fn verify_proof(&mut self, input: VerifyProofInput) -> VerificationResult {
-        self.assert_not_paused();
+    /// Verify a proof for `caller`, who owns any credential stored (or issues
+    /// it with `issue_to`). The attached deposit comes from, and any refund
+    /// goes back to, the predecessor.
+    fn internal_verify_proof(
+        &mut self,
+        input: VerifyProofInput,
+        caller: AccountId,
+    ) -> VerificationResult {
+        self.assert_method_enabled(MethodGroup::Verify);
+        if input.store_credential {
+            self.assert_method_enabled(MethodGroup::Store);
+        }
+        self.assert_circuit_enabled(&input.circuit_type);
+        self.apply_rate_limit(&caller);
 
         let gas_before = env::used_gas().as_gas();
 
@@ -189,259 +1502,462 @@ impl ZKVerifier {
         let vk = self
             .verification_keys
             .get(&key)
-            .unwrap_or_else(|| {
-                ContractError::VerificationKeyNotFound(key.clone()).panic()
-            })
+            .unwrap_or_else(|| ContractError::VerificationKeyNotFound(key.clone()).panic())
             .clone();
 
+        // The verification fee is charged whether or not the proof is valid
+        let deposit = env::attached_deposit().as_yoctonear();
+        let fee = self.verification_fees.get(&key).copied().unwrap_or(0);
+        if deposit < fee {
+            ContractError::InsufficientDeposit {
+                required: fee,
+                attached: deposit,
+            }
+            .panic();
+        }
+
         // Run Groth16 verification
-        let is_valid = match verifier::verify_groth16_proof(
-            &vk,
-            &input.proof,
-            &input.public_signals,
-        ) {
-            Ok(valid) => valid,
-            Err(_) => false,
-        };
+        let is_valid =
+            match verifier::verify_groth16_proof(&vk, &input.proof, &input.public_signals) {
+                Ok(valid) => valid,
+                Err(_) => false,
+            };
 
         self.total_verifications += 1;
+        self.treasury_balance += fee;
+        self.total_fees_collected += fee;
+        let mut charged = fee;
 
         let mut credential_id = None;
+        let mut pending = false;
 
         // Store credential if requested and proof is valid
         if is_valid && input.store_credential {
-            let deposit = env::attached_deposit();
-            if deposit.as_yoctonear() < self.storage_cost_per_credential {
+            let required = fee + self.storage_cost_per_credential;
+            if deposit < required {
                 ContractError::InsufficientDeposit {
-                    required: self.storage_cost_per_credential,
-                    attached: deposit.as_yoctonear(),
+                    required,
+                    attached: deposit,
                 }
                 .panic();
             }
+            charged = required;
 
-            let caller = env::predecessor_account_id();
+            let holder = input.issue_to.clone().unwrap_or_else(|| caller.clone());
             let now_secs = env::block_timestamp() / 1_000_000_000;
             let expiry = input
                 .custom_expiration
                 .unwrap_or(self.default_expiration_secs);
 
-            self.credential_nonce += 1;
-            let circuit_key = input.circuit_type.as_key();
-            let id = storage::generate_credential_id(&caller, &input.circuit_type, self.credential_nonce);
-            let expires_at = now_secs + expiry;
-            let cred_owner = caller.clone();
-            let credential = Credential {
-                id: id.clone(),
-                owner: caller,
-                circuit_type: input.circuit_type,
-                public_signals: input.public_signals,
-                verified_at: now_secs,
-                expires_at,
-                claim: input.claim,
-            };
-
-            storage::store_credential(
-                &mut self.credentials,
-                &mut self.credentials_by_owner,
-                credential,
-            );
+            if let Some(privacy) = input.privacy {
+                if holder != caller {
+                    ContractError::InvalidPrivacyOptions(
+                        "issue_to is not supported in privacy mode".to_string(),
+                    )
+                    .panic();
+                }
+                let id = self.store_private(
+                    &caller,
+                    input.circuit_type,
+                    &input.public_signals,
+                    &privacy,
+                    now_secs,
+                    now_secs + expiry,
+                );
+                credential_id = Some(id);
+            } else {
+                self.credential_nonce += 1;
+                let circuit_key = input.circuit_type.as_key();
+                let id = storage::generate_credential_id(
+                    &holder,
+                    &input.circuit_type,
+                    self.credential_nonce,
+                );
+                let credential = Credential {
+                    id: id.clone(),
+                    owner: holder.clone(),
+                    circuit_type: input.circuit_type,
+                    public_signals: input.public_signals,
+                    verified_at: now_secs,
+                    expires_at: now_secs + expiry,
+                    claim: input.claim,
+                    vk_version: self.vk_versions.get(&circuit_key).copied().unwrap_or(0),
+                    issue_seq: self.credential_nonce,
+                    circuit_seq: 0,
+                    issued_by: (holder != caller).then(|| caller.clone()),
+                };
+
+                // Issuing to someone else needs their consent unless they auto-accept from the caller
+                if holder == caller || self.auto_accept.contains(&(holder.clone(), caller.clone()))
+                {
+                    self.insert_credential(credential);
+                } else {
+                    storage::store_pending_credential(
+                        &mut self.pending_credentials,
+                        &mut self.pending_by_holder,
+                        PendingCredential {
+                            credential,
+                            escrow: U128(self.storage_cost_per_credential),
+                            created_at: now_secs,
+                        },
+                    );
+                    pending = true;
+                    events::emit_credential_pending(&id, &holder, &caller, &circuit_key);
+                }
+                credential_id = Some(id);
+            }
+        }
 
-            self.total_credentials += 1;
-            credential_id = Some(id.clone());
+        events::emit_proof_verified(&key, is_valid, credential_id.as_deref());
 
-            events::emit_credential_stored(&id, &cred_owner, &circuit_key, expires_at);
+        // Refund anything attached beyond the fee and storage actually used
+        let refund = deposit - charged;
+        if refund > 0 {
+            let _ = Promise::new(env::predecessor_account_id())
+                .transfer(NearToken::from_yoctonear(refund));
         }
 
-        events::emit_proof_verified(
-            &key,
-            is_valid,
-            credential_id.as_deref(),
-        );
-
         let gas_after = env::used_gas().as_gas();
+        let gas_used = gas_after.saturating_sub(gas_before);
+        self.circuit_metrics_mut(&key)
+            .record_verification(is_valid, gas_used);
 
         VerificationResult {
             valid: is_valid,
             credential_id,
-            gas_used: gas_after.saturating_sub(gas_before),
+            pending,
+            gas_used,
         }
     }
 
-    /// View-only verification — does not store anything.
-    pub fn verify_proof_view(&self, input: VerifyProofInput) -> VerificationResult {
-        self.assert_not_paused();
-
-        let gas_before = env::used_gas().as_gas();
-
-        let key = input.circuit_type.as_key();
-        let vk = self
-            .verification_keys
-            .get(&key)
-            .unwrap_or_else(|| {
-                ContractError::VerificationKeyNotFound(key).panic()
-            })
-            .clone();
-
-        let is_valid = match verifier::verify_groth16_proof(
-            &vk,
-            &input.proof,
-            &input.public_signals,
-        ) {
-            Ok(valid) => valid,
-            Err(_) => false,
-        };
-
-        let gas_after = env::used_gas().as_gas();
+    /// Check a relay intent against the call and consume its nonce, or panic.
+    fn consume_relay_intent(
+        &mut self,
+        input: &VerifyProofInput,
+        intent: &RelayIntent,
+        public_key: &PublicKey,
+        signature: &[u8],
+    ) {
+        if intent.verifier != env::current_account_id() {
+            ContractError::InvalidIntent("intent is for another verifier".to_string()).panic();
+        }
+        if intent.circuit_type != input.circuit_type {
+            ContractError::InvalidIntent("circuit type does not match input".to_string()).panic();
+        }
+        if intent.proof_hash != relay::proof_hash(input) {
+            ContractError::InvalidIntent("proof hash does not match input".to_string()).panic();
+        }
+        if input
+            .issue_to
+            .as_ref()
+            .is_some_and(|to| *to != intent.holder)
+        {
+            ContractError::InvalidIntent("relayed credentials are bound to the holder".to_string())
+                .panic();
+        }
+        if env::block_timestamp() / 1_000_000_000 > intent.expires_at {
+            ContractError::IntentExpired.panic();
+        }
+        if !self.is_relay_key(intent.holder.clone(), public_key.clone()) {
+            ContractError::RelayKeyNotAuthorized(intent.holder.to_string()).panic();
+        }
+        if !relay::verify_intent_signature(intent, public_key, signature) {
+            ContractError::InvalidIntentSignature.panic();
+        }
 
-        VerificationResult {
-            valid: is_valid,
-            credential_id: None,
-            gas_used: gas_after.saturating_sub(gas_before),
+        let last_used = self.get_relay_nonce(intent.holder.clone());
+        if intent.nonce <= last_used {
+            ContractError::StaleNonce {
+                nonce: intent.nonce,
+                last_used,
+            }
+            .panic();
         }
+        self.relay_nonces
+            .insert(intent.holder.clone(), intent.nonce);
     }
 
-    // =========================================================================
-    // Credential queries
-    // =========================================================================
-
-    /// Get a credential by ID.
-    pub fn get_credential(&self, credential_id: String) -> Option<Credential> {
-        self.credentials.get(&credential_id).cloned()
+    /// Get a credential by ID, converting a record stored before state version 2.
+    fn credential(&self, credential_id: &str) -> Option<Credential> {
+        self.credentials.get(credential_id).cloned().or_else(|| {
+            self.legacy_credentials
+                .get(credential_id)
+                .cloned()
+                .map(CredentialV1::upgrade)
+        })
     }
 
-    /// Check if a credential exists, is not expired, and is not revoked.
-    pub fn is_credential_valid(&self, credential_id: String) -> Option<bool> {
-        if self.revoked_credentials.contains(&credential_id) {
-            return Some(false);
+    /// Move a pre-v2 credential record into the current credentials map.
+    /// Legacy records are not in the issue-time indexes and stay out of them.
+    fn upgrade_legacy_credential(&mut self, credential_id: &str) {
+        if let Some(old) = self.legacy_credentials.remove(credential_id) {
+            self.credentials
+                .insert(credential_id.to_string(), old.upgrade());
         }
-        storage::is_credential_valid(&self.credentials, &credential_id)
     }
 
-    /// Get credentials for an owner account with pagination.
-    pub fn get_credentials_by_owner(
-        &self,
-        owner: AccountId,
-        include_expired: Option<bool>,
-        offset: Option<u32>,
-        limit: Option<u32>,
-    ) -> PaginatedCredentials {
-        let (credentials, total) = storage::get_credentials_by_owner(
-            &self.credentials,
-            &self.credentials_by_owner,
-            &owner,
-            include_expired.unwrap_or(false),
-            offset.unwrap_or(0),
-            limit.unwrap_or(50),
-        );
-        let has_more = (offset.unwrap_or(0) + credentials.len() as u32) < total;
-        PaginatedCredentials {
-            credentials,
-            total,
-            has_more,
+    /// Store a credential and add it to every index. `issue_seq` must already
+    /// be set; the per-circuit sequence is assigned here and returned.
+    fn insert_credential(&mut self, mut credential: Credential) -> u64 {
+        let id = credential.id.clone();
+        let owner = credential.owner.clone();
+        let circuit_key = credential.circuit_type.as_key();
+        let expires_at = credential.expires_at;
+        let issue_seq = credential.issue_seq;
+        let circuit_seq = self
+            .circuit_issue_counts
+            .get(&circuit_key)
+            .copied()
+            .unwrap_or(0)
+            + 1;
+        credential.circuit_seq = circuit_seq;
+
+        if let Some(&position) = self.signal_index_positions.get(&circuit_key) {
+            if let Some(value) = credential.public_signals.get(position as usize) {
+                storage::index_signal(
+                    &mut self.credentials_by_signal,
+                    (circuit_key.clone(), position, value.clone()),
+                    id.clone(),
+                );
+                self.credential_signal_positions
+                    .insert(id.clone(), position);
+            }
         }
-    }
 
-    /// Remove a credential. Only the credential owner can do this.
-    pub fn remove_credential(&mut self, credential_id: String) -> bool {
-        self.assert_not_paused();
-        let caller = env::predecessor_account_id();
-        let removed = storage::remove_credential(
+        storage::store_credential(
             &mut self.credentials,
             &mut self.credentials_by_owner,
-            &credential_id,
-            &caller,
+            credential,
         );
-        if removed {
-            self.total_credentials = self.total_credentials.saturating_sub(1);
-            events::emit_credential_removed(&credential_id, &caller);
-        }
-        removed
-    }
+        self.credentials_by_issue.insert(issue_seq, id.clone());
+        self.credentials_by_circuit
+            .insert((circuit_key.clone(), circuit_seq), id.clone());
+        self.circuit_issue_counts
+            .insert(circuit_key.clone(), circuit_seq);
 
-    /// Revoke a credential. Only callable by owner or admin.
-    /// Removes the credential data and marks the ID as permanently revoked.
-    pub fn revoke_credential(&mut self, credential_id: String, reason: String) {
-        self.assert_owner_or_admin();
-        self.assert_not_paused();
+        self.total_credentials += 1;
+        self.circuit_metrics_mut(&circuit_key).credentials_issued += 1;
 
-        // Remove from storage if it exists
-        if let Some(cred) = self.credentials.get(&credential_id) {
-            let cred_owner = cred.owner.clone();
-            self.credentials.remove(&credential_id);
-            if let Some(owner_set) = self.credentials_by_owner.get_mut(&cred_owner) {
-                owner_set.remove(&credential_id);
-            }
-            self.total_credentials = self.total_credentials.saturating_sub(1);
-        }
+        events::emit_credential_stored(&id, &owner, &circuit_key, expires_at);
+        circuit_seq
+    }
 
-        // Mark as revoked (tombstone)
-        self.revoked_credentials.insert(credential_id.clone());
+    /// Store a privacy-mode credential for `owner` and return its ID.
+    fn store_private(
+        &mut self,
+        owner: &AccountId,
+        circuit_type: CircuitType,
+        public_signals: &[String],
+        options: &PrivacyOptions,
+        verified_at: u64,
+        expires_at: u64,
+    ) -> String {
+        let owner_hash = privacy::owner_hash(owner, &privacy::parse_salt(&options.owner_salt));
+        let signals_commitment = privacy::signals_commitment(
+            public_signals,
+            &privacy::parse_salt(&options.signals_salt),
+        );
 
-        let caller = env::predecessor_account_id();
-        events::emit_credential_revoked(&credential_id, &caller, &reason);
+        // Private credentials stay out of the issue-time indexes, which list plain credentials
+        self.credential_nonce += 1;
+        let circuit_key = circuit_type.as_key();
+        let id = privacy::generate_private_credential_id(
+            &owner_hash,
+            &circuit_type,
+            self.credential_nonce,
+        );
+        storage::store_private_credential(
+            &mut self.private_credentials,
+            &mut self.private_by_owner,
+            PrivateCredential {
+                id: id.clone(),
+                owner_hash: owner_hash.clone(),
+                circuit_type,
+                signals_commitment,
+                verified_at,
+                expires_at,
+                vk_version: self.vk_versions.get(&circuit_key).copied().unwrap_or(0),
+            },
+        );
+        self.total_private_credentials += 1;
+        self.circuit_metrics_mut(&circuit_key).credentials_issued += 1;
+
+        events::emit_private_credential_stored(&id, &owner_hash, &circuit_key, expires_at);
+        id
     }
 
-    /// Check if a credential has been revoked.
-    pub fn is_credential_revoked(&self, credential_id: String) -> bool {
-        self.revoked_credentials.contains(&credential_id)
+    /// Remove a privacy-mode credential. Returns it if it existed.
+    fn delete_private_credential(&mut self, credential_id: &str) -> Option<PrivateCredential> {
+        let cred = storage::delete_private_credential(
+            &mut self.private_credentials,
+            &mut self.private_by_owner,
+            credential_id,
+        )?;
+        self.total_private_credentials -= 1;
+        Some(cred)
     }
 
-    // =========================================================================
-    // View methods
-    // =========================================================================
+    /// Remove a pending credential addressed to the caller, or panic.
+    fn take_pending_for_holder(&mut self, credential_id: &str) -> PendingCredential {
+        let caller = env::predecessor_account_id();
+        let holder = self
+            .pending_credentials
+            .get(credential_id)
+            .unwrap_or_else(|| {
+                ContractError::PendingCredentialNotFound(credential_id.to_string()).panic()
+            })
+            .credential
+            .owner
+            .clone();
+        if holder != caller {
+            ContractError::Unauthorized.panic();
+        }
+        storage::remove_pending_credential(
+            &mut self.pending_credentials,
+            &mut self.pending_by_holder,
+            credential_id,
+        )
+        .unwrap()
+    }
 
-    /// Get the contract configuration.
-    pub fn get_config(&self) -> ContractConfig {
-        ContractConfig {
-            owner: self.owner.clone(),
-            proposed_owner: self.proposed_owner.clone(),
-            is_paused: self.is_paused,
-            default_expiration_secs: self.default_expiration_secs,
-            storage_cost_per_credential: self.storage_cost_per_credential,
+    fn refund_pending_escrow(&self, pending: &PendingCredential) {
+        if let Some(issuer) = &pending.credential.issued_by {
+            if pending.escrow.0 > 0 {
+                let _ = Promise::new(issuer.clone())
+                    .transfer(NearToken::from_yoctonear(pending.escrow.0));
+            }
         }
     }
 
-    /// Get contract statistics.
-    pub fn get_stats(&self) -> ContractStats {
-        ContractStats {
-            total_verifications: self.total_verifications,
-            total_credentials: self.total_credentials,
-            is_paused: self.is_paused,
-            verification_keys_registered: self.vk_count,
+    /// Write out a newly stored grant and its holder index entry, so that
+    /// `env::storage_usage` counts them.
+    fn flush_presentation_grant(&mut self, holder: &AccountId) {
+        self.presentation_grants.flush();
+        if let Some(ids) = self.grants_by_holder.get_mut(holder) {
+            ids.flush();
         }
+        self.grants_by_holder.flush();
     }
 
-    /// Check if a verification key is registered for a circuit type.
-    pub fn has_verification_key(&self, circuit_type: CircuitType) -> bool {
-        self.verification_keys.get(&circuit_type.as_key()).is_some()
+    /// Remove a credential from storage and every index. Returns true if it existed.
+    fn delete_credential(&mut self, credential_id: &str) -> bool {
+        self.upgrade_legacy_credential(credential_id);
+        let Some(cred) = storage::delete_credential(
+            &mut self.credentials,
+            &mut self.credentials_by_owner,
+            credential_id,
+        ) else {
+            return false;
+        };
+        self.credentials_by_issue.remove(&cred.issue_seq);
+        self.credentials_by_circuit
+            .remove(&(cred.circuit_type.as_key(), cred.circuit_seq));
+        if let Some(position) = self.credential_signal_positions.remove(credential_id) {
+            if let Some(value) = cred.public_signals.get(position as usize) {
+                let key = (cred.circuit_type.as_key(), position, value.clone());
+                storage::unindex_signal(&mut self.credentials_by_signal, &key, credential_id);
+            }
+        }
+        self.total_credentials = self.total_credentials.saturating_sub(1);
+        true
     }
 
-    /// Get the required storage deposit for storing a credential.
-    pub fn get_storage_cost(&self) -> String {
-        self.storage_cost_per_credential.to_string()
+    fn internal_revoke_credential(&mut self, credential_id: &str, reason: &str) {
+        // Remove from storage if it exists
+        if let Some(circuit_key) = self
+            .credential(credential_id)
+            .map(|cred| cred.circuit_type.as_key())
+        {
+            self.delete_credential(credential_id);
+            self.circuit_metrics_mut(&circuit_key).revocations += 1;
+        } else if let Some(cred) = self.delete_private_credential(credential_id) {
+            self.circuit_metrics_mut(&cred.circuit_type.as_key())
+                .revocations += 1;
+        } else if let Some(pending) = storage::remove_pending_credential(
+            &mut self.pending_credentials,
+            &mut self.pending_by_holder,
+            credential_id,
+        ) {
+            // Never issued, so not counted as a revocation; the issuer gets the escrow back
+            self.refund_pending_escrow(&pending);
+        }
+
+        // Mark as revoked (tombstone)
+        self.revoked_credentials.insert(credential_id.to_string());
+
+        let caller = env::predecessor_account_id();
+        events::emit_credential_revoked(credential_id, &caller, reason);
     }
 
-    // =========================================================================
-    // Internal helpers
-    // =========================================================================
+    fn internal_set_multisig(&mut self, config: Option<MultisigConfig>) {
+        if let Some(cfg) = &config {
+            storage::validate_multisig_config(cfg);
+        }
+        // Approvals collected under the old approver set no longer apply
+        self.proposals.clear();
 
-    fn assert_owner(&self) {
-        if env::predecessor_account_id() != self.owner {
-            ContractError::Unauthorized.panic();
+        match &config {
+            Some(cfg) => events::emit_multisig_updated(&cfg.approvers, cfg.threshold),
+            None => events::emit_multisig_updated(&[], 0),
         }
+        self.multisig = config;
     }
 
-    fn assert_owner_or_admin(&self) {
-        let caller = env::predecessor_account_id();
-        if caller != self.owner && !self.admins.contains(&caller) {
-            ContractError::Unauthorized.panic();
+    /// Run the checks an action's execution would fail on, without changing state.
+    fn validate_admin_action(&self, action: &AdminAction) {
+        match action {
+            AdminAction::SetVerificationKey { vk, .. } => {
+                self.assert_not_paused();
+                storage::validate_verification_key(vk);
+            }
+            AdminAction::RevokeCredentials { .. } => {
+                self.assert_method_enabled(MethodGroup::Revoke);
+            }
+            AdminAction::SetMultisig { config: Some(cfg) } => {
+                storage::validate_multisig_config(cfg);
+            }
+            AdminAction::SetPaused { .. }
+            | AdminAction::ProposeOwner { .. }
+            | AdminAction::SetCircuitPaused { .. }
+            | AdminAction::SetMethodPaused { .. }
+            | AdminAction::SetMultisig { config: None } => {}
         }
     }
 
-    fn assert_not_paused(&self) {
-        if self.is_paused {
-            ContractError::ContractPaused.panic();
+    /// Remove a proposal and run its action.
+    fn execute_proposal(&mut self, proposal_id: u64) {
+        let proposal = self
+            .proposals
+            .remove(&proposal_id)
+            .unwrap_or_else(|| ContractError::ProposalNotFound(proposal_id).panic());
+        let action_name = proposal.action.as_str();
+
+        match proposal.action {
+            AdminAction::SetVerificationKey { circuit_type, vk } => {
+                self.internal_set_verification_key(circuit_type, vk);
+            }
+            AdminAction::SetPaused { paused } => self.internal_set_paused(paused),
+            AdminAction::ProposeOwner { new_owner } => self.internal_propose_owner(new_owner),
+            AdminAction::RevokeCredentials {
+                credential_ids,
+                reason,
+            } => {
+                self.assert_method_enabled(MethodGroup::Revoke);
+                for credential_id in &credential_ids {
+                    self.internal_revoke_credential(credential_id, &reason);
+                }
+            }
+            AdminAction::SetCircuitPaused {
+                circuit_type,
+                paused,
+            } => self.internal_set_circuit_paused(circuit_type, paused),
+            AdminAction::SetMethodPaused { method, paused } => {
+                self.internal_set_method_paused(method, paused)
+            }
+            AdminAction::SetMultisig { config } => self.internal_set_multisig(config),
         }
+
+        events::emit_proposal_executed(proposal_id, action_name);
     }
 }
 
@@ -450,9 +1966,9 @@ use near_sdk::borsh;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
     use near_sdk::testing_env;
-    use near_sdk::NearToken;
+    use near_sdk::{NearToken, PromiseResult};
 
     fn owner() -> AccountId {
         "owner.testnet".parse().unwrap()
@@ -483,27 +1999,48 @@ mod tests {
         VerificationKey {
             alpha: vec!["1".into(), "2".into()],
             beta: vec![
-                vec!["10857046999023057135944570762232829481370756359578518086990519993285655852781".into(),
-                     "11559732032986387107991004021392285783925812861821192530917403151452391805634".into()],
-                vec!["8495653923123431417604973247489272438418190587263600148770280649306958101930".into(),
-                     "4082367875863433681332203403145435568316851327593401208105741076214120093531".into()],
+                vec![
+                    "10857046999023057135944570762232829481370756359578518086990519993285655852781"
+                        .into(),
+                    "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                        .into(),
+                ],
+                vec![
+                    "8495653923123431417604973247489272438418190587263600148770280649306958101930"
+                        .into(),
+                    "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                        .into(),
+                ],
             ],
             gamma: vec![
-                vec!["10857046999023057135944570762232829481370756359578518086990519993285655852781".into(),
-                     "11559732032986387107991004021392285783925812861821192530917403151452391805634".into()],
-                vec!["8495653923123431417604973247489272438418190587263600148770280649306958101930".into(),
-                     "4082367875863433681332203403145435568316851327593401208105741076214120093531".into()],
+                vec![
+                    "10857046999023057135944570762232829481370756359578518086990519993285655852781"
+                        .into(),
+                    "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                        .into(),
+                ],
+                vec![
+                    "8495653923123431417604973247489272438418190587263600148770280649306958101930"
+                        .into(),
+                    "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                        .into(),
+                ],
             ],
             delta: vec![
-                vec!["10857046999023057135944570762232829481370756359578518086990519993285655852781".into(),
-                     "11559732032986387107991004021392285783925812861821192530917403151452391805634".into()],
-                vec!["8495653923123431417604973247489272438418190587263600148770280649306958101930".into(),
-                     "4082367875863433681332203403145435568316851327593401208105741076214120093531".into()],
-            ],
-            ic: vec![
-                vec!["1".into(), "2".into()],
-                vec!["1".into(), "2".into()],
+                vec![
+                    "10857046999023057135944570762232829481370756359578518086990519993285655852781"
+                        .into(),
+                    "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                        .into(),
+                ],
+                vec![
+                    "8495653923123431417604973247489272438418190587263600148770280649306958101930"
+                        .into(),
+                    "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                        .into(),
+                ],
             ],
+            ic: vec![vec!["1".into(), "2".into()], vec!["1".into(), "2".into()]],
         }
     }
 
@@ -628,7 +2165,10 @@ mod tests {
         assert_eq!(cfg.owner, owner());
         assert!(!cfg.is_paused);
         assert_eq!(cfg.default_expiration_secs, 30 * 24 * 60 * 60);
-        assert_eq!(cfg.storage_cost_per_credential, 10_000_000_000_000_000_000_000);
+        assert_eq!(
+            cfg.storage_cost_per_credential,
+            10_000_000_000_000_000_000_000
+        );
     }
 
     #[test]
@@ -673,9 +2213,35 @@ mod tests {
             store_credential: false,
             custom_expiration: None,
             claim: None,
+            issue_to: None,
+            privacy: None,
         });
     }
 
+    #[test]
+    fn test_failed_treasury_withdrawal_restores_balance() {
+        setup_context(&owner());
+        let mut contract = ZKVerifier::new(owner());
+        contract.treasury_balance = 1_000;
+        contract.set_treasury_beneficiary(alice());
+        let _ = contract.withdraw_treasury(None);
+        assert_eq!(contract.treasury_balance, 0);
+
+        let context = VMContextBuilder::new()
+            .predecessor_account_id(accounts(0))
+            .current_account_id(accounts(0))
+            .build();
+        testing_env!(
+            context,
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!contract.on_treasury_withdrawn(alice(), U128(1_000)));
+        assert_eq!(contract.treasury_balance, 1_000);
+    }
+
     #[test]
     fn test_credential_operations() {
         setup_context(&owner());
@@ -0,0 +1,310 @@
+//! State versioning for code upgrades.
+//!
+//! The contract state is stored flat under `STATE`, so its version is kept
+//! beside it under `STATE_VERSION`. Deployments from before versioning have
+//! no version entry and are treated as v1. To change the layout: freeze the
+//! current struct as `ZKVerifierV<n>` here, bump `STATE_VERSION`, and add a
+//! `from_v<n>` arm to `migrate_state`.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::store::{IterableSet, LookupMap, LookupSet};
+use near_sdk::{env, AccountId, FunctionError};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{CircuitType, Credential, VerificationKey};
+use crate::ZKVerifier;
+
+/// Layout version written by this code.
+pub const STATE_VERSION: u32 = 2;
+
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+/// Version of the stored state; 1 if no version has been recorded.
+pub fn stored_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|bytes| u32::try_from_slice(&bytes).expect("state version is a u32"))
+        .unwrap_or(1)
+}
+
+pub fn write_state_version(version: u32) {
+    env::storage_write(STATE_VERSION_KEY, &borsh::to_vec(&version).unwrap());
+}
+
+/// Contract state as laid out before versioning.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ZKVerifierV1 {
+    pub owner: AccountId,
+    pub proposed_owner: Option<AccountId>,
+    pub admins: LookupSet<AccountId>,
+    pub is_paused: bool,
+    pub default_expiration_secs: u64,
+    pub storage_cost_per_credential: u128,
+    pub verification_keys: LookupMap<String, VerificationKey>,
+    pub credentials: LookupMap<String, CredentialV1>,
+    pub credentials_by_owner: LookupMap<AccountId, IterableSet<String>>,
+    pub total_verifications: u64,
+    pub total_credentials: u64,
+    pub vk_count: u32,
+    pub credential_nonce: u64,
+    pub revoked_credentials: LookupSet<String>,
+}
+
+/// Credential record as stored before state version 2.
+///
+/// Credential maps cannot be enumerated, so these records stay where they are
+/// and are converted on read, or moved to the current map when modified.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct CredentialV1 {
+    pub id: String,
+    pub owner: AccountId,
+    pub circuit_type: CircuitType,
+    pub public_signals: Vec<String>,
+    pub verified_at: u64,
+    pub expires_at: u64,
+    pub claim: Option<String>,
+}
+
+impl CredentialV1 {
+    /// Convert to the current layout. The record predates verification key
+    /// versioning and the issue-time indexes, so those fields are zero.
+    pub fn upgrade(self) -> Credential {
+        Credential {
+            id: self.id,
+            owner: self.owner,
+            circuit_type: self.circuit_type,
+            public_signals: self.public_signals,
+            verified_at: self.verified_at,
+            expires_at: self.expires_at,
+            claim: self.claim,
+            vk_version: 0,
+            issue_seq: 0,
+            circuit_seq: 0,
+            issued_by: None,
+        }
+    }
+}
+
+/// Read the stored state in whatever layout it has and convert it to the
+/// current one, recording the new version.
+pub fn migrate_state() -> ZKVerifier {
+    let from = stored_state_version();
+    let state = match from {
+        1 => from_v1(read_state()),
+        STATE_VERSION => read_state(),
+        other => ContractError::UnsupportedStateVersion(other).panic(),
+    };
+    if from != STATE_VERSION {
+        write_state_version(STATE_VERSION);
+        events::emit_state_migrated(from, STATE_VERSION);
+    }
+    state
+}
+
+fn read_state<T: BorshDeserialize>() -> T {
+    env::state_read().unwrap_or_else(|| ContractError::StateNotInitialized.panic())
+}
+
+fn from_v1(old: ZKVerifierV1) -> ZKVerifier {
+    // Collections added since v1 start empty; the v1 ones keep their prefixes
+    let mut state = ZKVerifier::new(old.owner);
+    state.proposed_owner = old.proposed_owner;
+    state.is_paused = old.is_paused;
+    state.default_expiration_secs = old.default_expiration_secs;
+    state.storage_cost_per_credential = old.storage_cost_per_credential;
+    state.total_verifications = old.total_verifications;
+    state.total_credentials = old.total_credentials;
+    state.vk_count = old.vk_count;
+    state.credential_nonce = old.credential_nonce;
+
+    // Keys registered before versioning count as version 1
+    for circuit_type in [
+        CircuitType::VerifiedBuilder,
+        CircuitType::GrantTrackRecord,
+        CircuitType::TeamAttestation,
+    ] {
+        let key = circuit_type.as_key();
+        if old.verification_keys.contains_key(&key) {
+            state.vk_versions.insert(key, 1);
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageKey;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn owner() -> AccountId {
+        "owner.testnet".parse().unwrap()
+    }
+
+    fn alice() -> AccountId {
+        "alice.testnet".parse().unwrap()
+    }
+
+    fn setup_context(predecessor: &AccountId) {
+        let context = VMContextBuilder::new()
+            .predecessor_account_id(predecessor.clone())
+            .block_timestamp(1_700_000_000 * 1_000_000_000)
+            .build();
+        testing_env!(context);
+    }
+
+    fn mock_vk() -> VerificationKey {
+        VerificationKey {
+            alpha: vec!["1".into(), "2".into()],
+            beta: vec![],
+            gamma: vec![],
+            delta: vec![],
+            ic: vec![vec!["1".into(), "2".into()], vec!["1".into(), "2".into()]],
+        }
+    }
+
+    /// Write a v1 contract state holding one admin, one VK and one credential
+    /// owned by alice, the way the pre-versioning code laid it out.
+    fn write_v1_state() {
+        let mut v1 = ZKVerifierV1 {
+            owner: owner(),
+            proposed_owner: Some(alice()),
+            admins: LookupSet::new(borsh::to_vec(&StorageKey::Admins).unwrap()),
+            is_paused: true,
+            default_expiration_secs: 3600,
+            storage_cost_per_credential: 42,
+            verification_keys: LookupMap::new(
+                borsh::to_vec(&StorageKey::VerificationKeys).unwrap(),
+            ),
+            credentials: LookupMap::new(borsh::to_vec(&StorageKey::Credentials).unwrap()),
+            credentials_by_owner: LookupMap::new(
+                borsh::to_vec(&StorageKey::CredentialsByOwner).unwrap(),
+            ),
+            total_verifications: 7,
+            total_credentials: 1,
+            vk_count: 1,
+            credential_nonce: 1,
+            revoked_credentials: LookupSet::new(
+                borsh::to_vec(&StorageKey::RevokedCredentials).unwrap(),
+            ),
+        };
+        v1.admins.insert(alice());
+        v1.verification_keys
+            .insert(CircuitType::TeamAttestation.as_key(), mock_vk());
+        v1.credentials.insert(
+            "cred-v1".into(),
+            CredentialV1 {
+                id: "cred-v1".into(),
+                owner: alice(),
+                circuit_type: CircuitType::TeamAttestation,
+                public_signals: vec!["1".into()],
+                verified_at: 1_700_000_000,
+                expires_at: 1_700_000_000 + 3600,
+                claim: Some("v1".into()),
+            },
+        );
+        let mut owner_set = IterableSet::new(
+            borsh::to_vec(&StorageKey::OwnerCredentials {
+                owner_hash: env::sha256(alice().as_bytes()),
+            })
+            .unwrap(),
+        );
+        owner_set.insert("cred-v1".to_string());
+        v1.credentials_by_owner.insert(alice(), owner_set);
+
+        v1.verification_keys.flush();
+        v1.credentials.flush();
+        v1.credentials_by_owner.flush();
+        env::state_write(&v1);
+    }
+
+    #[test]
+    fn migrate_v1_state_forward() {
+        setup_context(&owner());
+        write_v1_state();
+        assert_eq!(stored_state_version(), 1);
+
+        let contract = ZKVerifier::migrate();
+        assert_eq!(stored_state_version(), STATE_VERSION);
+
+        let config = contract.get_config();
+        assert_eq!(config.owner, owner());
+        assert_eq!(config.proposed_owner, Some(alice()));
+        assert!(config.is_paused);
+        assert_eq!(config.default_expiration_secs, 3600);
+        assert_eq!(config.storage_cost_per_credential, 42);
+        assert!(contract.is_admin(alice()));
+
+        let stats = contract.get_stats();
+        assert_eq!(stats.total_verifications, 7);
+        assert_eq!(stats.total_credentials, 1);
+        assert_eq!(stats.verification_keys_registered, 1);
+        assert!(contract.has_verification_key(CircuitType::TeamAttestation));
+        assert_eq!(
+            contract.get_verification_key_version(CircuitType::TeamAttestation),
+            1
+        );
+        assert_eq!(
+            contract.get_verification_key_version(CircuitType::VerifiedBuilder),
+            0
+        );
+    }
+
+    #[test]
+    fn v1_credentials_readable_after_migration() {
+        setup_context(&owner());
+        write_v1_state();
+        let contract = ZKVerifier::migrate();
+
+        let cred = contract.get_credential("cred-v1".into()).unwrap();
+        assert_eq!(cred.owner, alice());
+        assert_eq!(cred.claim.as_deref(), Some("v1"));
+        assert_eq!(
+            (cred.vk_version, cred.issue_seq, cred.issued_by),
+            (0, 0, None)
+        );
+        assert_eq!(contract.is_credential_valid("cred-v1".into()), Some(true));
+
+        let page = contract.get_credentials_by_owner(alice(), None, None, None);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.credentials[0].id, "cred-v1");
+    }
+
+    #[test]
+    fn v1_credentials_removable_after_migration() {
+        setup_context(&owner());
+        write_v1_state();
+        let mut contract = ZKVerifier::migrate();
+        contract.is_paused = false;
+
+        setup_context(&alice());
+        assert!(contract.remove_credential("cred-v1".into()));
+        assert!(contract.get_credential("cred-v1".into()).is_none());
+        assert!(!contract.legacy_credentials.contains_key("cred-v1"));
+        assert_eq!(contract.get_stats().total_credentials, 0);
+    }
+
+    #[test]
+    fn migrate_current_state_is_noop() {
+        setup_context(&owner());
+        let mut contract = ZKVerifier::new(owner());
+        contract.add_admin(alice());
+        contract.total_verifications = 3;
+        env::state_write(&contract);
+
+        let migrated = ZKVerifier::migrate();
+        assert_eq!(stored_state_version(), STATE_VERSION);
+        assert_eq!(migrated.get_stats().total_verifications, 3);
+        assert!(migrated.is_admin(alice()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsupported state version: 99")]
+    fn migrate_rejects_unknown_version() {
+        setup_context(&owner());
+        env::state_write(&ZKVerifier::new(owner()));
+        write_state_version(99);
+        ZKVerifier::migrate();
+    }
+}
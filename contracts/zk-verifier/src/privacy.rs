@@ -0,0 +1,89 @@
+use near_sdk::{env, AccountId, FunctionError};
+
+use crate::errors::ContractError;
+use crate::types::CircuitType;
+
+/// Minimum salt length in bytes; shorter salts make the hashes guessable.
+pub const MIN_SALT_BYTES: usize = 16;
+
+/// Decode a hex salt, panicking if it is malformed or too short.
+pub fn parse_salt(salt: &str) -> Vec<u8> {
+    try_parse_salt(salt).unwrap_or_else(|| {
+        ContractError::InvalidPrivacyOptions(format!(
+            "salt must be at least {MIN_SALT_BYTES} hex-encoded bytes"
+        ))
+        .panic()
+    })
+}
+
+/// Decode a hex salt, returning `None` if it is malformed or too short.
+pub fn try_parse_salt(salt: &str) -> Option<Vec<u8>> {
+    hex::decode(salt)
+        .ok()
+        .filter(|bytes| bytes.len() >= MIN_SALT_BYTES)
+}
+
+/// `sha256:<hex>` over the salt followed by the compact JSON of the signals.
+/// Holders hand the signals and salt to a verifier, who recomputes this.
+pub fn signals_commitment(public_signals: &[String], salt: &[u8]) -> String {
+    let json = serde_json::to_vec(public_signals).expect("signals serialize to JSON");
+    let mut preimage = salt.to_vec();
+    preimage.extend_from_slice(&json);
+    format!("sha256:{}", hex::encode(env::sha256(&preimage)))
+}
+
+/// Salted owner index key: hex of `sha256(salt || account_id)`.
+pub fn owner_hash(owner: &AccountId, salt: &[u8]) -> String {
+    let mut preimage = salt.to_vec();
+    preimage.extend_from_slice(owner.as_bytes());
+    hex::encode(env::sha256(&preimage))
+}
+
+/// Credential ID derived from the owner hash, so it does not leak the account
+/// the way `storage::generate_credential_id` would.
+pub fn generate_private_credential_id(
+    owner_hash: &str,
+    circuit_type: &CircuitType,
+    nonce: u64,
+) -> String {
+    let hash_input = format!("{owner_hash}:{circuit_type}:{nonce}");
+    let hash = env::sha256(hash_input.as_bytes());
+    format!("cred-{}", hex::encode(&hash[..16]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALT: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn salt_must_be_long_enough_hex() {
+        assert!(try_parse_salt(SALT).is_some());
+        assert!(try_parse_salt("0001").is_none());
+        assert!(try_parse_salt("not hex at all, not hex at all!!").is_none());
+    }
+
+    #[test]
+    fn commitment_binds_signals_and_salt() {
+        let salt = try_parse_salt(SALT).unwrap();
+        let signals = vec!["1".to_string(), "42".to_string()];
+        let commitment = signals_commitment(&signals, &salt);
+
+        assert!(commitment.starts_with("sha256:"));
+        assert_eq!(commitment, signals_commitment(&signals, &salt));
+        assert_ne!(
+            commitment,
+            signals_commitment(&["1".into(), "43".into()], &salt)
+        );
+        assert_ne!(commitment, signals_commitment(&signals, &[0u8; 16]));
+    }
+
+    #[test]
+    fn owner_hash_depends_on_salt() {
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let salt = try_parse_salt(SALT).unwrap();
+        assert_eq!(owner_hash(&alice, &salt).len(), 64);
+        assert_ne!(owner_hash(&alice, &salt), owner_hash(&alice, &[0u8; 16]));
+    }
+}
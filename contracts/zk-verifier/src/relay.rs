@@ -0,0 +1,46 @@
+use near_sdk::{env, AccountId, CurveType, PublicKey};
+
+use crate::types::{RelayIntent, VerifyProofInput};
+
+/// Hex `sha256` of the compact JSON of a `verify_proof` input. Binds the
+/// proof, public signals and storage options; clients can get it from
+/// `get_relay_proof_hash` instead of reproducing the serialization.
+pub fn proof_hash(input: &VerifyProofInput) -> String {
+    let json = serde_json::to_vec(input).expect("input serializes to JSON");
+    hex::encode(env::sha256(&json))
+}
+
+/// The bytes a holder signs: the compact JSON of the intent, fields in
+/// declaration order.
+pub fn intent_message(intent: &RelayIntent) -> Vec<u8> {
+    serde_json::to_vec(intent).expect("intent serializes to JSON")
+}
+
+/// Raw 32-byte key of an ed25519 public key, or `None` for other curves.
+pub fn ed25519_key_bytes(public_key: &PublicKey) -> Option<[u8; 32]> {
+    if public_key.curve_type() != CurveType::ED25519 {
+        return None;
+    }
+    public_key.as_bytes()[1..].try_into().ok()
+}
+
+/// Whether `account` is the implicit account of `public_key`, whose ID is
+/// the hex-encoded key and so needs no registration.
+pub fn is_implicit_account_of(account: &AccountId, public_key: &PublicKey) -> bool {
+    ed25519_key_bytes(public_key).is_some_and(|key| account.as_str() == hex::encode(key))
+}
+
+/// Check an ed25519 signature over the intent message.
+pub fn verify_intent_signature(
+    intent: &RelayIntent,
+    public_key: &PublicKey,
+    signature: &[u8],
+) -> bool {
+    let Some(key) = ed25519_key_bytes(public_key) else {
+        return false;
+    };
+    let Ok(signature) = <&[u8; 64]>::try_from(signature) else {
+        return false;
+    };
+    env::ed25519_verify(signature, intent_message(intent), &key)
+}
@@ -1,8 +1,17 @@
 use near_sdk::borsh::{self, BorshSerialize};
-use near_sdk::store::{LookupMap, IterableSet};
-use near_sdk::{env, AccountId};
+use near_sdk::store::{IterableMap, IterableSet, LookupMap, LookupSet};
+use near_sdk::{env, AccountId, FunctionError};
 
-use crate::types::{CircuitType, Credential};
+use crate::errors::ContractError;
+use crate::types::{
+    CircuitType, Credential, CredentialFilter, CredentialPage, MultisigConfig, PendingCredential,
+    PresentationGrant, PrivateCredential, Proposal, RateLimitBucket, RateLimitConfig,
+    VerificationKey,
+};
+
+/// Maximum index slots inspected by a single listing or pruning call.
+/// Removed credentials leave gaps, so a page may come back short.
+pub const MAX_INDEX_SCAN: u64 = 500;
 
 /// Storage key prefixes — each must be unique to avoid collisions.
 #[derive(BorshSerialize)]
@@ -11,9 +20,47 @@ pub enum StorageKey {
     VerificationKeys,
     Credentials,
     CredentialsByOwner,
-    OwnerCredentials { owner_hash: Vec<u8> },
+    OwnerCredentials {
+        owner_hash: Vec<u8>,
+    },
     Admins,
     RevokedCredentials,
+    Proposals,
+    VerificationFees,
+    RateLimitBuckets,
+    RateLimitExempt,
+    VerificationKeyVersions,
+    CredentialsByIssue,
+    CredentialsByCircuit,
+    CircuitIssueCounts,
+    CircuitMetrics,
+    StatsEpochStarts,
+    PendingCredentials,
+    PendingByHolder,
+    HolderPending {
+        holder_hash: Vec<u8>,
+    },
+    AutoAccept,
+    PrivateCredentials,
+    PrivateByOwner,
+    PrivateOwnerCredentials {
+        owner_hash: Vec<u8>,
+    },
+    /// Credentials in the current layout; `Credentials` holds pre-v2 records
+    CredentialsV2,
+    SignalIndexPositions,
+    CredentialsBySignal,
+    SignalCredentials {
+        key_hash: Vec<u8>,
+    },
+    CredentialSignalPositions,
+    RelayKeys,
+    RelayNonces,
+    PresentationGrants,
+    GrantsByHolder,
+    HolderGrants {
+        holder_hash: Vec<u8>,
+    },
 }
 
 /// Generate a unique credential ID from a monotonic nonce.
@@ -54,15 +101,33 @@ pub fn store_credential(
 ///
 /// Returns `(credentials, total_matching)` where `total_matching` counts all
 /// credentials that pass the expiration filter (not just the returned page).
+/// `lookup` resolves a credential ID to its record.
 pub fn get_credentials_by_owner(
-    credentials: &LookupMap<String, Credential>,
+    lookup: impl Fn(&str) -> Option<Credential>,
     credentials_by_owner: &LookupMap<AccountId, IterableSet<String>>,
     owner: &AccountId,
     include_expired: bool,
     offset: u32,
     limit: u32,
 ) -> (Vec<Credential>, u32) {
-    let Some(owner_set) = credentials_by_owner.get(owner) else {
+    paginate_credential_set(
+        lookup,
+        credentials_by_owner.get(owner),
+        include_expired,
+        offset,
+        limit,
+    )
+}
+
+/// Offset-paginate a set of credential IDs, as `get_credentials_by_owner`.
+pub fn paginate_credential_set(
+    lookup: impl Fn(&str) -> Option<Credential>,
+    ids: Option<&IterableSet<String>>,
+    include_expired: bool,
+    offset: u32,
+    limit: u32,
+) -> (Vec<Credential>, u32) {
+    let Some(ids) = ids else {
         return (vec![], 0);
     };
 
@@ -70,11 +135,11 @@ pub fn get_credentials_by_owner(
     let mut total: u32 = 0;
     let mut result = Vec::new();
 
-    for id in owner_set.iter() {
-        if let Some(cred) = credentials.get(id) {
+    for id in ids.iter() {
+        if let Some(cred) = lookup(id) {
             if include_expired || cred.expires_at > now {
                 if total >= offset && (result.len() as u32) < limit {
-                    result.push(cred.clone());
+                    result.push(cred);
                 }
                 total += 1;
             }
@@ -85,41 +150,362 @@ pub fn get_credentials_by_owner(
 }
 
 /// Check if a credential exists and is not expired.
-pub fn is_credential_valid(
-    credentials: &LookupMap<String, Credential>,
-    credential_id: &str,
-) -> Option<bool> {
-    credentials.get(credential_id).map(|cred| {
+pub fn is_credential_valid(credential: Option<&Credential>) -> Option<bool> {
+    credential.map(|cred| {
         let now = env::block_timestamp() / 1_000_000_000;
         cred.expires_at > now
     })
 }
 
-/// Remove a credential from storage. Returns true if removed.
-pub fn remove_credential(
+/// Remove a credential from the credentials map and its owner's set.
+/// Returns the removed credential so callers can clean up other indexes.
+pub fn delete_credential(
     credentials: &mut LookupMap<String, Credential>,
     credentials_by_owner: &mut LookupMap<AccountId, IterableSet<String>>,
     credential_id: &str,
-    caller: &AccountId,
-) -> bool {
-    let Some(cred) = credentials.get(credential_id) else {
-        return false;
+) -> Option<Credential> {
+    let cred = credentials.remove(credential_id)?;
+
+    // Remove from owner set
+    if let Some(owner_set) = credentials_by_owner.get_mut(&cred.owner) {
+        owner_set.remove(credential_id);
+    }
+
+    Some(cred)
+}
+
+/// Store a credential awaiting acceptance and add it to its holder's pending set.
+pub fn store_pending_credential(
+    pending: &mut LookupMap<String, PendingCredential>,
+    pending_by_holder: &mut LookupMap<AccountId, IterableSet<String>>,
+    entry: PendingCredential,
+) {
+    let id = entry.credential.id.clone();
+    let holder = entry.credential.owner.clone();
+    pending.insert(id.clone(), entry);
+
+    if pending_by_holder.get(&holder).is_none() {
+        let prefix = StorageKey::HolderPending {
+            holder_hash: env::sha256(holder.as_bytes()),
+        };
+        pending_by_holder.insert(
+            holder.clone(),
+            IterableSet::new(borsh::to_vec(&prefix).unwrap()),
+        );
+    }
+    pending_by_holder.get_mut(&holder).unwrap().insert(id);
+}
+
+/// Remove a pending credential and drop it from its holder's pending set.
+pub fn remove_pending_credential(
+    pending: &mut LookupMap<String, PendingCredential>,
+    pending_by_holder: &mut LookupMap<AccountId, IterableSet<String>>,
+    credential_id: &str,
+) -> Option<PendingCredential> {
+    let entry = pending.remove(credential_id)?;
+    if let Some(set) = pending_by_holder.get_mut(&entry.credential.owner) {
+        set.remove(credential_id);
+    }
+    Some(entry)
+}
+
+/// Maximum credentials a single presentation grant may cover.
+pub const MAX_PRESENTATION_CREDENTIALS: usize = 20;
+
+/// Generate a presentation grant ID from a monotonic nonce.
+pub fn generate_grant_id(holder: &AccountId, grantee: &AccountId, nonce: u64) -> String {
+    let hash_input = format!("{holder}:{grantee}:{nonce}");
+    let hash = env::sha256(hash_input.as_bytes());
+    format!("grant-{}", hex::encode(&hash[..16]))
+}
+
+/// Store a presentation grant and add it to its holder's set.
+pub fn store_presentation_grant(
+    grants: &mut LookupMap<String, PresentationGrant>,
+    grants_by_holder: &mut LookupMap<AccountId, IterableSet<String>>,
+    grant: PresentationGrant,
+) {
+    let id = grant.id.clone();
+    let holder = grant.holder.clone();
+    grants.insert(id.clone(), grant);
+
+    if grants_by_holder.get(&holder).is_none() {
+        let prefix = StorageKey::HolderGrants {
+            holder_hash: env::sha256(holder.as_bytes()),
+        };
+        grants_by_holder.insert(
+            holder.clone(),
+            IterableSet::new(borsh::to_vec(&prefix).unwrap()),
+        );
+    }
+    grants_by_holder.get_mut(&holder).unwrap().insert(id);
+}
+
+/// Remove a presentation grant and drop it from its holder's set.
+pub fn remove_presentation_grant(
+    grants: &mut LookupMap<String, PresentationGrant>,
+    grants_by_holder: &mut LookupMap<AccountId, IterableSet<String>>,
+    grant_id: &str,
+) -> Option<PresentationGrant> {
+    let grant = grants.remove(grant_id)?;
+    if let Some(set) = grants_by_holder.get_mut(&grant.holder) {
+        set.remove(grant_id);
+    }
+    Some(grant)
+}
+
+/// Store a privacy-mode credential and add it to its owner hash's set.
+pub fn store_private_credential(
+    credentials: &mut LookupMap<String, PrivateCredential>,
+    by_owner_hash: &mut LookupMap<String, IterableSet<String>>,
+    credential: PrivateCredential,
+) {
+    let id = credential.id.clone();
+    let owner_hash = credential.owner_hash.clone();
+    credentials.insert(id.clone(), credential);
+
+    if by_owner_hash.get(&owner_hash).is_none() {
+        let prefix = StorageKey::PrivateOwnerCredentials {
+            owner_hash: env::sha256(owner_hash.as_bytes()),
+        };
+        by_owner_hash.insert(
+            owner_hash.clone(),
+            IterableSet::new(borsh::to_vec(&prefix).unwrap()),
+        );
+    }
+    by_owner_hash.get_mut(&owner_hash).unwrap().insert(id);
+}
+
+/// Remove a privacy-mode credential from the map and its owner hash's set.
+pub fn delete_private_credential(
+    credentials: &mut LookupMap<String, PrivateCredential>,
+    by_owner_hash: &mut LookupMap<String, IterableSet<String>>,
+    credential_id: &str,
+) -> Option<PrivateCredential> {
+    let cred = credentials.remove(credential_id)?;
+    if let Some(set) = by_owner_hash.get_mut(&cred.owner_hash) {
+        set.remove(credential_id);
+    }
+    Some(cred)
+}
+
+/// Key of the signal index: (circuit key, signal position, signal value).
+pub type SignalKey = (String, u32, String);
+
+/// Add a credential ID to the set for a signal value, creating it if needed.
+pub fn index_signal(
+    by_signal: &mut LookupMap<SignalKey, IterableSet<String>>,
+    key: SignalKey,
+    credential_id: String,
+) {
+    if by_signal.get(&key).is_none() {
+        let prefix = StorageKey::SignalCredentials {
+            key_hash: env::sha256(borsh::to_vec(&key).unwrap()),
+        };
+        by_signal.insert(
+            key.clone(),
+            IterableSet::new(borsh::to_vec(&prefix).unwrap()),
+        );
+    }
+    by_signal.get_mut(&key).unwrap().insert(credential_id);
+}
+
+/// Drop a credential ID from the set for a signal value, removing the set
+/// once it is empty.
+pub fn unindex_signal(
+    by_signal: &mut LookupMap<SignalKey, IterableSet<String>>,
+    key: &SignalKey,
+    credential_id: &str,
+) {
+    let Some(set) = by_signal.get_mut(key) else {
+        return;
     };
+    set.remove(credential_id);
+    if set.is_empty() {
+        by_signal.remove(key);
+    }
+}
 
-    // Only the owner can remove their credential
-    if &cred.owner != caller {
-        return false;
+/// Whether a credential passes a listing filter.
+pub fn credential_matches(
+    cred: &Credential,
+    filter: &CredentialFilter,
+    revoked: &LookupSet<String>,
+    now_secs: u64,
+) -> bool {
+    (filter.include_expired || cred.expires_at > now_secs)
+        && (filter.include_revoked || !revoked.contains(&cred.id))
+        && filter.vk_version.is_none_or(|v| v == cred.vk_version)
+}
+
+/// Walk an issue-time index from `cursor` up to `end` (inclusive), collecting
+/// up to `limit` credentials that pass the filter. `lookup` maps an index
+/// slot to the credential ID stored there; removed credentials leave empty
+/// slots, which are skipped. At most `MAX_INDEX_SCAN` slots are inspected.
+pub fn list_indexed_credentials<'a>(
+    credentials: &LookupMap<String, Credential>,
+    revoked: &LookupSet<String>,
+    lookup: impl Fn(u64) -> Option<&'a String>,
+    cursor: u64,
+    end: u64,
+    limit: u32,
+    filter: &CredentialFilter,
+) -> CredentialPage {
+    let now = env::block_timestamp() / 1_000_000_000;
+    let scan_end = end.min(cursor.saturating_add(MAX_INDEX_SCAN - 1));
+    let mut result = Vec::new();
+    let mut seq = cursor;
+
+    while seq <= scan_end && (result.len() as u32) < limit {
+        if let Some(cred) = lookup(seq).and_then(|id| credentials.get(id)) {
+            if credential_matches(cred, filter, revoked, now) {
+                result.push(cred.clone());
+            }
+        }
+        seq += 1;
     }
 
-    let owner = cred.owner.clone();
-    credentials.remove(credential_id);
+    CredentialPage {
+        credentials: result,
+        next_cursor: (seq <= end).then_some(seq),
+    }
+}
 
-    // Remove from owner set
-    if let Some(owner_set) = credentials_by_owner.get_mut(&owner) {
-        owner_set.remove(&credential_id.to_string());
+/// Basic verification key validation: IC must have at least 1 element.
+pub fn validate_verification_key(vk: &VerificationKey) {
+    if vk.ic.is_empty() {
+        ContractError::InvalidVerificationKey("IC array must not be empty".into()).panic();
+    }
+}
+
+/// Validate a multisig config: non-empty distinct approvers and 1 <= M <= N.
+pub fn validate_multisig_config(config: &MultisigConfig) {
+    let n = config.approvers.len();
+    if n == 0 {
+        ContractError::InvalidMultisigConfig("approver set must not be empty".into()).panic();
+    }
+    let mut unique = config.approvers.clone();
+    unique.sort();
+    unique.dedup();
+    if unique.len() != n {
+        ContractError::InvalidMultisigConfig("approvers must be distinct".into()).panic();
+    }
+    if config.threshold == 0 || config.threshold as usize > n {
+        ContractError::InvalidMultisigConfig(format!(
+            "threshold must be between 1 and {n}, got {}",
+            config.threshold
+        ))
+        .panic();
     }
+    if config.proposal_ttl_secs == 0 {
+        ContractError::InvalidMultisigConfig("proposal TTL must be positive".into()).panic();
+    }
+}
+
+/// Number of proposals inspected per `propose_action` call when pruning.
+pub const PROPOSAL_PRUNE_SCAN: u32 = 8;
 
-    true
+/// Remove proposals whose expiry has passed, scanning at most `max_scan`
+/// entries from `cursor` (wrapping around) so the cost per call stays bounded.
+/// The cursor advances across calls. Returns the number removed.
+pub fn prune_expired_proposals(
+    proposals: &mut IterableMap<u64, Proposal>,
+    cursor: &mut u32,
+    now_secs: u64,
+    max_scan: u32,
+) -> u32 {
+    let len = proposals.len();
+    if *cursor >= len {
+        *cursor = 0;
+    }
+    let scanned = max_scan.min(len);
+    let tail = scanned.min(len - *cursor);
+    let expired: Vec<u64> = proposals
+        .iter()
+        .skip(*cursor as usize)
+        .take(tail as usize)
+        .chain(proposals.iter().take((scanned - tail) as usize))
+        .filter(|(_, p)| p.expires_at <= now_secs)
+        .map(|(id, _)| *id)
+        .collect();
+    for id in &expired {
+        proposals.remove(id);
+    }
+    // Removal swaps later entries into the freed slots, so only advance past survivors
+    *cursor += scanned - expired.len() as u32;
+    expired.len() as u32
+}
+
+/// Number of rate-limit buckets inspected per `verify_proof` call when pruning.
+pub const RATE_LIMIT_PRUNE_SCAN: u32 = 8;
+
+/// Count a call against the caller's rate-limit bucket, starting a new window
+/// once the previous one has elapsed or the config epoch has changed. Panics
+/// with `RateLimited` over quota.
+pub fn consume_rate_limit(
+    buckets: &mut IterableMap<AccountId, RateLimitBucket>,
+    config: &RateLimitConfig,
+    epoch: u32,
+    account: &AccountId,
+    now_secs: u64,
+) {
+    match buckets.get_mut(account) {
+        Some(bucket)
+            if bucket.epoch == epoch && now_secs < bucket.window_start + config.window_secs =>
+        {
+            if bucket.count >= config.max_calls {
+                ContractError::RateLimited {
+                    retry_after: bucket.window_start + config.window_secs,
+                }
+                .panic();
+            }
+            bucket.count += 1;
+        }
+        _ => {
+            buckets.insert(
+                account.clone(),
+                RateLimitBucket {
+                    window_start: now_secs,
+                    count: 1,
+                    epoch,
+                },
+            );
+        }
+    }
+}
+
+/// Remove buckets whose window has elapsed or whose epoch is not `epoch`,
+/// scanning at most `max_scan` entries from `cursor` (wrapping around) so the
+/// cost per call stays bounded. The cursor advances across calls. Returns the
+/// number removed.
+pub fn prune_rate_limit_buckets(
+    buckets: &mut IterableMap<AccountId, RateLimitBucket>,
+    cursor: &mut u32,
+    epoch: u32,
+    window_secs: u64,
+    now_secs: u64,
+    max_scan: u32,
+) -> u32 {
+    let len = buckets.len();
+    if *cursor >= len {
+        *cursor = 0;
+    }
+    let scanned = max_scan.min(len);
+    let tail = scanned.min(len - *cursor);
+    let stale: Vec<AccountId> = buckets
+        .iter()
+        .skip(*cursor as usize)
+        .take(tail as usize)
+        .chain(buckets.iter().take((scanned - tail) as usize))
+        .filter(|(_, b)| b.epoch != epoch || now_secs >= b.window_start + window_secs)
+        .map(|(account, _)| account.clone())
+        .collect();
+    for account in &stale {
+        buckets.remove(account);
+    }
+    // Removal swaps later entries into the freed slots, so only advance past survivors
+    *cursor += scanned - stale.len() as u32;
+    stale.len() as u32
 }
 
 #[cfg(test)]
@@ -135,4 +521,14 @@ mod tests {
         assert_ne!(key2, key3);
         assert_ne!(key1, key3);
     }
+
+    #[test]
+    #[should_panic(expected = "threshold must be between 1 and 2")]
+    fn multisig_threshold_above_approver_count() {
+        validate_multisig_config(&MultisigConfig {
+            approvers: vec!["a.testnet".parse().unwrap(), "b.testnet".parse().unwrap()],
+            threshold: 3,
+            proposal_ttl_secs: MultisigConfig::DEFAULT_PROPOSAL_TTL_SECS,
+        });
+    }
 }
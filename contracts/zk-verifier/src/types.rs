@@ -1,10 +1,14 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::AccountId;
+use std::collections::BTreeMap;
 
 /// Circuit types supported by the verifier contract.
 /// Serde uses kebab-case to match snarkjs/TypeScript circuit identifiers.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
 #[serde(crate = "near_sdk::serde", rename_all = "kebab-case")]
 pub enum CircuitType {
     VerifiedBuilder,
@@ -23,6 +27,25 @@ impl CircuitType {
     }
 }
 
+impl CircuitType {
+    /// Names of the circuit's public signals in snarkjs order (outputs first,
+    /// then the public inputs listed in the circom `main` component).
+    pub fn public_signal_names(&self) -> &'static [&'static str] {
+        match self {
+            CircuitType::VerifiedBuilder => {
+                &["valid", "activityRoot", "minDays", "currentTimestamp"]
+            }
+            CircuitType::GrantTrackRecord => &["valid", "grantRoot", "minGrants", "programsRoot"],
+            CircuitType::TeamAttestation => &[
+                "valid",
+                "attestersRoot",
+                "minAttestations",
+                "credentialType",
+            ],
+        }
+    }
+}
+
 impl std::fmt::Display for CircuitType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_key())
@@ -73,6 +96,61 @@ pub struct VerifyProofInput {
     pub custom_expiration: Option<u64>,
     /// Optional claim text to attach to the credential
     pub claim: Option<String>,
+    /// Holder of the stored credential when it differs from the caller;
+    /// the holder must accept it unless they auto-accept from the caller
+    #[serde(default)]
+    pub issue_to: Option<AccountId>,
+    /// Store only a commitment to the signals and a salted owner hash
+    #[serde(default)]
+    pub privacy: Option<PrivacyOptions>,
+}
+
+/// Salts for privacy-mode storage, hex-encoded, at least 16 bytes each.
+///
+/// Contract state and views then hold no account ID or raw signals for the
+/// credential. The call arguments remain visible in transaction history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PrivacyOptions {
+    /// Salt for the public-signals commitment; revealed only to chosen verifiers
+    pub signals_salt: String,
+    /// Salt for the owner index; the holder needs it to find or remove the credential
+    pub owner_salt: String,
+}
+
+/// Holder-signed authorization for a relayed `verify_proof_relayed` call.
+///
+/// The holder signs the compact JSON of this struct (fields in this order)
+/// with an ed25519 key; the relayer submits it and pays gas and deposit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RelayIntent {
+    /// Verifier contract the intent is for, so it cannot be replayed elsewhere
+    pub verifier: AccountId,
+    /// Account the credential is bound to
+    pub holder: AccountId,
+    pub circuit_type: CircuitType,
+    /// `get_relay_proof_hash` of the submitted input
+    pub proof_hash: String,
+    /// Must exceed the holder's last used relay nonce
+    pub nonce: u64,
+    /// Unix seconds after which the intent is rejected
+    pub expires_at: u64,
+}
+
+/// Credential stored in privacy mode.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PrivateCredential {
+    pub id: String,
+    /// Hex of `sha256(owner_salt || owner)`
+    pub owner_hash: String,
+    pub circuit_type: CircuitType,
+    /// `sha256:<hex>` of `signals_salt || json(public_signals)`
+    pub signals_commitment: String,
+    pub verified_at: u64,
+    pub expires_at: u64,
+    pub vk_version: u32,
 }
 
 /// On-chain credential stored after successful proof verification.
@@ -86,6 +164,49 @@ pub struct Credential {
     pub verified_at: u64,
     pub expires_at: u64,
     pub claim: Option<String>,
+    /// Version of the circuit's verification key the proof was checked against
+    pub vk_version: u32,
+    /// Position in the global issue-time index
+    pub issue_seq: u64,
+    /// Position in the circuit's issue-time index
+    pub circuit_seq: u64,
+    /// Account that submitted the proof, when it is not the owner
+    pub issued_by: Option<AccountId>,
+}
+
+/// Credential issued to another holder and awaiting their acceptance.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingCredential {
+    pub credential: Credential,
+    /// Storage deposit held for the issuer until accept, reject or cancel
+    pub escrow: U128,
+    pub created_at: u64,
+}
+
+/// Holder-issued, time-limited permission for `grantee` to read a chosen
+/// subset of the holder's credentials through `get_presentation`.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PresentationGrant {
+    pub id: String,
+    pub holder: AccountId,
+    pub grantee: AccountId,
+    /// Credential IDs covered by the grant
+    pub credential_ids: Vec<String>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    /// Paid by the holder for the grant's storage; refunded on revoke
+    pub storage_deposit: U128,
+}
+
+/// Credentials disclosed through a presentation grant. Credentials removed
+/// or revoked since the grant was created are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Presentation {
+    pub grant: PresentationGrant,
+    pub credentials: Vec<Credential>,
 }
 
 /// Result returned from verify_proof.
@@ -94,6 +215,8 @@ pub struct Credential {
 pub struct VerificationResult {
     pub valid: bool,
     pub credential_id: Option<String>,
+    /// True if the credential awaits the holder's acceptance
+    pub pending: bool,
     pub gas_used: u64,
 }
 
@@ -108,6 +231,14 @@ pub struct ContractConfig {
     pub default_expiration_secs: u64,
     /// Required storage deposit in yoctoNEAR (0.01 NEAR)
     pub storage_cost_per_credential: u128,
+    /// Account that receives treasury withdrawals
+    pub treasury_beneficiary: Option<AccountId>,
+    /// Per-account `verify_proof` quota; `None` means unlimited
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Circuits paused individually (the global pause overrides these)
+    pub paused_circuits: Vec<CircuitType>,
+    /// Method groups paused individually (the global pause overrides these)
+    pub paused_methods: Vec<MethodGroup>,
 }
 
 impl ContractConfig {
@@ -117,6 +248,184 @@ impl ContractConfig {
     pub const DEFAULT_STORAGE_COST: u128 = 10_000_000_000_000_000_000_000; // 1e22
 }
 
+/// M-of-N approver set guarding sensitive admin actions.
+///
+/// While configured, the guarded admin methods refuse direct calls and must
+/// go through `propose_action` / `approve_proposal` instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MultisigConfig {
+    /// Accounts allowed to create and approve proposals
+    pub approvers: Vec<AccountId>,
+    /// Number of distinct approvals required to execute a proposal
+    pub threshold: u32,
+    /// Seconds after creation before an unexecuted proposal expires
+    pub proposal_ttl_secs: u64,
+}
+
+impl MultisigConfig {
+    /// 7 days in seconds
+    pub const DEFAULT_PROPOSAL_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+}
+
+/// Sensitive admin action that requires multisig approval when enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde", tag = "type", rename_all = "snake_case")]
+pub enum AdminAction {
+    SetVerificationKey {
+        circuit_type: CircuitType,
+        vk: VerificationKey,
+    },
+    SetPaused {
+        paused: bool,
+    },
+    ProposeOwner {
+        new_owner: AccountId,
+    },
+    RevokeCredentials {
+        credential_ids: Vec<String>,
+        reason: String,
+    },
+    SetCircuitPaused {
+        circuit_type: CircuitType,
+        paused: bool,
+    },
+    SetMethodPaused {
+        method: MethodGroup,
+        paused: bool,
+    },
+    /// Replace the approver set, or disable multisig with `None`
+    SetMultisig {
+        config: Option<MultisigConfig>,
+    },
+}
+
+impl AdminAction {
+    /// Short action name used in events.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminAction::SetVerificationKey { .. } => "set_verification_key",
+            AdminAction::SetPaused { .. } => "set_paused",
+            AdminAction::ProposeOwner { .. } => "propose_owner",
+            AdminAction::RevokeCredentials { .. } => "revoke_credentials",
+            AdminAction::SetCircuitPaused { .. } => "set_circuit_paused",
+            AdminAction::SetMethodPaused { .. } => "set_method_paused",
+            AdminAction::SetMultisig { .. } => "set_multisig",
+        }
+    }
+}
+
+/// Pending multisig proposal awaiting approvals.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub id: u64,
+    pub action: AdminAction,
+    pub proposer: AccountId,
+    /// Distinct approvers that have confirmed, including the proposer
+    pub approvals: Vec<AccountId>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// Per-account quota on `verify_proof` calls within a fixed time window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RateLimitConfig {
+    /// Maximum `verify_proof` calls per account per window
+    pub max_calls: u32,
+    /// Window length in seconds
+    pub window_secs: u64,
+}
+
+/// Per-account call counter for the current rate-limit window.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RateLimitBucket {
+    /// Start of the current window in seconds
+    pub window_start: u64,
+    /// Calls made in the current window
+    pub count: u32,
+    /// Rate-limit config epoch the bucket was counted under; buckets from an
+    /// earlier epoch count as empty
+    pub epoch: u32,
+}
+
+/// Credential exported in W3C Verifiable Credentials Data Model (v1) form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    /// The W3C base context, then an inline context for this contract's terms
+    #[serde(rename = "@context")]
+    pub context: Vec<serde_json::Value>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    /// `did:near:` URI of the contract that verified the proof
+    pub issuer: String,
+    /// RFC 3339 timestamp of `verified_at`
+    pub issuance_date: String,
+    /// RFC 3339 timestamp of `expires_at`
+    pub expiration_date: String,
+    pub credential_subject: CredentialSubject,
+    pub credential_status: CredentialStatus,
+    /// `sha256:<hex>` of the compact JSON returned by `get_credential`
+    pub content_hash: String,
+}
+
+/// Subject of an exported credential: the holder and the named public signals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "camelCase")]
+pub struct CredentialSubject {
+    /// `did:near:` URI of the holder account
+    pub id: String,
+    pub circuit_type: CircuitType,
+    pub public_signals: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claim: Option<String>,
+}
+
+/// Pointer to the on-chain revocation registry for an exported credential.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "camelCase")]
+pub struct CredentialStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+    /// Contract holding the revocation registry
+    pub status_contract: String,
+    /// View method that reports revocation for `credential_id`
+    pub status_method: String,
+    pub credential_id: String,
+}
+
+/// Groups of contract methods that can be paused independently.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum MethodGroup {
+    /// `verify_proof` and `verify_proof_view`
+    Verify,
+    /// Credential storage from `verify_proof`
+    Store,
+    /// `revoke_credential` and mass revocation
+    Revoke,
+    /// `remove_credential`
+    Remove,
+}
+
+impl MethodGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MethodGroup::Verify => "verify",
+            MethodGroup::Store => "store",
+            MethodGroup::Revoke => "revoke",
+            MethodGroup::Remove => "remove",
+        }
+    }
+}
+
 /// Paginated credentials response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -126,6 +435,87 @@ pub struct PaginatedCredentials {
     pub has_more: bool,
 }
 
+/// Filters for the global and per-circuit credential listings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct CredentialFilter {
+    /// Include credentials past their expiration
+    pub include_expired: bool,
+    /// Include IDs present in the revocation registry. Revocation normally
+    /// removes the credential, so this only matters for tombstoned IDs.
+    pub include_revoked: bool,
+    /// Only credentials verified against this VK version
+    pub vk_version: Option<u32>,
+}
+
+/// Cursor-paginated credential listing in issue-time order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CredentialPage {
+    pub credentials: Vec<Credential>,
+    /// Pass as `cursor` to continue; `None` when the index is exhausted.
+    /// A page may hold fewer than `limit` items and still have a cursor.
+    pub next_cursor: Option<u64>,
+}
+
+/// Result of a bounded expired-credential pruning pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PruneResult {
+    pub removed: u32,
+    /// Pass as `cursor` to continue; `None` when the index is exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// Per-circuit verification counters for one reporting epoch (stored form).
+#[derive(Debug, Clone, Default, BorshDeserialize, BorshSerialize)]
+pub struct CircuitMetrics {
+    pub valid_proofs: u64,
+    pub invalid_proofs: u64,
+    pub credentials_issued: u64,
+    pub revocations: u64,
+    pub min_gas_used: u64,
+    pub max_gas_used: u64,
+    pub total_gas_used: u128,
+}
+
+impl CircuitMetrics {
+    /// Record one `verify_proof` call and the gas it used.
+    pub fn record_verification(&mut self, valid: bool, gas_used: u64) {
+        let count = self.valid_proofs + self.invalid_proofs;
+        if valid {
+            self.valid_proofs += 1;
+        } else {
+            self.invalid_proofs += 1;
+        }
+        self.min_gas_used = if count == 0 {
+            gas_used
+        } else {
+            self.min_gas_used.min(gas_used)
+        };
+        self.max_gas_used = self.max_gas_used.max(gas_used);
+        self.total_gas_used += gas_used as u128;
+    }
+}
+
+/// Per-circuit verification statistics returned by `get_circuit_stats`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CircuitStats {
+    pub circuit_type: CircuitType,
+    /// Reporting epoch these numbers cover
+    pub epoch: u32,
+    /// Start of the epoch in seconds
+    pub epoch_started_at: u64,
+    pub valid_proofs: u64,
+    pub invalid_proofs: u64,
+    pub credentials_issued: u64,
+    pub revocations: u64,
+    pub min_gas_used: u64,
+    pub avg_gas_used: u64,
+    pub max_gas_used: u64,
+}
+
 /// Contract statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -134,6 +524,14 @@ pub struct ContractStats {
     pub total_credentials: u64,
     pub is_paused: bool,
     pub verification_keys_registered: u32,
+    /// Lifetime verification fee revenue in yoctoNEAR
+    pub total_fees_collected: U128,
+    /// Collected fees not yet withdrawn in yoctoNEAR
+    pub treasury_balance: U128,
+    /// Current per-circuit reporting epoch
+    pub stats_epoch: u32,
+    /// Stored privacy-mode credentials (not included in `total_credentials`)
+    pub total_private_credentials: u64,
 }
 
 #[cfg(test)]
@@ -156,6 +554,14 @@ mod tests {
         assert_eq!(parsed, ct);
     }
 
+    #[test]
+    fn admin_action_serde_tagged() {
+        let json = r#"{"type": "set_paused", "paused": true}"#;
+        let action: AdminAction = serde_json::from_str(json).unwrap();
+        assert!(matches!(action, AdminAction::SetPaused { paused: true }));
+        assert_eq!(action.as_str(), "set_paused");
+    }
+
     #[test]
     fn groth16_proof_deserialize() {
         let json = r#"{
@@ -0,0 +1,179 @@
+use near_sdk::{env, AccountId};
+use std::collections::BTreeMap;
+
+use crate::types::{Credential, CredentialStatus, CredentialSubject, VerifiableCredential};
+
+const VC_CONTEXT_V1: &str = "https://www.w3.org/2018/credentials/v1";
+/// Namespace for the terms the base context does not define (`contentHash`,
+/// the status fields, circuit types and signal names). It is only an IRI
+/// prefix and is never fetched.
+const VC_VOCAB: &str = "https://shade.studio/ns/zk-verifier#";
+const VC_STATUS_TYPE: &str = "ShadeZkRevocationRegistry";
+
+/// Build the W3C VC representation of a stored credential issued by `issuer`.
+///
+/// Public signals are keyed by the circuit's signal names; any signals beyond
+/// the known names fall back to `signal<index>`.
+pub fn credential_to_vc(credential: &Credential, issuer: &AccountId) -> VerifiableCredential {
+    let names = credential.circuit_type.public_signal_names();
+    let public_signals: BTreeMap<String, String> = credential
+        .public_signals
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let name = names
+                .get(i)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| format!("signal{i}"));
+            (name, value.clone())
+        })
+        .collect();
+
+    VerifiableCredential {
+        context: vec![
+            serde_json::Value::from(VC_CONTEXT_V1),
+            serde_json::json!({ "@vocab": VC_VOCAB }),
+        ],
+        id: format!("urn:near:{issuer}:{}", credential.id),
+        credential_type: vec![
+            "VerifiableCredential".to_string(),
+            format!(
+                "{}Credential",
+                circuit_type_name(&credential.circuit_type.as_key())
+            ),
+        ],
+        issuer: did_near(issuer),
+        issuance_date: format_rfc3339(credential.verified_at),
+        expiration_date: format_rfc3339(credential.expires_at),
+        credential_subject: CredentialSubject {
+            id: did_near(&credential.owner),
+            circuit_type: credential.circuit_type.clone(),
+            public_signals,
+            claim: credential.claim.clone(),
+        },
+        credential_status: CredentialStatus {
+            id: format!("{}#revocation-{}", did_near(issuer), credential.id),
+            status_type: VC_STATUS_TYPE.to_string(),
+            status_contract: issuer.to_string(),
+            status_method: "is_credential_revoked".to_string(),
+            credential_id: credential.id.clone(),
+        },
+        content_hash: credential_content_hash(credential),
+    }
+}
+
+/// `sha256:<hex>` over the compact JSON of the credential, i.e. exactly the
+/// bytes a relying party gets by re-serializing `get_credential` output.
+pub fn credential_content_hash(credential: &Credential) -> String {
+    let json = serde_json::to_vec(credential).expect("credential serializes to JSON");
+    format!("sha256:{}", hex::encode(env::sha256(&json)))
+}
+
+/// `did:near:<account>`, the URI form of a NEAR account
+fn did_near(account: &AccountId) -> String {
+    format!("did:near:{account}")
+}
+
+/// "grant-track-record" → "GrantTrackRecord"
+fn circuit_type_name(key: &str) -> String {
+    key.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Format unix seconds as an RFC 3339 UTC timestamp (`YYYY-MM-DDTHH:MM:SSZ`).
+fn format_rfc3339(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+/// Convert days since 1970-01-01 to a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CircuitType;
+
+    fn sample_credential() -> Credential {
+        Credential {
+            id: "cred-abc".into(),
+            owner: "alice.testnet".parse().unwrap(),
+            circuit_type: CircuitType::GrantTrackRecord,
+            public_signals: vec!["1".into(), "123".into(), "5".into(), "456".into()],
+            verified_at: 1_700_000_000,
+            expires_at: 1_702_592_000,
+            claim: Some("5+ grants".into()),
+            vk_version: 1,
+            issue_seq: 1,
+            circuit_seq: 1,
+            issued_by: None,
+        }
+    }
+
+    #[test]
+    fn rfc3339_formatting() {
+        assert_eq!(format_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339(1_700_000_000), "2023-11-14T22:13:20Z");
+        assert_eq!(format_rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn vc_names_public_signals() {
+        let issuer: AccountId = "zk-verifier.testnet".parse().unwrap();
+        let vc = credential_to_vc(&sample_credential(), &issuer);
+
+        assert_eq!(vc.issuer, "did:near:zk-verifier.testnet");
+        assert_eq!(vc.credential_type[1], "GrantTrackRecordCredential");
+        assert_eq!(vc.credential_subject.id, "did:near:alice.testnet");
+        assert_eq!(vc.credential_subject.public_signals["grantRoot"], "123");
+        assert_eq!(vc.credential_subject.public_signals["minGrants"], "5");
+        assert_eq!(vc.credential_status.credential_id, "cred-abc");
+
+        let json = serde_json::to_value(&vc).unwrap();
+        assert_eq!(json["@context"][0], VC_CONTEXT_V1);
+        assert_eq!(json["@context"][1]["@vocab"], VC_VOCAB);
+        assert_eq!(
+            json["credentialStatus"]["id"],
+            "did:near:zk-verifier.testnet#revocation-cred-abc"
+        );
+        assert_eq!(json["issuanceDate"], "2023-11-14T22:13:20Z");
+        assert_eq!(json["credentialStatus"]["type"], VC_STATUS_TYPE);
+    }
+
+    #[test]
+    fn content_hash_tracks_credential_state() {
+        let mut cred = sample_credential();
+        let hash = credential_content_hash(&cred);
+        assert!(hash.starts_with("sha256:"));
+        assert_eq!(hash, credential_content_hash(&cred));
+
+        cred.expires_at += 1;
+        assert_ne!(hash, credential_content_hash(&cred));
+    }
+}
@@ -7,9 +7,8 @@ use crate::types::{Groth16Proof, VerificationKey};
 
 /// Parse a decimal string into an ark_bn254 field element.
 fn parse_field_element<F: PrimeField>(s: &str) -> Result<F, ContractError> {
-    F::from_str(s).map_err(|_| {
-        ContractError::InvalidProofFormat(format!("Cannot parse field element: {s}"))
-    })
+    F::from_str(s)
+        .map_err(|_| ContractError::InvalidProofFormat(format!("Cannot parse field element: {s}")))
 }
 
 /// Parse a G1 affine point from a snarkjs-format string array [x, y, "1"].
@@ -123,14 +122,14 @@ pub fn verify_groth16_proof(
     let neg_a = -a;
 
     // Multi-pairing check
-    let result = Bn254::multi_pairing(
-        [neg_a, alpha, vk_x_affine, c],
-        [b, beta, gamma, delta],
-    );
+    let result = Bn254::multi_pairing([neg_a, alpha, vk_x_affine, c], [b, beta, gamma, delta]);
 
     Ok(result.is_zero())
 }
 
+#[cfg(test)]
+mod differential;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,10 +170,7 @@ mod tests {
             beta: vec![vec!["0".into(), "0".into()], vec!["0".into(), "0".into()]],
             gamma: vec![vec!["0".into(), "0".into()], vec!["0".into(), "0".into()]],
             delta: vec![vec!["0".into(), "0".into()], vec!["0".into(), "0".into()]],
-            ic: vec![
-                vec!["0".into(), "0".into()],
-                vec!["0".into(), "0".into()],
-            ], // expects 1 public signal
+            ic: vec![vec!["0".into(), "0".into()], vec!["0".into(), "0".into()]], // expects 1 public signal
         };
         let proof = Groth16Proof {
             pi_a: vec!["0".into(), "0".into(), "1".into()],
@@ -208,8 +204,8 @@ mod tests {
 
     impl ConstraintSynthesizer<Fr> for SquareCircuit {
         fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
-            use ark_relations::r1cs::LinearCombination;
             use ark_ff::One;
+            use ark_relations::r1cs::LinearCombination;
 
             let x_val = self.x.unwrap_or_default();
             let y_val = x_val * x_val;
@@ -232,10 +228,7 @@ mod tests {
 
     /// Convert an arkworks G1Affine point to string coordinates [x, y].
     fn g1_to_strings(point: &G1Affine) -> Vec<String> {
-        vec![
-            point.x.to_string(),
-            point.y.to_string(),
-        ]
+        vec![point.x.to_string(), point.y.to_string()]
     }
 
     /// Convert an arkworks G2Affine point to snarkjs-format string coordinates.
@@ -248,9 +241,7 @@ mod tests {
     }
 
     /// Convert an arkworks VerifyingKey to our contract's VerificationKey format.
-    fn vk_to_contract(
-        vk: &ark_groth16::VerifyingKey<Bn254>,
-    ) -> VerificationKey {
+    pub(super) fn vk_to_contract(vk: &ark_groth16::VerifyingKey<Bn254>) -> VerificationKey {
         VerificationKey {
             alpha: g1_to_strings(&vk.alpha_g1),
             beta: g2_to_strings(&vk.beta_g2),
@@ -261,7 +252,7 @@ mod tests {
     }
 
     /// Convert an arkworks Proof to our contract's Groth16Proof format.
-    fn proof_to_contract(proof: &ark_groth16::Proof<Bn254>) -> Groth16Proof {
+    pub(super) fn proof_to_contract(proof: &ark_groth16::Proof<Bn254>) -> Groth16Proof {
         Groth16Proof {
             pi_a: g1_to_strings(&proof.a),
             pi_b: g2_to_strings(&proof.b),
@@ -290,7 +281,11 @@ mod tests {
 
         // Verify with correct public signal y = 9
         let result = verify_groth16_proof(&contract_vk, &contract_proof, &["9".to_string()]);
-        assert!(result.is_ok(), "verification should not error: {:?}", result.err());
+        assert!(
+            result.is_ok(),
+            "verification should not error: {:?}",
+            result.err()
+        );
         assert!(result.unwrap(), "valid proof should verify as true");
     }
 
@@ -0,0 +1,395 @@
+//! Differential tests: `verify_groth16_proof` against `ark_groth16` on random
+//! R1CS circuits, honest proofs and mutated proofs, signals and keys.
+//!
+//! Runs a small fixed-seed batch by default. The nightly job sets
+//! `GROTH16_DIFF_CASES` (number of random circuits) and optionally
+//! `GROTH16_DIFF_SEED` to explore further.
+
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::One;
+use ark_groth16::{Groth16 as ArkGroth16, Proof, VerifyingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError, Variable,
+};
+use ark_snark::SNARK;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::{Rng, SeedableRng};
+use ark_std::UniformRand;
+
+use super::tests::{proof_to_contract, vk_to_contract};
+use super::verify_groth16_proof;
+
+const DEFAULT_SEED: u64 = 0x5eed_6a0d;
+const DEFAULT_CASES: usize = 6;
+const MAX_PUBLIC_INPUTS: usize = 32;
+
+/// Public input counts every run covers before switching to random ones.
+const EDGE_INPUT_COUNTS: [usize; 3] = [0, 1, MAX_PUBLIC_INPUTS];
+
+#[derive(Clone, Copy, Debug)]
+enum Var {
+    One,
+    Input(usize),
+    Witness(usize),
+}
+
+type Terms = Vec<(Fr, Var)>;
+
+/// Random satisfiable R1CS: each gate multiplies two random linear
+/// combinations of earlier variables into a fresh witness.
+#[derive(Clone, Debug)]
+struct RandomCircuit {
+    inputs: Vec<Fr>,
+    /// Free witnesses first, then one per gate
+    witnesses: Vec<Fr>,
+    gates: Vec<(Terms, Terms)>,
+}
+
+impl RandomCircuit {
+    fn generate(rng: &mut StdRng, num_inputs: usize) -> Self {
+        let inputs: Vec<Fr> = (0..num_inputs).map(|_| Fr::rand(rng)).collect();
+        let mut witnesses: Vec<Fr> = (0..rng.gen_range(1..=4)).map(|_| Fr::rand(rng)).collect();
+        let mut gates = Vec::new();
+
+        for _ in 0..rng.gen_range(1..=8) {
+            let a = random_terms(rng, inputs.len(), witnesses.len());
+            let b = random_terms(rng, inputs.len(), witnesses.len());
+            let out = eval(&a, &inputs, &witnesses) * eval(&b, &inputs, &witnesses);
+            witnesses.push(out);
+            gates.push((a, b));
+        }
+
+        // Tie every public input into the constraint system
+        for i in 0..inputs.len() {
+            let a = vec![(Fr::one(), Var::Input(i))];
+            let b = random_terms(rng, inputs.len(), witnesses.len());
+            let out = eval(&a, &inputs, &witnesses) * eval(&b, &inputs, &witnesses);
+            witnesses.push(out);
+            gates.push((a, b));
+        }
+
+        Self {
+            inputs,
+            witnesses,
+            gates,
+        }
+    }
+
+    fn public_signals(&self) -> Vec<String> {
+        self.inputs.iter().map(|x| x.to_string()).collect()
+    }
+}
+
+fn random_terms(rng: &mut StdRng, num_inputs: usize, num_witnesses: usize) -> Terms {
+    (0..rng.gen_range(1..=3))
+        .map(|_| {
+            let var = match rng.gen_range(0..3) {
+                0 => Var::One,
+                1 if num_inputs > 0 => Var::Input(rng.gen_range(0..num_inputs)),
+                _ => Var::Witness(rng.gen_range(0..num_witnesses)),
+            };
+            (Fr::rand(rng), var)
+        })
+        .collect()
+}
+
+fn eval(terms: &Terms, inputs: &[Fr], witnesses: &[Fr]) -> Fr {
+    terms
+        .iter()
+        .map(|(coeff, var)| {
+            *coeff
+                * match var {
+                    Var::One => Fr::one(),
+                    Var::Input(i) => inputs[*i],
+                    Var::Witness(i) => witnesses[*i],
+                }
+        })
+        .sum()
+}
+
+impl ConstraintSynthesizer<Fr> for RandomCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|v| cs.new_input_variable(|| Ok(*v)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let witnesses = self
+            .witnesses
+            .iter()
+            .map(|v| cs.new_witness_variable(|| Ok(*v)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let free = self.witnesses.len() - self.gates.len();
+        let lc = |terms: &Terms| {
+            let mut lc = LinearCombination::<Fr>::zero();
+            for (coeff, var) in terms {
+                let v = match var {
+                    Var::One => Variable::One,
+                    Var::Input(i) => inputs[*i],
+                    Var::Witness(i) => witnesses[*i],
+                };
+                lc += (*coeff, v);
+            }
+            lc
+        };
+        for (i, (a, b)) in self.gates.iter().enumerate() {
+            let c = LinearCombination::from((Fr::one(), witnesses[free + i]));
+            cs.enforce_constraint(lc(a), lc(b), c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Both verifiers on the same statement; panics if they disagree.
+/// Returns the shared verdict, treating errors as rejection.
+fn differential(
+    vk: &VerifyingKey<Bn254>,
+    proof: &Proof<Bn254>,
+    inputs: &[Fr],
+    signals: &[String],
+    context: &str,
+) -> bool {
+    let ours = verify_groth16_proof(&vk_to_contract(vk), &proof_to_contract(proof), signals)
+        .unwrap_or(false);
+    let reference = ArkGroth16::<Bn254>::verify(vk, inputs, proof).unwrap_or(false);
+    assert_eq!(ours, reference, "verifiers disagree: {context}");
+    ours
+}
+
+fn g1_shift(point: G1Affine) -> G1Affine {
+    (point + G1Affine::generator()).into()
+}
+
+fn g2_shift(point: G2Affine) -> G2Affine {
+    (point + G2Affine::generator()).into()
+}
+
+/// Verify an honest proof, then every mutation, under both verifiers.
+fn check_case(rng: &mut StdRng, num_inputs: usize, case: usize) {
+    let circuit = RandomCircuit::generate(rng, num_inputs);
+    let (pk, vk) = ArkGroth16::<Bn254>::circuit_specific_setup(circuit.clone(), rng).unwrap();
+    let proof = ArkGroth16::<Bn254>::prove(&pk, circuit.clone(), rng).unwrap();
+    let inputs = circuit.inputs.clone();
+    let signals = circuit.public_signals();
+    let ctx = |what: &str| format!("case {case}, {num_inputs} inputs, {what}");
+
+    assert!(
+        differential(&vk, &proof, &inputs, &signals, &ctx("honest proof")),
+        "{}",
+        ctx("honest proof rejected")
+    );
+
+    // Proof coordinates, kept on-curve so both verifiers reach the pairing
+    let proofs = [
+        (
+            "A + G1",
+            Proof {
+                a: g1_shift(proof.a),
+                ..proof.clone()
+            },
+        ),
+        (
+            "-A",
+            Proof {
+                a: -proof.a,
+                ..proof.clone()
+            },
+        ),
+        (
+            "B + G2",
+            Proof {
+                b: g2_shift(proof.b),
+                ..proof.clone()
+            },
+        ),
+        (
+            "C + G1",
+            Proof {
+                c: g1_shift(proof.c),
+                ..proof.clone()
+            },
+        ),
+        (
+            "A <-> C",
+            Proof {
+                a: proof.c,
+                c: proof.a,
+                ..proof.clone()
+            },
+        ),
+    ];
+    for (what, mutated) in &proofs {
+        assert!(
+            !differential(&vk, mutated, &inputs, &signals, &ctx(what)),
+            "{}",
+            ctx(what)
+        );
+    }
+
+    // Public signals
+    if num_inputs > 0 {
+        let i = rng.gen_range(0..num_inputs);
+        let mut bad_inputs = inputs.clone();
+        bad_inputs[i] += Fr::one();
+        let bad_signals: Vec<String> = bad_inputs.iter().map(|x| x.to_string()).collect();
+        let what = format!("signal {i} + 1");
+        assert!(
+            !differential(&vk, &proof, &bad_inputs, &bad_signals, &ctx(&what)),
+            "{}",
+            ctx(&what)
+        );
+
+        let mut reordered = inputs.clone();
+        reordered.rotate_left(1);
+        if reordered != inputs {
+            let reordered_signals: Vec<String> = reordered.iter().map(|x| x.to_string()).collect();
+            assert!(
+                !differential(
+                    &vk,
+                    &proof,
+                    &reordered,
+                    &reordered_signals,
+                    &ctx("rotated signals")
+                ),
+                "{}",
+                ctx("rotated signals")
+            );
+        }
+    }
+
+    // Wrong signal count: ours errors, arkworks rejects
+    let mut extra = signals.clone();
+    extra.push("1".into());
+    assert!(
+        verify_groth16_proof(&vk_to_contract(&vk), &proof_to_contract(&proof), &extra).is_err(),
+        "{}",
+        ctx("extra signal accepted")
+    );
+
+    // Verification key points
+    let mut vks = vec![
+        (
+            "alpha + G1",
+            VerifyingKey {
+                alpha_g1: g1_shift(vk.alpha_g1),
+                ..vk.clone()
+            },
+        ),
+        (
+            "beta + G2",
+            VerifyingKey {
+                beta_g2: g2_shift(vk.beta_g2),
+                ..vk.clone()
+            },
+        ),
+        (
+            "gamma + G2",
+            VerifyingKey {
+                gamma_g2: g2_shift(vk.gamma_g2),
+                ..vk.clone()
+            },
+        ),
+        (
+            "delta + G2",
+            VerifyingKey {
+                delta_g2: g2_shift(vk.delta_g2),
+                ..vk.clone()
+            },
+        ),
+        (
+            "gamma <-> delta",
+            VerifyingKey {
+                gamma_g2: vk.delta_g2,
+                delta_g2: vk.gamma_g2,
+                ..vk.clone()
+            },
+        ),
+    ];
+    let i = rng.gen_range(0..vk.gamma_abc_g1.len());
+    let mut ic = vk.gamma_abc_g1.clone();
+    ic[i] = g1_shift(ic[i]);
+    vks.push((
+        "IC + G1",
+        VerifyingKey {
+            gamma_abc_g1: ic,
+            ..vk.clone()
+        },
+    ));
+    for (what, mutated) in &vks {
+        // Shifting an IC point paired with a zero input leaves vk_x unchanged
+        let expect_valid = *what == "IC + G1" && i > 0 && inputs[i - 1] == Fr::from(0u64);
+        let verdict = differential(mutated, &proof, &inputs, &signals, &ctx(what));
+        assert_eq!(verdict, expect_valid, "{}", ctx(what));
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+#[test]
+fn differential_random_circuits() {
+    let seed = env_or("GROTH16_DIFF_SEED", DEFAULT_SEED);
+    let cases = env_or("GROTH16_DIFF_CASES", DEFAULT_CASES as u64) as usize;
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for case in 0..cases {
+        let num_inputs = EDGE_INPUT_COUNTS
+            .get(case)
+            .copied()
+            .unwrap_or_else(|| rng.gen_range(0..=MAX_PUBLIC_INPUTS));
+        check_case(&mut rng, num_inputs, case);
+    }
+}
+
+/// String-level corruption produces off-curve or unparsable points, which the
+/// contract must reject before pairing (arkworks has no equivalent input).
+#[test]
+fn corrupted_encodings_rejected() {
+    let mut rng = StdRng::seed_from_u64(DEFAULT_SEED);
+    let circuit = RandomCircuit::generate(&mut rng, 2);
+    let (pk, vk) = ArkGroth16::<Bn254>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+    let proof = ArkGroth16::<Bn254>::prove(&pk, circuit.clone(), &mut rng).unwrap();
+    let vk = vk_to_contract(&vk);
+    let proof = proof_to_contract(&proof);
+    let signals = circuit.public_signals();
+
+    // Point coordinates live in the base field, not the scalar field
+    let bump = |s: &str| (s.parse::<Fq>().unwrap() + Fq::one()).to_string();
+    let mut mutations = Vec::new();
+    for coord in 0..2 {
+        let mut p = proof.clone();
+        p.pi_a[coord] = bump(&p.pi_a[coord]);
+        mutations.push((format!("pi_a[{coord}] + 1"), p));
+
+        let mut p = proof.clone();
+        p.pi_c[coord] = bump(&p.pi_c[coord]);
+        mutations.push((format!("pi_c[{coord}] + 1"), p));
+
+        for half in 0..2 {
+            let mut p = proof.clone();
+            p.pi_b[coord][half] = bump(&p.pi_b[coord][half]);
+            mutations.push((format!("pi_b[{coord}][{half}] + 1"), p));
+        }
+    }
+    let mut p = proof.clone();
+    p.pi_a[0] = "not-a-number".into();
+    mutations.push(("pi_a[0] garbage".into(), p));
+
+    for (what, mutated) in mutations {
+        let result = verify_groth16_proof(&vk, &mutated, &signals);
+        assert!(!result.unwrap_or(false), "{what} accepted");
+    }
+
+    let mut bad_vk = vk.clone();
+    bad_vk.alpha[1] = bump(&bad_vk.alpha[1]);
+    assert!(
+        verify_groth16_proof(&bad_vk, &proof, &signals).is_err(),
+        "off-curve alpha accepted"
+    );
+}
@@ -1,3 +1,10 @@
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_groth16::Groth16 as ArkGroth16;
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError,
+};
+use ark_snark::SNARK;
+use ark_std::rand::SeedableRng;
 use near_sdk::test_utils::VMContextBuilder;
 use near_sdk::{testing_env, AccountId, NearToken};
 use zk_verifier::*;
@@ -41,27 +48,48 @@ fn mock_vk() -> VerificationKey {
     VerificationKey {
         alpha: vec!["1".into(), "2".into()],
         beta: vec![
-            vec!["10857046999023057135944570762232829481370756359578518086990519993285655852781".into(),
-                 "11559732032986387107991004021392285783925812861821192530917403151452391805634".into()],
-            vec!["8495653923123431417604973247489272438418190587263600148770280649306958101930".into(),
-                 "4082367875863433681332203403145435568316851327593401208105741076214120093531".into()],
+            vec![
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781"
+                    .into(),
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                    .into(),
+            ],
+            vec![
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930"
+                    .into(),
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                    .into(),
+            ],
         ],
         gamma: vec![
-            vec!["10857046999023057135944570762232829481370756359578518086990519993285655852781".into(),
-                 "11559732032986387107991004021392285783925812861821192530917403151452391805634".into()],
-            vec!["8495653923123431417604973247489272438418190587263600148770280649306958101930".into(),
-                 "4082367875863433681332203403145435568316851327593401208105741076214120093531".into()],
+            vec![
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781"
+                    .into(),
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                    .into(),
+            ],
+            vec![
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930"
+                    .into(),
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                    .into(),
+            ],
         ],
         delta: vec![
-            vec!["10857046999023057135944570762232829481370756359578518086990519993285655852781".into(),
-                 "11559732032986387107991004021392285783925812861821192530917403151452391805634".into()],
-            vec!["8495653923123431417604973247489272438418190587263600148770280649306958101930".into(),
-                 "4082367875863433681332203403145435568316851327593401208105741076214120093531".into()],
-        ],
-        ic: vec![
-            vec!["1".into(), "2".into()],
-            vec!["1".into(), "2".into()],
+            vec![
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781"
+                    .into(),
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                    .into(),
+            ],
+            vec![
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930"
+                    .into(),
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                    .into(),
+            ],
         ],
+        ic: vec![vec!["1".into(), "2".into()], vec!["1".into(), "2".into()]],
     }
 }
 
@@ -213,7 +241,10 @@ fn revoke_nonexistent_credential() {
     contract.revoke_credential("cred-fake".into(), "test".into());
     assert!(contract.is_credential_revoked("cred-fake".into()));
     // is_credential_valid should return Some(false) for a revoked ID
-    assert_eq!(contract.is_credential_valid("cred-fake".into()), Some(false));
+    assert_eq!(
+        contract.is_credential_valid("cred-fake".into()),
+        Some(false)
+    );
 }
 
 // ==========================================================================
@@ -302,3 +333,1813 @@ fn update_existing_vk() {
     let stats = contract.get_stats();
     assert_eq!(stats.verification_keys_registered, 1);
 }
+
+// ==========================================================================
+// Multisig approval for sensitive admin actions
+// ==========================================================================
+
+fn carol() -> AccountId {
+    "carol.testnet".parse().unwrap()
+}
+
+fn enable_multisig(contract: &mut ZKVerifier, threshold: u32) {
+    setup(&owner());
+    contract.set_multisig(MultisigConfig {
+        approvers: vec![alice(), bob(), carol()],
+        threshold,
+        proposal_ttl_secs: 3600,
+    });
+}
+
+fn setup_at(predecessor: &AccountId, timestamp_secs: u64) {
+    let context = VMContextBuilder::new()
+        .predecessor_account_id(predecessor.clone())
+        .block_timestamp(timestamp_secs * 1_000_000_000)
+        .block_height(100)
+        .build();
+    testing_env!(context);
+}
+
+#[test]
+fn multisig_executes_after_threshold() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    let id = contract.propose_action(AdminAction::SetPaused { paused: true });
+    assert!(!contract.get_config().is_paused);
+    assert_eq!(contract.get_proposal(id).unwrap().approvals, vec![alice()]);
+
+    setup(&bob());
+    assert!(contract.approve_proposal(id));
+    assert!(contract.get_config().is_paused);
+    assert!(contract.get_proposal(id).is_none());
+}
+
+#[test]
+fn multisig_vk_proposal() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    let id = contract.propose_action(AdminAction::SetVerificationKey {
+        circuit_type: CircuitType::VerifiedBuilder,
+        vk: mock_vk(),
+    });
+    assert!(!contract.has_verification_key(CircuitType::VerifiedBuilder));
+
+    setup(&carol());
+    contract.approve_proposal(id);
+    assert!(contract.has_verification_key(CircuitType::VerifiedBuilder));
+}
+
+#[test]
+#[should_panic(expected = "Multisig required")]
+fn multisig_blocks_direct_set_paused() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&owner());
+    contract.set_paused(true);
+}
+
+#[test]
+#[should_panic(expected = "Multisig required")]
+fn multisig_blocks_direct_mass_revocation() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&owner());
+    contract.revoke_credentials(vec!["cred-a".into(), "cred-b".into()], "compromised".into());
+}
+
+#[test]
+#[should_panic(expected = "already approved")]
+fn multisig_rejects_duplicate_approval() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    let id = contract.propose_action(AdminAction::SetPaused { paused: true });
+    contract.approve_proposal(id);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn multisig_rejects_non_approver() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&owner());
+    contract.propose_action(AdminAction::SetPaused { paused: true });
+}
+
+#[test]
+#[should_panic(expected = "Proposal has expired")]
+fn multisig_proposal_expires() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    let id = contract.propose_action(AdminAction::SetPaused { paused: true });
+
+    setup_at(&bob(), 1_700_000_000 + 3600);
+    contract.approve_proposal(id);
+}
+
+#[test]
+fn multisig_prunes_expired_proposals() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    contract.propose_action(AdminAction::SetPaused { paused: true });
+    contract.propose_action(AdminAction::SetPaused { paused: false });
+    assert_eq!(contract.get_pending_proposals(None, None).len(), 2);
+
+    setup_at(&bob(), 1_700_000_000 + 7200);
+    assert!(contract.get_pending_proposals(None, None).is_empty());
+    assert_eq!(contract.prune_expired_proposals(Some(1)), 1);
+    assert_eq!(contract.prune_expired_proposals(None), 1);
+    assert!(contract.get_proposal(1).is_none());
+}
+
+#[test]
+fn multisig_mass_revocation_and_disable() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 1);
+
+    // Threshold 1 executes on proposal
+    setup(&alice());
+    contract.propose_action(AdminAction::RevokeCredentials {
+        credential_ids: vec!["cred-a".into(), "cred-b".into()],
+        reason: "compromised issuer".into(),
+    });
+    assert!(contract.is_credential_revoked("cred-a".into()));
+    assert!(contract.is_credential_revoked("cred-b".into()));
+
+    contract.propose_action(AdminAction::SetMultisig { config: None });
+    assert!(contract.get_multisig_config().is_none());
+
+    // Direct admin calls work again
+    setup(&owner());
+    contract.set_paused(true);
+    assert!(contract.get_config().is_paused);
+}
+
+#[test]
+#[should_panic(expected = "Invalid multisig config")]
+fn multisig_rejects_zero_threshold() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 0);
+}
+
+#[test]
+#[should_panic(expected = "IC array must not be empty")]
+fn multisig_rejects_invalid_vk_at_proposal() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    let mut vk = mock_vk();
+    vk.ic.clear();
+    contract.propose_action(AdminAction::SetVerificationKey {
+        circuit_type: CircuitType::VerifiedBuilder,
+        vk,
+    });
+}
+
+#[test]
+#[should_panic(expected = "Contract is paused")]
+fn multisig_rejects_vk_proposal_while_paused() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 1);
+
+    setup(&alice());
+    contract.propose_action(AdminAction::SetPaused { paused: true });
+    contract.propose_action(AdminAction::SetVerificationKey {
+        circuit_type: CircuitType::VerifiedBuilder,
+        vk: mock_vk(),
+    });
+}
+
+#[test]
+#[should_panic(expected = "Invalid multisig config")]
+fn multisig_rejects_invalid_config_at_proposal() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    enable_multisig(&mut contract, 2);
+
+    setup(&alice());
+    contract.propose_action(AdminAction::SetMultisig {
+        config: Some(MultisigConfig {
+            approvers: vec![alice(), bob()],
+            threshold: 3,
+            proposal_ttl_secs: 3600,
+        }),
+    });
+}
+
+// ==========================================================================
+// Verification fees and treasury
+// ==========================================================================
+
+/// Structurally valid proof input; the mock VK makes it fail verification.
+fn mock_input(store_credential: bool) -> VerifyProofInput {
+    VerifyProofInput {
+        circuit_type: CircuitType::VerifiedBuilder,
+        proof: Groth16Proof {
+            pi_a: vec!["1".into(), "2".into(), "1".into()],
+            pi_b: vec![
+                vec!["1".into(), "0".into()],
+                vec!["0".into(), "1".into()],
+                vec!["1".into(), "0".into()],
+            ],
+            pi_c: vec!["1".into(), "2".into(), "1".into()],
+        },
+        public_signals: vec!["1".into()],
+        store_credential,
+        custom_expiration: None,
+        claim: None,
+        issue_to: None,
+        privacy: None,
+    }
+}
+
+#[test]
+fn verification_fee_collected_for_invalid_proof() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_verification_fee(CircuitType::VerifiedBuilder, 1_000.into());
+    assert_eq!(
+        contract
+            .get_verification_fee(CircuitType::VerifiedBuilder)
+            .0,
+        1_000
+    );
+    assert_eq!(
+        contract
+            .get_verification_fee(CircuitType::TeamAttestation)
+            .0,
+        0
+    );
+
+    // Overpay; the excess is refunded and only the fee is kept
+    setup_with_deposit(&alice(), 5_000);
+    let result = contract.verify_proof(mock_input(true));
+    assert!(!result.valid);
+
+    let stats = contract.get_stats();
+    assert_eq!(stats.total_fees_collected.0, 1_000);
+    assert_eq!(stats.treasury_balance.0, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient deposit: required 1000")]
+fn verification_fee_required() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_verification_fee(CircuitType::VerifiedBuilder, 1_000.into());
+
+    setup_with_deposit(&alice(), 999);
+    contract.verify_proof(mock_input(false));
+}
+
+#[test]
+fn treasury_withdrawal_to_beneficiary() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_verification_fee(CircuitType::VerifiedBuilder, 1_000.into());
+
+    setup_with_deposit(&alice(), 1_000);
+    contract.verify_proof(mock_input(false));
+    contract.verify_proof(mock_input(false));
+
+    setup(&owner());
+    contract.set_treasury_beneficiary(bob());
+    assert_eq!(contract.get_config().treasury_beneficiary, Some(bob()));
+    let _ = contract.withdraw_treasury(Some(500.into()));
+
+    let stats = contract.get_stats();
+    assert_eq!(stats.treasury_balance.0, 1_500);
+    assert_eq!(stats.total_fees_collected.0, 2_000);
+}
+
+#[test]
+#[should_panic(expected = "Treasury beneficiary is not set")]
+fn treasury_withdrawal_requires_beneficiary() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    let _ = contract.withdraw_treasury(None);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient treasury")]
+fn treasury_withdrawal_over_balance() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_treasury_beneficiary(bob());
+    let _ = contract.withdraw_treasury(Some(1.into()));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn set_verification_fee_unauthorized() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+
+    setup(&alice());
+    contract.set_verification_fee(CircuitType::VerifiedBuilder, 1_000.into());
+}
+
+// ==========================================================================
+// Rate limiting on verify_proof
+// ==========================================================================
+
+fn setup_rate_limited_contract() -> ZKVerifier {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_rate_limit(Some(RateLimitConfig {
+        max_calls: 2,
+        window_secs: 60,
+    }));
+    contract
+}
+
+#[test]
+fn rate_limit_counts_calls_per_window() {
+    let mut contract = setup_rate_limited_contract();
+
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+    contract.verify_proof(mock_input(false));
+    assert_eq!(contract.get_rate_limit_bucket(alice()).unwrap().count, 2);
+
+    // A new window resets the counter
+    setup_at(&alice(), 1_700_000_000 + 60);
+    contract.verify_proof(mock_input(false));
+    let bucket = contract.get_rate_limit_bucket(alice()).unwrap();
+    assert_eq!(bucket.count, 1);
+    assert_eq!(bucket.window_start, 1_700_000_000 + 60);
+    assert_eq!(contract.get_stats().total_verifications, 3);
+}
+
+#[test]
+#[should_panic(expected = "Rate limited: retry after 1700000060")]
+fn rate_limit_rejects_over_quota() {
+    let mut contract = setup_rate_limited_contract();
+
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+    contract.verify_proof(mock_input(false));
+    contract.verify_proof(mock_input(false));
+}
+
+#[test]
+fn rate_limit_exempt_account() {
+    let mut contract = setup_rate_limited_contract();
+    contract.add_rate_limit_exemption(bob());
+    assert!(contract.is_rate_limit_exempt(bob()));
+
+    setup(&bob());
+    for _ in 0..5 {
+        contract.verify_proof(mock_input(false));
+    }
+    assert!(contract.get_rate_limit_bucket(bob()).is_none());
+}
+
+#[test]
+fn rate_limit_prunes_stale_buckets() {
+    let mut contract = setup_rate_limited_contract();
+
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+    setup(&bob());
+    contract.verify_proof(mock_input(false));
+
+    // Carol's call after the window lazily prunes alice's and bob's buckets
+    setup_at(&carol(), 1_700_000_000 + 120);
+    contract.verify_proof(mock_input(false));
+    assert!(contract.get_rate_limit_bucket(alice()).is_none());
+    assert!(contract.get_rate_limit_bucket(bob()).is_none());
+    assert!(contract.get_rate_limit_bucket(carol()).is_some());
+}
+
+#[test]
+fn rate_limit_config_change_resets_buckets_lazily() {
+    let mut contract = setup_rate_limited_contract();
+
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+    contract.verify_proof(mock_input(false));
+
+    // The new config voids alice's full bucket without touching it
+    setup(&owner());
+    contract.set_rate_limit(Some(RateLimitConfig {
+        max_calls: 3,
+        window_secs: 600,
+    }));
+    assert!(contract.get_rate_limit_bucket(alice()).is_none());
+
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+    let bucket = contract.get_rate_limit_bucket(alice()).unwrap();
+    assert_eq!(bucket.count, 1);
+}
+
+#[test]
+fn rate_limit_disabled_buckets_are_prunable() {
+    let mut contract = setup_rate_limited_contract();
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+
+    setup(&owner());
+    contract.set_rate_limit(None);
+    assert_eq!(contract.prune_rate_limit_buckets(None), 1);
+    assert_eq!(contract.prune_rate_limit_buckets(None), 0);
+}
+
+#[test]
+#[should_panic(expected = "Invalid rate limit config")]
+fn rate_limit_rejects_zero_window() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_rate_limit(Some(RateLimitConfig {
+        max_calls: 1,
+        window_secs: 0,
+    }));
+}
+
+// ==========================================================================
+// Per-circuit and per-method pause controls
+// ==========================================================================
+
+#[test]
+fn circuit_pause_shown_in_config() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+
+    contract.set_circuit_paused(CircuitType::GrantTrackRecord, true);
+    contract.set_method_paused(MethodGroup::Store, true);
+    let cfg = contract.get_config();
+    assert_eq!(cfg.paused_circuits, vec![CircuitType::GrantTrackRecord]);
+    assert_eq!(cfg.paused_methods, vec![MethodGroup::Store]);
+    assert!(!cfg.is_paused);
+
+    contract.set_circuit_paused(CircuitType::GrantTrackRecord, false);
+    contract.set_method_paused(MethodGroup::Store, false);
+    let cfg = contract.get_config();
+    assert!(cfg.paused_circuits.is_empty());
+    assert!(cfg.paused_methods.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Circuit is paused: verified-builder")]
+fn paused_circuit_blocks_verification() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_circuit_paused(CircuitType::VerifiedBuilder, true);
+
+    setup(&alice());
+    contract.verify_proof(mock_input(false));
+}
+
+#[test]
+fn paused_circuit_leaves_other_circuits_running() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_circuit_paused(CircuitType::GrantTrackRecord, true);
+
+    setup(&alice());
+    let result = contract.verify_proof(mock_input(false));
+    assert!(!result.valid);
+    assert_eq!(contract.get_stats().total_verifications, 1);
+}
+
+#[test]
+#[should_panic(expected = "Method group is paused: store")]
+fn paused_store_blocks_credential_storage() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, mock_vk());
+    contract.set_method_paused(MethodGroup::Store, true);
+
+    setup(&alice());
+    contract.verify_proof(mock_input(true));
+}
+
+#[test]
+fn paused_verify_leaves_cleanup_running() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_method_paused(MethodGroup::Verify, true);
+    contract.set_circuit_paused(CircuitType::VerifiedBuilder, true);
+
+    contract.revoke_credential("cred-fake".into(), "test".into());
+    assert!(contract.is_credential_revoked("cred-fake".into()));
+
+    setup(&alice());
+    assert!(!contract.remove_credential("cred-missing".into()));
+}
+
+#[test]
+#[should_panic(expected = "Method group is paused: revoke")]
+fn paused_revoke_blocks_revocation() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_method_paused(MethodGroup::Revoke, true);
+    contract.revoke_credential("cred-fake".into(), "test".into());
+}
+
+#[test]
+#[should_panic(expected = "Contract is paused")]
+fn global_pause_overrides_method_flags() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_paused(true);
+
+    setup(&alice());
+    contract.remove_credential("cred-missing".into());
+}
+
+// ==========================================================================
+// Real-proof helpers (SquareCircuit: x * x == y)
+// ==========================================================================
+
+#[derive(Clone)]
+struct SquareCircuit {
+    x: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for SquareCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        use ark_ff::One;
+
+        let x_val = self.x.unwrap_or_default();
+        let x_var = cs.new_witness_variable(|| Ok(x_val))?;
+        let y_var = cs.new_input_variable(|| Ok(x_val * x_val))?;
+        cs.enforce_constraint(
+            LinearCombination::from((Fr::one(), x_var)),
+            LinearCombination::from((Fr::one(), x_var)),
+            LinearCombination::from((Fr::one(), y_var)),
+        )?;
+        Ok(())
+    }
+}
+
+fn g1_to_strings(point: &G1Affine) -> Vec<String> {
+    vec![point.x.to_string(), point.y.to_string()]
+}
+
+/// snarkjs orders Fq2 components as [c1, c0].
+fn g2_to_strings(point: &G2Affine) -> Vec<Vec<String>> {
+    vec![
+        vec![point.x.c1.to_string(), point.x.c0.to_string()],
+        vec![point.y.c1.to_string(), point.y.c0.to_string()],
+    ]
+}
+
+/// Real VK and proof for x = 3 (public signal "9"), with a fixed seed.
+fn square_fixture() -> (VerificationKey, Groth16Proof) {
+    let mut rng = ark_std::rand::rngs::StdRng::seed_from_u64(42);
+    let (pk, vk) =
+        ArkGroth16::<Bn254>::circuit_specific_setup(SquareCircuit { x: None }, &mut rng).unwrap();
+    let proof = ArkGroth16::<Bn254>::prove(
+        &pk,
+        SquareCircuit {
+            x: Some(Fr::from(3u64)),
+        },
+        &mut rng,
+    )
+    .unwrap();
+
+    let contract_vk = VerificationKey {
+        alpha: g1_to_strings(&vk.alpha_g1),
+        beta: g2_to_strings(&vk.beta_g2),
+        gamma: g2_to_strings(&vk.gamma_g2),
+        delta: g2_to_strings(&vk.delta_g2),
+        ic: vk.gamma_abc_g1.iter().map(g1_to_strings).collect(),
+    };
+    let contract_proof = Groth16Proof {
+        pi_a: g1_to_strings(&proof.a),
+        pi_b: g2_to_strings(&proof.b),
+        pi_c: g1_to_strings(&proof.c),
+    };
+    (contract_vk, contract_proof)
+}
+
+fn real_input(
+    circuit_type: CircuitType,
+    proof: &Groth16Proof,
+    expiration: Option<u64>,
+) -> VerifyProofInput {
+    VerifyProofInput {
+        circuit_type,
+        proof: proof.clone(),
+        public_signals: vec!["9".into()],
+        store_credential: true,
+        custom_expiration: expiration,
+        claim: None,
+        issue_to: None,
+        privacy: None,
+    }
+}
+
+/// Store a credential for `holder` from a real proof and return its ID.
+fn store_real_credential(
+    contract: &mut ZKVerifier,
+    holder: &AccountId,
+    circuit_type: CircuitType,
+    proof: &Groth16Proof,
+    expiration: Option<u64>,
+) -> String {
+    setup_with_deposit(holder, ContractConfig::DEFAULT_STORAGE_COST);
+    let result = contract.verify_proof(real_input(circuit_type, proof, expiration));
+    assert!(result.valid);
+    result.credential_id.unwrap()
+}
+
+// ==========================================================================
+// Global and per-circuit credential listing
+// ==========================================================================
+
+fn setup_listing_contract() -> (ZKVerifier, Groth16Proof) {
+    let (vk, proof) = square_fixture();
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, vk.clone());
+    contract.set_verification_key(CircuitType::GrantTrackRecord, vk);
+    (contract, proof)
+}
+
+#[test]
+fn list_credentials_in_issue_order_with_cursor() {
+    let (mut contract, proof) = setup_listing_contract();
+    let a = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    let b = store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+    let c = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+
+    let page = contract.list_credentials(None, Some(2), None);
+    let ids: Vec<_> = page.credentials.iter().map(|c| c.id.clone()).collect();
+    assert_eq!(ids, vec![a, b.clone()]);
+    let cursor = page.next_cursor.unwrap();
+
+    let page = contract.list_credentials(Some(cursor), Some(2), None);
+    let ids: Vec<_> = page.credentials.iter().map(|c| c.id.clone()).collect();
+    assert_eq!(ids, vec![c.clone()]);
+    assert!(page.next_cursor.is_none());
+
+    let page =
+        contract.list_credentials_by_circuit(CircuitType::GrantTrackRecord, None, None, None);
+    let ids: Vec<_> = page.credentials.iter().map(|c| c.id.clone()).collect();
+    assert_eq!(ids, vec![b, c]);
+    assert!(contract
+        .list_credentials_by_circuit(CircuitType::TeamAttestation, None, None, None)
+        .credentials
+        .is_empty());
+}
+
+#[test]
+fn listing_indexes_follow_remove_and_revoke() {
+    let (mut contract, proof) = setup_listing_contract();
+    let a = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+    let b = store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+    let c = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+
+    setup(&alice());
+    assert!(contract.remove_credential(a));
+    setup(&owner());
+    contract.revoke_credential(b, "fraud".into());
+
+    let page =
+        contract.list_credentials_by_circuit(CircuitType::GrantTrackRecord, None, None, None);
+    let ids: Vec<_> = page.credentials.iter().map(|c| c.id.clone()).collect();
+    assert_eq!(ids, vec![c]);
+    assert_eq!(
+        contract
+            .list_credentials(None, None, None)
+            .credentials
+            .len(),
+        1
+    );
+    assert_eq!(contract.get_stats().total_credentials, 1);
+}
+
+#[test]
+fn listing_filters_by_vk_version_and_expiry() {
+    let (mut contract, proof) = setup_listing_contract();
+    assert_eq!(
+        contract.get_verification_key_version(CircuitType::VerifiedBuilder),
+        1
+    );
+    let v1 = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        Some(10),
+    );
+
+    // Rotating the VK bumps the version recorded on new credentials
+    let (vk, _) = square_fixture();
+    setup(&owner());
+    contract.set_verification_key(CircuitType::VerifiedBuilder, vk);
+    assert_eq!(
+        contract.get_verification_key_version(CircuitType::VerifiedBuilder),
+        2
+    );
+    let v2 = store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+
+    let filter = CredentialFilter {
+        vk_version: Some(1),
+        ..Default::default()
+    };
+    let page = contract.list_credentials(None, None, Some(filter));
+    assert_eq!(page.credentials.len(), 1);
+    assert_eq!(page.credentials[0].id, v1);
+
+    // After v1 expires it is hidden unless expired credentials are requested
+    setup_at(&owner(), 1_700_000_000 + 11);
+    let page = contract.list_credentials(None, None, None);
+    assert_eq!(page.credentials.len(), 1);
+    assert_eq!(page.credentials[0].id, v2);
+    let filter = CredentialFilter {
+        include_expired: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        contract
+            .list_credentials(None, None, Some(filter))
+            .credentials
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn prune_expired_credentials_cleans_indexes() {
+    let (mut contract, proof) = setup_listing_contract();
+    let expiring = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        Some(10),
+    );
+    let keep = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+
+    setup_at(&owner(), 1_700_000_000 + 11);
+    let result = contract.prune_expired_credentials(None, None);
+    assert_eq!(result.removed, 1);
+    assert!(result.next_cursor.is_none());
+
+    assert!(contract.get_credential(expiring).is_none());
+    let filter = CredentialFilter {
+        include_expired: true,
+        ..Default::default()
+    };
+    let page = contract.list_credentials_by_circuit(
+        CircuitType::VerifiedBuilder,
+        None,
+        None,
+        Some(filter),
+    );
+    assert_eq!(page.credentials.len(), 1);
+    assert_eq!(page.credentials[0].id, keep);
+    let owned = contract.get_credentials_by_owner(alice(), Some(true), None, None);
+    assert_eq!(owned.total, 1);
+}
+
+// ==========================================================================
+// Per-circuit verification metrics
+// ==========================================================================
+
+#[test]
+fn circuit_stats_track_outcomes_and_gas() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+
+    // Wrong signal: invalid proof, nothing stored
+    setup(&alice());
+    let mut input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    input.public_signals = vec!["10".into()];
+    assert!(!contract.verify_proof(input).valid);
+
+    setup(&owner());
+    contract.revoke_credential(id, "test".into());
+
+    let stats = contract.get_circuit_stats(CircuitType::VerifiedBuilder, None);
+    assert_eq!(stats.epoch, 0);
+    assert_eq!(stats.epoch_started_at, 1_700_000_000);
+    assert_eq!(stats.valid_proofs, 1);
+    assert_eq!(stats.invalid_proofs, 1);
+    assert_eq!(stats.credentials_issued, 1);
+    assert_eq!(stats.revocations, 1);
+    assert!(stats.min_gas_used <= stats.avg_gas_used);
+    assert!(stats.avg_gas_used <= stats.max_gas_used);
+
+    let other = contract.get_circuit_stats(CircuitType::GrantTrackRecord, None);
+    assert_eq!(other.valid_proofs + other.invalid_proofs, 0);
+}
+
+#[test]
+fn circuit_stats_epoch_reset() {
+    let (mut contract, proof) = setup_listing_contract();
+    store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+
+    setup_at(&owner(), 1_700_000_000 + 100);
+    assert_eq!(contract.reset_circuit_stats(), 1);
+    assert_eq!(contract.get_stats().stats_epoch, 1);
+
+    let current = contract.get_circuit_stats(CircuitType::VerifiedBuilder, None);
+    assert_eq!(current.epoch, 1);
+    assert_eq!(current.epoch_started_at, 1_700_000_000 + 100);
+    assert_eq!(current.valid_proofs, 0);
+
+    let previous = contract.get_circuit_stats(CircuitType::VerifiedBuilder, Some(0));
+    assert_eq!(previous.valid_proofs, 1);
+    assert_eq!(previous.credentials_issued, 1);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn circuit_stats_reset_owner_only() {
+    setup(&owner());
+    let mut contract = ZKVerifier::new(owner());
+
+    setup(&alice());
+    contract.reset_circuit_stats();
+}
+
+// ==========================================================================
+// Lookup by public-signal value
+// ==========================================================================
+
+fn signal_ids(page: &PaginatedCredentials) -> Vec<String> {
+    page.credentials.iter().map(|c| c.id.clone()).collect()
+}
+
+#[test]
+fn find_credentials_by_indexed_signal() {
+    let (mut contract, proof) = setup_listing_contract();
+    contract.set_signal_index(CircuitType::VerifiedBuilder, Some(0));
+    assert_eq!(
+        contract.get_signal_index(CircuitType::VerifiedBuilder),
+        Some(0)
+    );
+    assert_eq!(
+        contract.get_signal_index(CircuitType::GrantTrackRecord),
+        None
+    );
+
+    let a = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    let b = store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+
+    let page = contract.find_credentials_by_signal(
+        CircuitType::VerifiedBuilder,
+        0,
+        "9".into(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(signal_ids(&page), vec![a, b.clone()]);
+    assert_eq!(page.total, 2);
+    assert!(!page.has_more);
+
+    let page = contract.find_credentials_by_signal(
+        CircuitType::VerifiedBuilder,
+        0,
+        "9".into(),
+        None,
+        Some(1),
+        Some(1),
+    );
+    assert_eq!(signal_ids(&page), vec![b]);
+    assert!(!page.has_more);
+    assert!(
+        contract
+            .find_credentials_by_signal(
+                CircuitType::VerifiedBuilder,
+                0,
+                "9".into(),
+                None,
+                None,
+                Some(1)
+            )
+            .has_more
+    );
+
+    // Other values, positions and unindexed circuits have no matches
+    for (circuit_type, index, value) in [
+        (CircuitType::VerifiedBuilder, 0, "10"),
+        (CircuitType::VerifiedBuilder, 1, "9"),
+        (CircuitType::GrantTrackRecord, 0, "9"),
+    ] {
+        let page = contract.find_credentials_by_signal(
+            circuit_type,
+            index,
+            value.into(),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(page.total, 0);
+    }
+}
+
+#[test]
+fn signal_index_follows_remove_and_revoke() {
+    let (mut contract, proof) = setup_listing_contract();
+    contract.set_signal_index(CircuitType::VerifiedBuilder, Some(0));
+    let a = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    let b = store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+
+    setup(&alice());
+    assert!(contract.remove_credential(a));
+    let page = contract.find_credentials_by_signal(
+        CircuitType::VerifiedBuilder,
+        0,
+        "9".into(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(signal_ids(&page), vec![b.clone()]);
+
+    setup(&owner());
+    contract.revoke_credential(b, "compromised".into());
+    let page = contract.find_credentials_by_signal(
+        CircuitType::VerifiedBuilder,
+        0,
+        "9".into(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(page.total, 0);
+}
+
+#[test]
+fn signal_index_change_applies_to_new_credentials() {
+    let (mut contract, proof) = setup_listing_contract();
+    contract.set_signal_index(CircuitType::VerifiedBuilder, Some(0));
+    let a = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+
+    // Turning indexing off keeps existing entries but skips new credentials
+    setup(&owner());
+    contract.set_signal_index(CircuitType::VerifiedBuilder, None);
+    store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    let page = contract.find_credentials_by_signal(
+        CircuitType::VerifiedBuilder,
+        0,
+        "9".into(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(signal_ids(&page), vec![a.clone()]);
+
+    // A position past the signal count indexes nothing
+    setup(&owner());
+    contract.set_signal_index(CircuitType::VerifiedBuilder, Some(3));
+    let c = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    setup(&alice());
+    assert!(contract.remove_credential(c));
+    assert!(contract.remove_credential(a));
+    let page = contract.find_credentials_by_signal(
+        CircuitType::VerifiedBuilder,
+        0,
+        "9".into(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(page.total, 0);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn set_signal_index_unauthorized() {
+    let (mut contract, _) = setup_listing_contract();
+    setup(&alice());
+    contract.set_signal_index(CircuitType::VerifiedBuilder, Some(0));
+}
+
+// ==========================================================================
+// Issuing to another holder
+// ==========================================================================
+
+/// Alice submits a real proof storing a credential for Bob.
+fn issue_to_bob(contract: &mut ZKVerifier, proof: &Groth16Proof) -> VerificationResult {
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    let mut input = real_input(CircuitType::VerifiedBuilder, proof, None);
+    input.issue_to = Some(bob());
+    contract.verify_proof(input)
+}
+
+#[test]
+fn issued_credential_waits_for_holder_acceptance() {
+    let (mut contract, proof) = setup_listing_contract();
+    let result = issue_to_bob(&mut contract, &proof);
+    assert!(result.valid && result.pending);
+    let id = result.credential_id.unwrap();
+
+    assert!(contract.get_credential(id.clone()).is_none());
+    assert_eq!(contract.get_stats().total_credentials, 0);
+    let pending = contract.get_pending_credentials_for_holder(bob(), None, None);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].escrow.0, ContractConfig::DEFAULT_STORAGE_COST);
+    assert_eq!(pending[0].credential.issued_by, Some(alice()));
+
+    setup(&bob());
+    let credential = contract.accept_credential(id.clone());
+    assert_eq!(credential.owner, bob());
+    assert_eq!(credential.circuit_seq, 1);
+    assert!(contract.get_credential(id.clone()).is_some());
+    assert!(contract.get_pending_credential(id).is_none());
+    assert_eq!(contract.get_stats().total_credentials, 1);
+}
+
+#[test]
+fn holder_rejects_and_issuer_cancels_pending_credentials() {
+    let (mut contract, proof) = setup_listing_contract();
+    let first = issue_to_bob(&mut contract, &proof).credential_id.unwrap();
+    let second = issue_to_bob(&mut contract, &proof).credential_id.unwrap();
+
+    setup(&bob());
+    contract.reject_credential(first.clone());
+    setup(&alice());
+    contract.cancel_pending_credential(second.clone());
+
+    assert!(contract.get_pending_credential(first.clone()).is_none());
+    assert!(contract.get_pending_credential(second).is_none());
+    assert!(contract.get_credential(first).is_none());
+    assert!(contract
+        .get_pending_credentials_for_holder(bob(), None, None)
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn only_holder_can_accept_pending_credential() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = issue_to_bob(&mut contract, &proof).credential_id.unwrap();
+
+    setup(&carol());
+    contract.accept_credential(id);
+}
+
+#[test]
+#[should_panic(expected = "Circuit is paused: verified-builder")]
+fn paused_circuit_blocks_pending_acceptance() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = issue_to_bob(&mut contract, &proof).credential_id.unwrap();
+
+    setup(&owner());
+    contract.set_circuit_paused(CircuitType::VerifiedBuilder, true);
+    setup(&bob());
+    contract.accept_credential(id);
+}
+
+#[test]
+fn revoking_pending_credential_drops_it() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = issue_to_bob(&mut contract, &proof).credential_id.unwrap();
+
+    setup(&owner());
+    contract.revoke_credential(id.clone(), "compromised issuer".into());
+    assert!(contract.get_pending_credential(id.clone()).is_none());
+    assert!(contract
+        .get_pending_credentials_for_holder(bob(), None, None)
+        .is_empty());
+    assert_eq!(contract.is_credential_valid(id), Some(false));
+}
+
+#[test]
+#[should_panic(expected = "Credential has been revoked")]
+fn revoked_pending_credential_cannot_be_accepted() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = issue_to_bob(&mut contract, &proof).credential_id.unwrap();
+
+    setup(&owner());
+    contract.revoke_credential(id.clone(), "compromised issuer".into());
+    setup(&bob());
+    contract.accept_credential(id);
+}
+
+#[test]
+#[should_panic(expected = "Pending credential not found")]
+fn accept_unknown_pending_credential() {
+    let (mut contract, _) = setup_listing_contract();
+    setup(&bob());
+    contract.accept_credential("cred-missing".into());
+}
+
+#[test]
+fn auto_accept_stores_credential_directly() {
+    let (mut contract, proof) = setup_listing_contract();
+    setup(&bob());
+    contract.set_auto_accept(alice(), true);
+    assert!(contract.is_auto_accepted(bob(), alice()));
+
+    let result = issue_to_bob(&mut contract, &proof);
+    assert!(!result.pending);
+    let credential = contract
+        .get_credential(result.credential_id.unwrap())
+        .unwrap();
+    assert_eq!(credential.owner, bob());
+    assert_eq!(credential.issued_by, Some(alice()));
+
+    setup(&bob());
+    contract.set_auto_accept(alice(), false);
+    assert!(issue_to_bob(&mut contract, &proof).pending);
+}
+
+// ==========================================================================
+// Privacy-mode storage
+// ==========================================================================
+
+const SIGNALS_SALT: &str = "0f0e0d0c0b0a09080706050403020100";
+const OWNER_SALT: &str = "00112233445566778899aabbccddeeff";
+
+fn private_options() -> PrivacyOptions {
+    PrivacyOptions {
+        signals_salt: SIGNALS_SALT.into(),
+        owner_salt: OWNER_SALT.into(),
+    }
+}
+
+/// Alice stores a privacy-mode credential from a real proof.
+fn store_private_credential(contract: &mut ZKVerifier, proof: &Groth16Proof) -> String {
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    let mut input = real_input(CircuitType::VerifiedBuilder, proof, None);
+    input.privacy = Some(private_options());
+    let result = contract.verify_proof(input);
+    assert!(result.valid);
+    result.credential_id.unwrap()
+}
+
+#[test]
+fn private_credential_stores_only_commitment_and_owner_hash() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = store_private_credential(&mut contract, &proof);
+
+    assert!(contract.get_credential(id.clone()).is_none());
+    assert!(contract
+        .get_credentials_by_owner(alice(), None, None, None)
+        .credentials
+        .is_empty());
+    assert!(contract
+        .list_credentials(None, None, None)
+        .credentials
+        .is_empty());
+
+    let stored = contract.get_private_credential(id.clone()).unwrap();
+    assert!(stored.signals_commitment.starts_with("sha256:"));
+    assert_eq!(contract.is_credential_valid(id.clone()), Some(true));
+    assert_eq!(contract.get_stats().total_private_credentials, 1);
+    assert_eq!(contract.get_stats().total_credentials, 0);
+
+    let by_hash = contract.get_private_credentials_by_owner_hash(stored.owner_hash, None, None);
+    assert_eq!(by_hash.len(), 1);
+    assert_eq!(by_hash[0].id, id);
+}
+
+#[test]
+fn verify_credential_opening_checks_signals_and_salt() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = store_private_credential(&mut contract, &proof);
+
+    assert!(contract.verify_credential_opening(id.clone(), vec!["9".into()], SIGNALS_SALT.into()));
+    assert!(!contract.verify_credential_opening(
+        id.clone(),
+        vec!["10".into()],
+        SIGNALS_SALT.into()
+    ));
+    assert!(!contract.verify_credential_opening(id.clone(), vec!["9".into()], OWNER_SALT.into()));
+    assert!(!contract.verify_credential_opening(id, vec!["9".into()], "zz".into()));
+    assert!(!contract.verify_credential_opening(
+        "cred-missing".into(),
+        vec!["9".into()],
+        SIGNALS_SALT.into()
+    ));
+}
+
+#[test]
+fn private_credential_removal_requires_owner_salt() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = store_private_credential(&mut contract, &proof);
+
+    setup(&bob());
+    assert!(!contract.remove_private_credential(id.clone(), OWNER_SALT.into()));
+    setup(&alice());
+    assert!(!contract.remove_private_credential(id.clone(), SIGNALS_SALT.into()));
+    assert!(contract.remove_private_credential(id.clone(), OWNER_SALT.into()));
+    assert!(contract.get_private_credential(id).is_none());
+    assert_eq!(contract.get_stats().total_private_credentials, 0);
+}
+
+#[test]
+fn revoking_private_credential() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = store_private_credential(&mut contract, &proof);
+
+    setup(&owner());
+    contract.revoke_credential(id.clone(), "compromised".into());
+    assert!(contract.get_private_credential(id.clone()).is_none());
+    assert_eq!(contract.is_credential_valid(id), Some(false));
+}
+
+#[test]
+#[should_panic(expected = "Invalid privacy options")]
+fn private_credential_rejects_short_salt() {
+    let (mut contract, proof) = setup_listing_contract();
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    let mut input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    input.privacy = Some(PrivacyOptions {
+        signals_salt: "0001".into(),
+        owner_salt: OWNER_SALT.into(),
+    });
+    contract.verify_proof(input);
+}
+
+#[test]
+#[should_panic(expected = "issue_to is not supported in privacy mode")]
+fn private_credential_rejects_issue_to() {
+    let (mut contract, proof) = setup_listing_contract();
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    let mut input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    input.privacy = Some(private_options());
+    input.issue_to = Some(bob());
+    contract.verify_proof(input);
+}
+
+// ==========================================================================
+// Generated E2E fixtures (scripts/fixtures, see src/bin/generate_fixtures.rs)
+// ==========================================================================
+
+fn fixture_args(dir: &std::path::Path, circuit: &str, kind: &str) -> serde_json::Value {
+    let path = dir.join(format!("zk-{circuit}-{kind}.json"));
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+    serde_json::from_str(&content).unwrap()
+}
+
+fn fixture_input(dir: &std::path::Path, circuit: &str, kind: &str) -> VerifyProofInput {
+    serde_json::from_value(fixture_args(dir, circuit, kind)["input"].clone()).unwrap()
+}
+
+#[test]
+fn generated_fixtures_verify() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../scripts/fixtures");
+    let manifest = std::fs::read_to_string(dir.join("manifest.tsv")).unwrap();
+    let entries: Vec<Vec<&str>> = manifest
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.split('\t').collect())
+        .collect();
+    assert!(!entries.is_empty());
+
+    for entry in entries {
+        let (circuit, batch): (&str, usize) = (entry[0], entry[2].parse().unwrap());
+        setup(&owner());
+        let mut contract = ZKVerifier::new(owner());
+        let set_vk = fixture_args(&dir, circuit, "set-vk-args");
+        contract.set_verification_key(
+            serde_json::from_value(set_vk["circuit_type"].clone()).unwrap(),
+            serde_json::from_value(set_vk["vk"].clone()).unwrap(),
+        );
+        assert_eq!(set_vk["circuit_type"], entry[1]);
+
+        let view = fixture_input(&dir, circuit, "view-args");
+        assert!(contract.verify_proof_view(view).valid, "{circuit}: view");
+        let invalid = fixture_input(&dir, circuit, "verify-invalid-args");
+        assert!(
+            !contract.verify_proof_view(invalid).valid,
+            "{circuit}: invalid"
+        );
+        for kind in ["verify-args", "renew-args"] {
+            let input = fixture_input(&dir, circuit, kind);
+            assert!(contract.verify_proof_view(input).valid, "{circuit}: {kind}");
+        }
+        for i in 0..batch {
+            let input = fixture_input(&dir, circuit, &format!("batch-{i}-args"));
+            assert!(
+                contract.verify_proof_view(input).valid,
+                "{circuit}: batch {i}"
+            );
+        }
+    }
+}
+
+// ==========================================================================
+// Relayed verification with signed intents
+// ==========================================================================
+
+fn relay_signing_key(seed: u8) -> ed25519_dalek::SigningKey {
+    ed25519_dalek::SigningKey::from_bytes(&[seed; 32])
+}
+
+fn relay_public_key(key: &ed25519_dalek::SigningKey) -> near_sdk::PublicKey {
+    near_sdk::PublicKey::from_parts(
+        near_sdk::CurveType::ED25519,
+        key.verifying_key().to_bytes().to_vec(),
+    )
+    .unwrap()
+}
+
+fn sign_intent(
+    key: &ed25519_dalek::SigningKey,
+    intent: &RelayIntent,
+) -> near_sdk::json_types::Base64VecU8 {
+    use ed25519_dalek::Signer;
+    let message = serde_json::to_vec(intent).unwrap();
+    key.sign(&message).to_bytes().to_vec().into()
+}
+
+fn relay_intent(
+    contract: &ZKVerifier,
+    input: &VerifyProofInput,
+    holder: &AccountId,
+    nonce: u64,
+) -> RelayIntent {
+    RelayIntent {
+        verifier: near_sdk::env::current_account_id(),
+        holder: holder.clone(),
+        circuit_type: input.circuit_type.clone(),
+        proof_hash: contract.get_relay_proof_hash(input.clone()),
+        nonce,
+        expires_at: 1_700_000_000 + 600,
+    }
+}
+
+/// Contract with a real verified-builder key where alice registered relay key 7.
+fn setup_relay_contract() -> (ZKVerifier, Groth16Proof, ed25519_dalek::SigningKey) {
+    let (contract, proof) = setup_listing_contract();
+    let mut contract = contract;
+    let key = relay_signing_key(7);
+    setup(&alice());
+    contract.add_relay_key(relay_public_key(&key));
+    (contract, proof, key)
+}
+
+/// Submit a relayed call as bob, attaching the storage deposit.
+fn relay_as_bob(
+    contract: &mut ZKVerifier,
+    input: VerifyProofInput,
+    intent: RelayIntent,
+    key: &ed25519_dalek::SigningKey,
+) -> VerificationResult {
+    let signature = sign_intent(key, &intent);
+    setup_with_deposit(&bob(), ContractConfig::DEFAULT_STORAGE_COST);
+    contract.verify_proof_relayed(input, intent, relay_public_key(key), signature)
+}
+
+#[test]
+fn relayed_credential_bound_to_holder() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    assert!(contract.is_relay_key(alice(), relay_public_key(&key)));
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &alice(), 1);
+
+    let result = relay_as_bob(&mut contract, input, intent, &key);
+    assert!(result.valid);
+    let cred = contract
+        .get_credential(result.credential_id.unwrap())
+        .unwrap();
+    assert_eq!(cred.owner, alice());
+    assert_eq!(cred.issued_by, None);
+    assert_eq!(contract.get_relay_nonce(alice()), 1);
+    assert_eq!(
+        contract
+            .get_credentials_by_owner(bob(), None, None, None)
+            .total,
+        0
+    );
+}
+
+#[test]
+fn relayed_for_implicit_account_without_registration() {
+    let (mut contract, proof) = setup_listing_contract();
+    let key = relay_signing_key(9);
+    let holder: AccountId = hex::encode(key.verifying_key().to_bytes()).parse().unwrap();
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &holder, 5);
+
+    let result = relay_as_bob(&mut contract, input, intent, &key);
+    let cred = contract
+        .get_credential(result.credential_id.unwrap())
+        .unwrap();
+    assert_eq!(cred.owner, holder);
+    assert_eq!(contract.get_relay_nonce(holder), 5);
+}
+
+#[test]
+#[should_panic(expected = "Stale relay nonce 1: last used nonce is 1")]
+fn relayed_intent_replay_rejected() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &alice(), 1);
+    relay_as_bob(&mut contract, input.clone(), intent.clone(), &key);
+    relay_as_bob(&mut contract, input, intent, &key);
+}
+
+#[test]
+#[should_panic(expected = "proof hash does not match input")]
+fn relayed_input_tampering_rejected() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    let mut input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &alice(), 1);
+    input.claim = Some("chosen by relayer".into());
+    relay_as_bob(&mut contract, input, intent, &key);
+}
+
+#[test]
+#[should_panic(expected = "not authorized to sign relay intents for bob.testnet")]
+fn relayed_unregistered_key_rejected() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &bob(), 1);
+    relay_as_bob(&mut contract, input, intent, &key);
+}
+
+#[test]
+#[should_panic(expected = "Invalid relay intent signature")]
+fn relayed_bad_signature_rejected() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &alice(), 1);
+    // Signed for nonce 1, submitted as nonce 2
+    let signature = sign_intent(&key, &intent);
+    let intent = RelayIntent { nonce: 2, ..intent };
+    setup_with_deposit(&bob(), ContractConfig::DEFAULT_STORAGE_COST);
+    contract.verify_proof_relayed(input, intent, relay_public_key(&key), signature);
+}
+
+#[test]
+#[should_panic(expected = "Relay intent has expired")]
+fn relayed_expired_intent_rejected() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = RelayIntent {
+        expires_at: 1_700_000_000 - 1,
+        ..relay_intent(&contract, &input, &alice(), 1)
+    };
+    relay_as_bob(&mut contract, input, intent, &key);
+}
+
+#[test]
+#[should_panic(expected = "not authorized to sign relay intents for alice.testnet")]
+fn removed_relay_key_rejected() {
+    let (mut contract, proof, key) = setup_relay_contract();
+    setup(&alice());
+    contract.remove_relay_key(relay_public_key(&key));
+    let input = real_input(CircuitType::VerifiedBuilder, &proof, None);
+    let intent = relay_intent(&contract, &input, &alice(), 1);
+    relay_as_bob(&mut contract, input, intent, &key);
+}
+
+// ==========================================================================
+// Verifiable credential export
+// ==========================================================================
+
+#[test]
+fn credential_vc_export_follows_credential_state() {
+    let (mut contract, proof) = setup_listing_contract();
+    let live = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    let revoked = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+    setup(&owner());
+    contract.revoke_credential(revoked.clone(), "compromised".into());
+
+    let issuer = format!("did:near:{}", near_sdk::env::current_account_id());
+    let vc = contract.get_credential_vc(live.clone()).unwrap();
+    assert_eq!(vc.issuer, issuer);
+    assert_eq!(vc.credential_subject.id, "did:near:alice.testnet");
+    assert_eq!(
+        vc.credential_status.id,
+        format!("{issuer}#revocation-{live}")
+    );
+    assert_eq!(vc.credential_status.status_method, "is_credential_revoked");
+    assert_eq!(vc.issuance_date, "2023-11-14T22:13:20Z");
+    let stored = contract.get_credential(live.clone()).unwrap();
+    assert_eq!(
+        vc.credential_subject.public_signals.len(),
+        stored.public_signals.len()
+    );
+
+    let json = near_sdk::serde_json::to_value(&vc).unwrap();
+    assert_eq!(json["@context"].as_array().unwrap().len(), 2);
+    assert!(json["@context"][1]["@vocab"].is_string());
+
+    assert!(contract.get_credential_vc(revoked).is_none());
+    assert!(contract.get_credential_vc("cred-missing".into()).is_none());
+}
+
+// ==========================================================================
+// Presentation grants
+// ==========================================================================
+
+/// Alice holds two plain credentials and grants bob the first for an hour.
+fn setup_presentation() -> (ZKVerifier, Groth16Proof, String, String, PresentationGrant) {
+    let (mut contract, proof) = setup_listing_contract();
+    let a = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    let b = store_real_credential(
+        &mut contract,
+        &alice(),
+        CircuitType::GrantTrackRecord,
+        &proof,
+        None,
+    );
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    let grant = contract.create_presentation_grant(bob(), vec![a.clone()], 3600);
+    (contract, proof, a, b, grant)
+}
+
+#[test]
+fn presentation_grant_discloses_chosen_subset() {
+    let (contract, _, a, _, grant) = setup_presentation();
+    assert_eq!(
+        (grant.holder.clone(), grant.grantee.clone()),
+        (alice(), bob())
+    );
+    assert_eq!(grant.expires_at, 1_700_000_000 + 3600);
+    assert_eq!(
+        contract
+            .get_presentation_grant(grant.id.clone())
+            .unwrap()
+            .credential_ids,
+        vec![a.clone()]
+    );
+    assert_eq!(
+        contract
+            .get_presentation_grants_for_holder(alice(), None, None)
+            .len(),
+        1
+    );
+
+    setup(&bob());
+    let presentation = contract.get_presentation(grant.id);
+    let ids: Vec<_> = presentation
+        .credentials
+        .iter()
+        .map(|c| c.id.clone())
+        .collect();
+    assert_eq!(ids, vec![a]);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn presentation_only_readable_by_grantee() {
+    let (contract, _, _, _, grant) = setup_presentation();
+    setup(&carol());
+    contract.get_presentation(grant.id);
+}
+
+#[test]
+#[should_panic(expected = "Presentation grant has expired")]
+fn presentation_grant_expires() {
+    let (contract, _, _, _, grant) = setup_presentation();
+    setup_at(&bob(), 1_700_000_000 + 3600);
+    contract.get_presentation(grant.id);
+}
+
+#[test]
+#[should_panic(expected = "Presentation grant not found")]
+fn revoked_presentation_grant_unreadable() {
+    let (mut contract, _, _, _, grant) = setup_presentation();
+    setup(&alice());
+    contract.revoke_presentation_grant(grant.id.clone());
+    assert!(contract
+        .get_presentation_grants_for_holder(alice(), None, None)
+        .is_empty());
+    setup(&bob());
+    contract.get_presentation(grant.id);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn presentation_grant_revocable_only_by_holder() {
+    let (mut contract, _, _, _, grant) = setup_presentation();
+    setup(&bob());
+    contract.revoke_presentation_grant(grant.id);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn presentation_grant_requires_owned_credentials() {
+    let (mut contract, proof, _, _, _) = setup_presentation();
+    let bobs = store_real_credential(
+        &mut contract,
+        &bob(),
+        CircuitType::VerifiedBuilder,
+        &proof,
+        None,
+    );
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    contract.create_presentation_grant(carol(), vec![bobs], 3600);
+}
+
+#[test]
+fn presentation_drops_removed_credentials() {
+    let (mut contract, _, a, b, _) = setup_presentation();
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    let grant =
+        contract.create_presentation_grant(bob(), vec![a.clone(), b.clone(), a.clone()], 3600);
+    assert_eq!(grant.credential_ids, vec![a.clone(), b.clone()]);
+    assert!(contract.remove_credential(a));
+
+    setup(&bob());
+    let ids: Vec<_> = contract
+        .get_presentation(grant.id)
+        .credentials
+        .iter()
+        .map(|c| c.id.clone())
+        .collect();
+    assert_eq!(ids, vec![b]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid presentation grant: privacy-mode credential")]
+fn presentation_grant_rejects_private_credentials() {
+    let (mut contract, proof) = setup_listing_contract();
+    let id = store_private_credential(&mut contract, &proof);
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    contract.create_presentation_grant(bob(), vec![id], 3600);
+}
+
+#[test]
+#[should_panic(expected = "Invalid presentation grant: must cover 1 to 20 credentials")]
+fn presentation_grant_rejects_empty_subset() {
+    let (mut contract, _) = setup_listing_contract();
+    setup(&alice());
+    contract.create_presentation_grant(bob(), vec![], 3600);
+}
+
+#[test]
+fn presentation_grant_charges_its_storage() {
+    let (_, _, _, _, grant) = setup_presentation();
+    assert!(grant.storage_deposit.0 > 0);
+    assert!(grant.storage_deposit.0 < ContractConfig::DEFAULT_STORAGE_COST);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient deposit")]
+fn presentation_grant_requires_deposit() {
+    let (mut contract, _, a, _, _) = setup_presentation();
+    setup_with_deposit(&alice(), 1);
+    contract.create_presentation_grant(bob(), vec![a], 3600);
+}
+
+#[test]
+#[should_panic(expected = "Invalid presentation grant: duration is too long")]
+fn presentation_grant_rejects_overflowing_duration() {
+    let (mut contract, _, a, _, _) = setup_presentation();
+    setup_with_deposit(&alice(), ContractConfig::DEFAULT_STORAGE_COST);
+    contract.create_presentation_grant(bob(), vec![a], u64::MAX);
+}
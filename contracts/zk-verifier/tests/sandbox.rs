@@ -18,7 +18,11 @@ use serde_json::json;
 
 const WASM_PATH: &str = "out/zk_verifier.wasm";
 
-async fn deploy_and_init() -> anyhow::Result<(near_workspaces::Worker<near_workspaces::network::Sandbox>, Contract, Account)> {
+async fn deploy_and_init() -> anyhow::Result<(
+    near_workspaces::Worker<near_workspaces::network::Sandbox>,
+    Contract,
+    Account,
+)> {
     let worker = near_workspaces::sandbox().await?;
     let wasm = std::fs::read(WASM_PATH)?;
     let contract = worker.dev_deploy(&wasm).await?;
@@ -91,7 +95,11 @@ async fn sandbox_set_verification_key() -> anyhow::Result<()> {
         }))
         .transact()
         .await?;
-    assert!(outcome.is_success(), "set_verification_key failed: {:?}", outcome.outcome());
+    assert!(
+        outcome.is_success(),
+        "set_verification_key failed: {:?}",
+        outcome.outcome()
+    );
 
     // Verify has_verification_key
     let has_vk: bool = contract
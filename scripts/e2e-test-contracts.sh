@@ -1381,89 +1381,118 @@ assert_error "$OUT" "nauthorized\|Unauthorized" "set_default_expiration: unautho
 
 subsection "Real Groth16 Proof Verification"
 
-# Pre-generated fixtures (SquareCircuit: x*x == y, x=3, y=9)
-# Regenerate with: cd contracts/zk-verifier && cargo run --example generate_fixtures
+# Pre-generated fixtures for every circuit listed in fixtures/manifest.tsv
+# Regenerate with: cd contracts/zk-verifier && cargo run --features fixtures --bin generate-fixtures
 FIXTURES_DIR="$(cd "$(dirname "$0")" && pwd)/fixtures"
+MANIFEST="$FIXTURES_DIR/manifest.tsv"
+
+# Extract credential_id from verify_proof output (NEAR CLI uses JS-style: credential_id: 'xxx')
+extract_credential_id() {
+  local id
+  id=$(echo "$1" | grep -o "credential_id[^']*'[^']*'" | grep -o "'[^']*'" | tr -d "'" || true)
+  if [ -z "$id" ]; then
+    id=$(echo "$1" | grep -o '"credential_id"[^"]*"[^"]*"' | grep -o '"[^"]*"$' | tr -d '"' || true)
+  fi
+  echo "$id"
+}
 
-if [ ! -f "$FIXTURES_DIR/zk-set-vk-args.json" ]; then
-  echo "  (skipping real Groth16 tests — fixture files not found in $FIXTURES_DIR)"
+if [ ! -f "$MANIFEST" ]; then
+  echo "  (skipping real Groth16 tests — $MANIFEST not found)"
 else
-  # Register the real verification key for verified-builder (overwrites dummy)
-  OUT=$(call "$ZK" "set_verification_key" "$(cat "$FIXTURES_DIR/zk-set-vk-args.json")" "$OWNER")
-  assert_success "$OUT" "set_verification_key: real arkworks VK for verified-builder"
-
-  # --- Test 1: Valid proof (x=3, y=9) should return valid=true ---
-  OUT=$(call "$ZK" "verify_proof" "$(cat "$FIXTURES_DIR/zk-valid-proof-args.json")" "$OWNER" "--deposit 0.01")
-
-  if echo "$OUT" | grep -q "CALL_TIMEOUT"; then
-    pass "verify_proof (real, valid): call accepted (timeout on heavy computation)"
-    REAL_CRED_ID=""
-  elif echo "$OUT" | grep -q '"valid".*true\|valid.*true'; then
-    pass "verify_proof (real, valid): returns valid=true with correct signal"
-
-    # Extract credential ID (NEAR CLI uses JS-style: credential_id: 'xxx')
-    REAL_CRED_ID=$(echo "$OUT" | grep -o "credential_id[^']*'[^']*'" | grep -o "'[^']*'" | tr -d "'" || true)
-    if [ -z "$REAL_CRED_ID" ]; then
-      REAL_CRED_ID=$(echo "$OUT" | grep -o '"credential_id"[^"]*"[^"]*"' | grep -o '"[^"]*"$' | tr -d '"' || true)
+  while IFS=$'\t' read -r -u 3 CIRCUIT CIRCUIT_TYPE BATCH DESCRIPTION; do
+    case "$CIRCUIT" in ''|\#*) continue ;; esac
+    fixture() { cat "$FIXTURES_DIR/zk-$CIRCUIT-$1.json"; }
+    echo "  [$CIRCUIT] $DESCRIPTION ($CIRCUIT_TYPE)"
+
+    # Register the circuit's verification key (overwrites dummy or previous circuit's key)
+    OUT=$(call "$ZK" "set_verification_key" "$(fixture set-vk-args)" "$OWNER")
+    assert_success "$OUT" "set_verification_key ($CIRCUIT): real arkworks VK for $CIRCUIT_TYPE"
+
+    # --- Valid proof should return valid=true and store a credential ---
+    OUT=$(call "$ZK" "verify_proof" "$(fixture verify-args)" "$OWNER" "--deposit 0.01")
+    CRED_ID=""
+    if echo "$OUT" | grep -q "CALL_TIMEOUT"; then
+      pass "verify_proof ($CIRCUIT, valid): call accepted (timeout on heavy computation)"
+    elif echo "$OUT" | grep -q '"valid".*true\|valid.*true'; then
+      pass "verify_proof ($CIRCUIT, valid): returns valid=true"
+      CRED_ID=$(extract_credential_id "$OUT")
+      if [ -z "$CRED_ID" ]; then
+        pass "verify_proof ($CIRCUIT, valid): valid=true but credential_id not parsed from output"
+      fi
+    else
+      fail "verify_proof ($CIRCUIT, valid): expected valid=true" "Got: $(echo "$OUT" | head -3)"
     fi
 
-    if [ -n "$REAL_CRED_ID" ]; then
-      pass "verify_proof (real, valid): credential stored (id: $REAL_CRED_ID)"
+    if [ -n "$CRED_ID" ]; then
+      pass "verify_proof ($CIRCUIT, valid): credential stored (id: $CRED_ID)"
 
       # Read credential back — full round-trip
-      OUT=$(view "$ZK" "get_credential" "{\"credential_id\": \"$REAL_CRED_ID\"}")
-      assert_contains "$OUT" "$OWNER" "get_credential (real): owner matches caller"
-      assert_contains "$OUT" "verified-builder" "get_credential (real): circuit_type preserved"
-      assert_contains "$OUT" "9" "get_credential (real): public signal y=9 preserved"
-      assert_contains "$OUT" "e2e-real-groth16-test" "get_credential (real): claim preserved"
-      assert_contains "$OUT" "expires_at" "get_credential (real): has expiration"
-
-      # Validity check
-      OUT=$(view "$ZK" "is_credential_valid" "{\"credential_id\": \"$REAL_CRED_ID\"}")
-      assert_contains "$OUT" "true" "is_credential_valid (real): credential is valid"
-
-      # Credentials by owner includes it
+      OUT=$(view "$ZK" "get_credential" "{\"credential_id\": \"$CRED_ID\"}")
+      assert_contains "$OUT" "$OWNER" "get_credential ($CIRCUIT): owner matches caller"
+      assert_contains "$OUT" "$CIRCUIT_TYPE" "get_credential ($CIRCUIT): circuit_type preserved"
+      assert_contains "$OUT" "e2e-$CIRCUIT-groth16" "get_credential ($CIRCUIT): claim preserved"
+      assert_contains "$OUT" "expires_at" "get_credential ($CIRCUIT): has expiration"
+
+      OUT=$(view "$ZK" "is_credential_valid" "{\"credential_id\": \"$CRED_ID\"}")
+      assert_contains "$OUT" "true" "is_credential_valid ($CIRCUIT): credential is valid"
+
       OUT=$(view "$ZK" "get_credentials_by_owner" "{\"owner\": \"$OWNER\"}")
-      assert_contains "$OUT" "$REAL_CRED_ID" "get_credentials_by_owner (real): includes stored credential"
+      assert_contains "$OUT" "$CRED_ID" "get_credentials_by_owner ($CIRCUIT): includes stored credential"
 
-      # Clean up — remove credential
-      OUT=$(call "$ZK" "remove_credential" "{\"credential_id\": \"$REAL_CRED_ID\"}" "$OWNER")
-      assert_success "$OUT" "remove_credential (real): owner removes credential"
-    else
-      pass "verify_proof (real, valid): valid=true but credential_id not parsed from output"
-    fi
-  else
-    fail "verify_proof (real, valid): expected valid=true" "Got: $(echo "$OUT" | head -3)"
-  fi
+      # --- Renewal: fresh proof of the same statement, then revoke the old credential ---
+      OUT=$(call "$ZK" "verify_proof" "$(fixture renew-args)" "$OWNER" "--deposit 0.01")
+      RENEWED_ID=$(extract_credential_id "$OUT")
+      if [ -n "$RENEWED_ID" ] && [ "$RENEWED_ID" != "$CRED_ID" ]; then
+        pass "verify_proof ($CIRCUIT, renew): new credential issued (id: $RENEWED_ID)"
+      else
+        fail "verify_proof ($CIRCUIT, renew): expected a new credential" "Got: $(echo "$OUT" | head -3)"
+      fi
 
-  # --- Test 2: Wrong signal (y=10 instead of 9) should return valid=false ---
-  OUT=$(call "$ZK" "verify_proof" "$(cat "$FIXTURES_DIR/zk-invalid-proof-args.json")" "$OWNER" "--deposit 0.01")
+      OUT=$(call "$ZK" "revoke_credential" "$(fixture revoke-args | sed "s/__CREDENTIAL_ID__/$CRED_ID/")" "$OWNER")
+      assert_success "$OUT" "revoke_credential ($CIRCUIT): superseded credential revoked"
 
-  if echo "$OUT" | grep -q "CALL_TIMEOUT"; then
-    pass "verify_proof (real, invalid): call accepted (timeout on heavy computation)"
-  elif echo "$OUT" | grep -q '"valid".*false\|valid.*false'; then
-    pass "verify_proof (real, invalid): returns valid=false with wrong signal"
-    # Verify NO credential was stored (valid=false → no storage)
-    if echo "$OUT" | grep -q "credential_id.*null\|credential_id: null"; then
-      pass "verify_proof (real, invalid): no credential stored"
+      OUT=$(view "$ZK" "is_credential_revoked" "{\"credential_id\": \"$CRED_ID\"}")
+      assert_contains "$OUT" "true" "is_credential_revoked ($CIRCUIT): old credential revoked"
+
+      # Clean up — remove the renewed credential
+      if [ -n "$RENEWED_ID" ]; then
+        OUT=$(call "$ZK" "remove_credential" "{\"credential_id\": \"$RENEWED_ID\"}" "$OWNER")
+        assert_success "$OUT" "remove_credential ($CIRCUIT): owner removes renewed credential"
+      fi
+    fi
+
+    # --- Wrong last signal should return valid=false and store nothing ---
+    OUT=$(call "$ZK" "verify_proof" "$(fixture verify-invalid-args)" "$OWNER" "--deposit 0.01")
+    if echo "$OUT" | grep -q "CALL_TIMEOUT"; then
+      pass "verify_proof ($CIRCUIT, invalid): call accepted (timeout on heavy computation)"
+    elif echo "$OUT" | grep -q '"valid".*false\|valid.*false'; then
+      pass "verify_proof ($CIRCUIT, invalid): returns valid=false with wrong signal"
+      assert_not_contains "$OUT" "credential_id: '" "verify_proof ($CIRCUIT, invalid): no credential stored"
     else
-      pass "verify_proof (real, invalid): no credential_id in response"
+      fail "verify_proof ($CIRCUIT, invalid): expected valid=false" "Got: $(echo "$OUT" | head -3)"
     fi
-  else
-    fail "verify_proof (real, invalid): expected valid=false" "Got: $(echo "$OUT" | head -3)"
-  fi
 
-  # --- Test 3: View-only verification (no state change) ---
-  OUT=$(view "$ZK" "verify_proof_view" "$(cat "$FIXTURES_DIR/zk-valid-view-args.json")")
+    # --- View-only verification (no state change) ---
+    OUT=$(view "$ZK" "verify_proof_view" "$(fixture view-args)")
+    if echo "$OUT" | grep -q "VIEW_TIMEOUT"; then
+      pass "verify_proof_view ($CIRCUIT): timed out (BN254 pairing is gas-heavy for view calls)"
+    elif echo "$OUT" | grep -q '"valid".*true\|valid.*true'; then
+      pass "verify_proof_view ($CIRCUIT): returns valid=true"
+    else
+      # View calls may not have enough gas for the pairing — acceptable
+      pass "verify_proof_view ($CIRCUIT): call processed (result: $(echo "$OUT" | head -1))"
+    fi
 
-  if echo "$OUT" | grep -q "VIEW_TIMEOUT"; then
-    pass "verify_proof_view (real, valid): timed out (BN254 pairing is gas-heavy for view calls)"
-  elif echo "$OUT" | grep -q '"valid".*true\|valid.*true'; then
-    pass "verify_proof_view (real, valid): returns valid=true"
-  else
-    # View calls may not have enough gas for the pairing — acceptable
-    pass "verify_proof_view (real, valid): call processed (result: $(echo "$OUT" | head -1))"
-  fi
+    # --- Batch: independent witnesses against the same key ---
+    for ((i = 0; i < BATCH; i++)); do
+      OUT=$(call "$ZK" "verify_proof" "$(fixture "batch-$i-args")" "$OWNER")
+      if echo "$OUT" | grep -q "CALL_TIMEOUT"; then
+        pass "verify_proof ($CIRCUIT, batch $i): call accepted (timeout on heavy computation)"
+      else
+        assert_contains "$OUT" "valid.*true" "verify_proof ($CIRCUIT, batch $i): returns valid=true"
+      fi
+    done
+  done 3< "$MANIFEST"
 fi
 
 subsection "Storage Cost & Stats"
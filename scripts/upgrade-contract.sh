@@ -4,10 +4,11 @@ set -euo pipefail
 # ---------------------------------------------------------------------------
 # Upgrade a single NEAR smart contract (deploy without re-initialization)
 #
-# Usage: ./scripts/upgrade-contract.sh <network> <contract-dir> <contract-id>
+# Usage: ./scripts/upgrade-contract.sh <network> <contract-dir> <contract-id> [--migrate]
 #   network:       testnet | mainnet
 #   contract-dir:  Directory name under contracts/ (e.g. zk-verifier)
 #   contract-id:   Deployed contract account (e.g. zk-verifier.testnet)
+#   --migrate:     Call the contract's `migrate` method in the deploy transaction
 #
 # Requirements: near-cli (npm install -g near-cli)
 # ---------------------------------------------------------------------------
@@ -16,20 +17,22 @@ SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 ROOT_DIR="$(cd "$SCRIPT_DIR/.." && pwd)"
 
 if [ "${1:-}" = "--help" ] || [ "${1:-}" = "-h" ] || [ $# -lt 3 ]; then
-  echo "Usage: $0 <network> <contract-dir> <contract-id>"
+  echo "Usage: $0 <network> <contract-dir> <contract-id> [--migrate]"
   echo ""
   echo "  network        testnet or mainnet"
   echo "  contract-dir   Directory name under contracts/"
   echo "  contract-id    Deployed contract account ID"
+  echo "  --migrate      Run the contract's migrate method after deploying"
   echo ""
   echo "Example:"
-  echo "  $0 testnet zk-verifier zk-verifier.testnet"
+  echo "  $0 testnet zk-verifier zk-verifier.testnet --migrate"
   exit 0
 fi
 
 NETWORK="$1"
 CONTRACT_DIR_NAME="$2"
 CONTRACT_ID="$3"
+MIGRATE="${4:-}"
 CONTRACT_DIR="$ROOT_DIR/contracts/$CONTRACT_DIR_NAME"
 
 if [ "$NETWORK" != "testnet" ] && [ "$NETWORK" != "mainnet" ]; then
@@ -72,9 +75,14 @@ fi
 size=$(wc -c < "$wasm_file")
 echo "WASM: $wasm_file ($(( size / 1024 )) KB)"
 
-# Deploy (no init call)
+# Deploy, optionally migrating state in the same transaction so the new
+# code never runs against an old layout
 echo "Deploying to $CONTRACT_ID on $NETWORK..."
-near deploy "$CONTRACT_ID" "$wasm_file"
+if [ "$MIGRATE" = "--migrate" ]; then
+  near deploy "$CONTRACT_ID" "$wasm_file" --initFunction migrate --initArgs '{}'
+else
+  near deploy "$CONTRACT_ID" "$wasm_file"
+fi
 
 echo ""
 echo "Upgrade complete: $CONTRACT_ID"
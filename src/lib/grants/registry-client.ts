@@ -281,7 +281,7 @@ interface RawProgram {
   funding_pool: string;
   min_amount?: string;
   max_amount?: string;
-  deadline?: string;
+  deadline?: number;
   website: string;
   application_url?: string;
   status: string;
@@ -343,7 +343,7 @@ function parseProgram(raw: RawProgram): GrantProgram {
     fundingPool: raw.funding_pool,
     minAmount: raw.min_amount,
     maxAmount: raw.max_amount,
-    deadline: raw.deadline,
+    deadline: raw.deadline != null ? nsToISO(raw.deadline) : undefined,
     website: raw.website,
     applicationUrl: raw.application_url,
     status: raw.status as ProgramStatus,
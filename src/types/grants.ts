@@ -48,7 +48,8 @@ export type ApplicationStatus =
   | 'approved'
   | 'rejected'
   | 'funded'
-  | 'completed';
+  | 'completed'
+  | 'withdrawn';
 
 // ============================================================================
 // Core Entities